@@ -5,54 +5,105 @@ fn main() {}
 fn main() {
     use std::{env, io::Write, path::Path};
 
-    use common::{generate_impls_for_n_args, ArgExprs};
+    use common::{generate_impls_for_n_args, ArgExprs, ARG_COUNT_MAX};
 
     let out_dir = env::var_os("OUT_DIR").expect("Failed to read `OUT_DIR` environment variable.");
     let out_dir = Path::new(&out_dir);
 
-    #[cfg(feature = "fn_res")]
     let mut fn_resource_impl = common::open_impl_file(out_dir, "fn_resource_impl.rs");
     #[cfg(feature = "fn_res_once")]
     let mut fn_res_once_impl = common::open_impl_file(out_dir, "fn_res_once_impl.rs");
     #[cfg(feature = "fn_res_mut")]
     let mut fn_res_mut_impl = common::open_impl_file(out_dir, "fn_res_mut_impl.rs");
-    #[cfg(feature = "fn_res")]
     let mut fn_res_impl = common::open_impl_file(out_dir, "fn_res_impl.rs");
+    let mut fn_res_local_impl = common::open_impl_file(out_dir, "fn_res_local_impl.rs");
+
+    #[cfg(feature = "fn_res_once")]
+    let mut into_fn_res_once_impl = common::open_impl_file(out_dir, "into_fn_res_once_impl.rs");
+    #[cfg(feature = "fn_res_mut")]
+    let mut into_fn_res_mut_impl = common::open_impl_file(out_dir, "into_fn_res_mut_impl.rs");
+    let mut into_fn_res_impl = common::open_impl_file(out_dir, "into_fn_res_impl.rs");
+    let mut into_fn_res_local_impl = common::open_impl_file(out_dir, "into_fn_res_local_impl.rs");
 
     #[cfg(feature = "fn_meta")]
     let mut fn_resource_meta_impl = common::open_impl_file(out_dir, "fn_resource_meta_impl.rs");
 
     let mut write_fn = |arg_exprs: ArgExprs<'_>| {
-        #[cfg(feature = "fn_res")]
         fn_resource_impl::write_fn_resource_impl(&mut fn_resource_impl, arg_exprs);
 
         #[cfg(feature = "fn_res_once")]
         fn_res_once_impl::write_fn_res_once_impl(&mut fn_res_once_impl, arg_exprs);
         #[cfg(feature = "fn_res_mut")]
         fn_res_mut_impl::write_fn_res_mut_impl(&mut fn_res_mut_impl, arg_exprs);
-        #[cfg(feature = "fn_res")]
         fn_res_impl::write_fn_res_impl(&mut fn_res_impl, arg_exprs);
+        fn_res_local_impl::write_fn_res_local_impl(&mut fn_res_local_impl, arg_exprs);
+        fn_res_local_impl::write_into_fn_res_local_impl(&mut into_fn_res_local_impl, arg_exprs);
+
+        #[cfg(feature = "fn_res_once")]
+        into_fn_res_impl::write_into_fn_res_impl(
+            &mut into_fn_res_once_impl,
+            arg_exprs,
+            "IntoFnResOnce",
+            "FnResOnce",
+            "FnOnce",
+            "into_fn_res_once",
+        );
+        #[cfg(feature = "fn_res_mut")]
+        into_fn_res_impl::write_into_fn_res_impl(
+            &mut into_fn_res_mut_impl,
+            arg_exprs,
+            "IntoFnResMut",
+            "FnResMut",
+            "FnMut",
+            "into_fn_res_mut",
+        );
+        into_fn_res_impl::write_into_fn_res_impl(
+            &mut into_fn_res_impl,
+            arg_exprs,
+            "IntoFnRes",
+            "FnRes",
+            "Fn",
+            "into_fn_res",
+        );
 
         #[cfg(feature = "fn_meta")]
         fn_resource_meta_impl::write_fn_resource_meta_impl(&mut fn_resource_meta_impl, arg_exprs);
     };
 
-    generate_impls_for_n_args::<_, 1>(&mut write_fn);
-    generate_impls_for_n_args::<_, 2>(&mut write_fn);
-    generate_impls_for_n_args::<_, 3>(&mut write_fn);
-    generate_impls_for_n_args::<_, 4>(&mut write_fn);
-    generate_impls_for_n_args::<_, 5>(&mut write_fn);
-    generate_impls_for_n_args::<_, 6>(&mut write_fn);
+    (1..=ARG_COUNT_MAX).for_each(|n| generate_impls_for_n_args(n, &mut write_fn));
 
-    #[cfg(feature = "high_arg_count")]
-    generate_impls_for_n_args::<_, 7>(&mut write_fn);
-    #[cfg(feature = "high_arg_count")]
-    generate_impls_for_n_args::<_, 8>(&mut write_fn);
-
-    #[cfg(feature = "fn_res")]
     fn_resource_impl
         .flush()
         .expect("Failed to flush writer for fn_resource_impl.rs");
+    #[cfg(feature = "fn_res_once")]
+    fn_res_once_impl
+        .flush()
+        .expect("Failed to flush writer for fn_res_once_impl.rs");
+    #[cfg(feature = "fn_res_mut")]
+    fn_res_mut_impl
+        .flush()
+        .expect("Failed to flush writer for fn_res_mut_impl.rs");
+    fn_res_impl
+        .flush()
+        .expect("Failed to flush writer for fn_res_impl.rs");
+    fn_res_local_impl
+        .flush()
+        .expect("Failed to flush writer for fn_res_local_impl.rs");
+
+    #[cfg(feature = "fn_res_once")]
+    into_fn_res_once_impl
+        .flush()
+        .expect("Failed to flush writer for into_fn_res_once_impl.rs");
+    #[cfg(feature = "fn_res_mut")]
+    into_fn_res_mut_impl
+        .flush()
+        .expect("Failed to flush writer for into_fn_res_mut_impl.rs");
+    into_fn_res_impl
+        .flush()
+        .expect("Failed to flush writer for into_fn_res_impl.rs");
+    into_fn_res_local_impl
+        .flush()
+        .expect("Failed to flush writer for into_fn_res_local_impl.rs");
 
     #[cfg(feature = "fn_meta")]
     fn_resource_meta_impl
@@ -62,7 +113,7 @@ fn main() {
     println!("cargo:rerun-if-changed=build.rs");
 }
 
-#[cfg(any(feature = "fn_meta", feature = "fn_res"))]
+#[cfg(feature = "fn_res")]
 mod common {
     use std::{
         fmt::Write as _,
@@ -71,20 +122,41 @@ mod common {
         path::Path,
     };
 
+    /// Maximum number of arguments that resource functions are implemented
+    /// for.
+    ///
+    /// Since each parameter kind implements `FetchParam`, the number of
+    /// generated impls grows linearly with this number.
+    pub const ARG_COUNT_MAX: usize = 16;
+
     #[derive(Clone, Copy, Debug)]
     pub struct ArgExprs<'s> {
         pub args_csv: &'s str,
-        pub arg_refs_csv: &'s str,
+        pub args_tuple: &'s str,
+        pub arg_items_csv: &'s str,
         pub arg_bounds_list: &'s str,
-        pub resource_arg_borrows: &'s str,
-        pub resource_arg_try_borrows: &'s str,
+        pub arg_stateless_bounds_list: &'s str,
+        pub arg_static_bounds_list: &'s str,
+        pub arg_local_bounds_list: &'s str,
+        pub arg_local_static_bounds_list: &'s str,
+        #[cfg(any(feature = "fn_res_mut", feature = "fn_res_once"))]
+        pub arg_states_tuple: &'s str,
+        pub arg_states_default: &'s str,
+        pub arg_states_unit: &'s str,
+        pub state_vars_pattern: &'s str,
+        pub resource_arg_fetches: &'s str,
+        pub resource_arg_try_fetches: &'s str,
         pub resource_arg_vars: &'s str,
-    }
-
-    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-    enum Ref {
-        Immutable,
-        Mutable,
+        pub resource_arg_local_fetches: &'s str,
+        pub resource_arg_local_try_fetches: &'s str,
+        pub resource_arg_local_vars: &'s str,
+        pub arg_type_name_pushes: &'s str,
+        pub arg_borrow_type_names: &'s str,
+        pub arg_borrow_mut_type_names: &'s str,
+        #[cfg(feature = "fn_meta")]
+        pub arg_borrows: &'s str,
+        #[cfg(feature = "fn_meta")]
+        pub arg_borrow_muts: &'s str,
     }
 
     pub fn open_impl_file(out_dir: &Path, file_name: &str) -> BufWriter<File> {
@@ -98,180 +170,273 @@ mod common {
         BufWriter::new(fn_resource_impl)
     }
 
-    pub fn generate_impls_for_n_args<FnWrite, const N: usize>(fn_write: &mut FnWrite)
+    pub fn generate_impls_for_n_args<FnWrite>(n: usize, fn_write: &mut FnWrite)
     where
         FnWrite: FnMut(ArgExprs<'_>),
     {
         // "A0, A1"
-        let args_csv = args_csv::<N>();
-
-        // "    A0: 'static,\n    A1: 'static,"
-        let arg_bounds_list = arg_bounds_list::<N>();
-
-        arg_refs_combinations::<N>().for_each(|arg_refs| {
-            let mut arg_refs_iter = arg_refs.iter().copied().enumerate();
-
-            // &mut A0, &A1
-            let arg_refs_csv = {
-                let mut arg_refs_csv = String::with_capacity(N * 8);
-                if let Some((_index, arg_ref_first)) = arg_refs_iter.next() {
-                    match arg_ref_first {
-                        Ref::Immutable => arg_refs_csv.push_str("&A0"),
-                        Ref::Mutable => arg_refs_csv.push_str("&mut A0"),
-                    }
-                }
+        let args_csv = args_csv(n);
 
-                if N == 1 {
-                    arg_refs_csv.push(',');
-                } else {
-                    arg_refs_iter
-                        .try_for_each(|(index, arg_ref)| match arg_ref {
-                            Ref::Immutable => write!(&mut arg_refs_csv, ", &A{index}"),
-                            Ref::Mutable => write!(&mut arg_refs_csv, ", &mut A{index}"),
-                        })
-                        .expect("Failed to append to `arg_refs_csv` string.");
-                }
+        // "(A0,)", "(A0, A1)"
+        let args_tuple = if n == 1 {
+            format!("({args_csv},)")
+        } else {
+            format!("({args_csv})")
+        };
 
-                arg_refs_csv
-            };
-
-            // let a0 = resources.borrow::<A0>();
-            // let mut a1 = resources.borrow_mut::<A1>();
-            // ..
-            let resource_arg_borrows = resource_arg_borrows(arg_refs);
-            let resource_arg_try_borrows = resource_arg_try_borrows(arg_refs);
-
-            // &*a0, &mut *a1
-            let resource_arg_vars = resource_arg_vars::<N>(arg_refs);
-
-            let args_csv = args_csv.as_str();
-            let arg_refs_csv = arg_refs_csv.as_str();
-            let arg_bounds_list = arg_bounds_list.as_str();
-            let resource_arg_borrows = resource_arg_borrows.as_str();
-            let resource_arg_try_borrows = resource_arg_try_borrows.as_str();
-            let resource_arg_vars = resource_arg_vars.as_str();
-
-            let arg_exprs = ArgExprs {
-                args_csv,
-                arg_refs_csv,
-                arg_bounds_list,
-                resource_arg_borrows,
-                resource_arg_try_borrows,
-                resource_arg_vars,
-            };
-
-            fn_write(arg_exprs);
-        })
+        // "A0::Item<'f>, A1::Item<'f>"
+        let arg_items_csv = arg_items_csv(n);
+
+        // "    A0: FetchParam + 'static,\n    A1: FetchParam + 'static,"
+        let arg_static_bounds_list = arg_bounds(n, "FetchParam + 'static");
+
+        // "    A0: FetchParam,\n    A1: FetchParam,"
+        let arg_bounds_list = arg_bounds(n, "FetchParam");
+
+        // "    A0: FetchParam<State = ()>,\n    A1: FetchParam<State = ()>,"
+        let arg_stateless_bounds_list = arg_bounds(n, "FetchParam<State = ()>");
+
+        // "    A0: FetchParamLocal,\n    A1: FetchParamLocal,"
+        let arg_local_bounds_list = arg_bounds(n, "FetchParamLocal");
+
+        // "    A0: FetchParamLocal + 'static,\n    A1: FetchParamLocal + 'static,"
+        let arg_local_static_bounds_list = arg_bounds(n, "FetchParamLocal + 'static");
+
+        // "(A0::State, A1::State)"
+        #[cfg(any(feature = "fn_res_mut", feature = "fn_res_once"))]
+        let arg_states_tuple = tuple_of(n, |index| format!("A{index}::State"));
+
+        // "(A0::State::default(), A1::State::default())"
+        let arg_states_default = tuple_of(n, |index| format!("A{index}::State::default()"));
+
+        // "((), ())"
+        let arg_states_unit = tuple_of(n, |_index| String::from("()"));
+
+        // "(s0, s1)"
+        let state_vars_pattern = tuple_of(n, |index| format!("s{index}"));
+
+        // let mut a0 = A0::fetch_local(resources);
+        // let mut a1 = A1::fetch_local(resources);
+        // ..
+        let resource_arg_local_fetches = resource_arg_fetches(n, "fetch_local", false);
+        let resource_arg_local_try_fetches = resource_arg_try_fetches(n, "try_fetch_local", false);
+
+        // let mut a0 = A0::fetch(resources, s0);
+        // let mut a1 = A1::fetch(resources, s1);
+        // ..
+        let resource_arg_fetches = resource_arg_fetches(n, "fetch", true);
+        let resource_arg_try_fetches = resource_arg_try_fetches(n, "try_fetch", true);
+
+        // A0::item(&mut a0), A1::item(&mut a1)
+        let resource_arg_local_vars = resource_arg_vars(n, "item_local");
+        let resource_arg_vars = resource_arg_vars(n, "item");
+
+        // A0::borrow_type_names(borrows);
+        // A0::borrow_mut_type_names(borrow_muts);
+        // ..
+        let arg_type_name_pushes = arg_type_name_pushes(n);
+
+        // A0::borrow_type_names(&mut type_names);
+        // A1::borrow_type_names(&mut type_names);
+        // ..
+        let arg_borrow_type_names = arg_type_name_calls(n, "borrow_type_names");
+        let arg_borrow_mut_type_names = arg_type_name_calls(n, "borrow_mut_type_names");
+
+        // A0::borrows(&mut type_ids);
+        // A1::borrows(&mut type_ids);
+        // ..
+        #[cfg(feature = "fn_meta")]
+        let arg_borrows = arg_meta_calls(n, "borrows");
+        #[cfg(feature = "fn_meta")]
+        let arg_borrow_muts = arg_meta_calls(n, "borrow_muts");
+
+        let arg_exprs = ArgExprs {
+            args_csv: &args_csv,
+            args_tuple: &args_tuple,
+            arg_items_csv: &arg_items_csv,
+            arg_bounds_list: &arg_bounds_list,
+            arg_stateless_bounds_list: &arg_stateless_bounds_list,
+            arg_static_bounds_list: &arg_static_bounds_list,
+            arg_local_bounds_list: &arg_local_bounds_list,
+            arg_local_static_bounds_list: &arg_local_static_bounds_list,
+            #[cfg(any(feature = "fn_res_mut", feature = "fn_res_once"))]
+            arg_states_tuple: &arg_states_tuple,
+            arg_states_default: &arg_states_default,
+            arg_states_unit: &arg_states_unit,
+            state_vars_pattern: &state_vars_pattern,
+            resource_arg_fetches: &resource_arg_fetches,
+            resource_arg_try_fetches: &resource_arg_try_fetches,
+            resource_arg_vars: &resource_arg_vars,
+            resource_arg_local_fetches: &resource_arg_local_fetches,
+            resource_arg_local_try_fetches: &resource_arg_local_try_fetches,
+            resource_arg_local_vars: &resource_arg_local_vars,
+            arg_type_name_pushes: &arg_type_name_pushes,
+            arg_borrow_type_names: &arg_borrow_type_names,
+            arg_borrow_mut_type_names: &arg_borrow_mut_type_names,
+            #[cfg(feature = "fn_meta")]
+            arg_borrows: &arg_borrows,
+            #[cfg(feature = "fn_meta")]
+            arg_borrow_muts: &arg_borrow_muts,
+        };
+
+        fn_write(arg_exprs);
     }
 
-    fn resource_arg_vars<const N: usize>(arg_refs: [Ref; N]) -> String {
-        let mut resource_arg_vars = String::with_capacity(N * 10);
-        let mut arg_refs_iter = arg_refs.iter().copied().enumerate();
-        if let Some((index, arg_ref)) = arg_refs_iter.next() {
-            match arg_ref {
-                Ref::Immutable => write!(&mut resource_arg_vars, "&*a{index}"),
-                Ref::Mutable => write!(&mut resource_arg_vars, "&mut *a{index}"),
-            }
-            .expect("Failed to append to `resource_arg_vars` string.")
-        }
-        arg_refs_iter
-            .try_for_each(|(index, arg_ref)| match arg_ref {
-                Ref::Immutable => write!(&mut resource_arg_vars, ", &*a{index}"),
-                Ref::Mutable => write!(&mut resource_arg_vars, ", &mut *a{index}"),
+    fn resource_arg_vars(n: usize, item_fn_name: &str) -> String {
+        let mut resource_arg_vars = String::with_capacity(n * 28);
+        (0..n)
+            .try_for_each(|index| {
+                if index != 0 {
+                    resource_arg_vars.push_str(", ");
+                }
+                write!(
+                    &mut resource_arg_vars,
+                    "A{index}::{item_fn_name}(&mut a{index})"
+                )
             })
             .expect("Failed to append to `resource_arg_vars` string.");
         resource_arg_vars
     }
 
-    fn resource_arg_borrows<const N: usize>(arg_refs: [Ref; N]) -> String {
-        let mut resource_arg_borrows = String::with_capacity(N * 44);
-        let mut arg_refs_iter = arg_refs.iter().copied().enumerate();
-        arg_refs_iter
-            .try_for_each(|(index, arg_ref)| match arg_ref {
-                Ref::Immutable => writeln!(
-                    &mut resource_arg_borrows,
-                    "let a{index} = resources.borrow::<A{index}>();",
-                ),
-                Ref::Mutable => writeln!(
-                    &mut resource_arg_borrows,
-                    "let mut a{index} = resources.borrow_mut::<A{index}>();",
-                ),
+    fn resource_arg_fetches(n: usize, fetch_fn_name: &str, with_state: bool) -> String {
+        let mut resource_arg_fetches = String::with_capacity(n * 50);
+        (0..n)
+            .try_for_each(|index| {
+                let state_arg = state_arg(index, with_state);
+                writeln!(
+                    &mut resource_arg_fetches,
+                    "let mut a{index} = A{index}::{fetch_fn_name}(resources{state_arg});",
+                )
             })
-            .expect("Failed to append to `resource_arg_borrows` string.");
-        resource_arg_borrows
+            .expect("Failed to append to `resource_arg_fetches` string.");
+        resource_arg_fetches
     }
 
-    fn resource_arg_try_borrows<const N: usize>(arg_refs: [Ref; N]) -> String {
-        let mut resource_arg_try_borrows = String::with_capacity(N * 44);
-        let mut arg_refs_iter = arg_refs.iter().copied().enumerate();
-        arg_refs_iter
-            .try_for_each(|(index, arg_ref)| match arg_ref {
-                Ref::Immutable => writeln!(
-                    &mut resource_arg_try_borrows,
-                    "let a{index} = resources.try_borrow::<A{index}>()?;",
-                ),
-                Ref::Mutable => writeln!(
-                    &mut resource_arg_try_borrows,
-                    "let mut a{index} = resources.try_borrow_mut::<A{index}>()?;",
-                ),
+    fn resource_arg_try_fetches(n: usize, try_fetch_fn_name: &str, with_state: bool) -> String {
+        let mut resource_arg_try_fetches = String::with_capacity(n * 54);
+        (0..n)
+            .try_for_each(|index| {
+                let state_arg = state_arg(index, with_state);
+                writeln!(
+                    &mut resource_arg_try_fetches,
+                    "let mut a{index} = A{index}::{try_fetch_fn_name}(resources{state_arg})?;",
+                )
             })
-            .expect("Failed to append to `resource_arg_try_borrows` string.");
-        resource_arg_try_borrows
+            .expect("Failed to append to `resource_arg_try_fetches` string.");
+        resource_arg_try_fetches
     }
 
-    fn arg_refs_combinations<const N: usize>() -> impl Iterator<Item = [Ref; N]> {
-        (0..(2 << (N - 1))).map(|m| {
-            // `m` is the combination variation count.
-            // Whether an argument is immutable or mutable is bed on its corresponding bit
-            // value of `m`.
-
-            let mut arg_refs: [Ref; N] = [Ref::Immutable; N];
-            arg_refs
-                .iter_mut()
-                .enumerate()
-                .for_each(move |(n, arg_ref)| {
-                    // for N = 5
-                    // m can be 0..32
-                    // if 31 >> 5 is 0
-
-                    if (m >> n) & 1 == 0 {
-                        *arg_ref = Ref::Immutable;
-                    } else {
-                        *arg_ref = Ref::Mutable;
-                    }
-                });
-
-            arg_refs
-        })
+    /// Returns the state argument to pass to the parameter's fetch function,
+    /// e.g. `", s0"`.
+    fn state_arg(index: usize, with_state: bool) -> String {
+        if with_state {
+            format!(", s{index}")
+        } else {
+            String::new()
+        }
     }
 
-    fn arg_bounds_list<const N: usize>() -> String {
-        let mut arg_bounds_list = String::with_capacity(N * 50);
-        #[cfg(feature = "debug")]
-        arg_bounds_list.push_str("    A0: std::fmt::Debug + Send + Sync + 'static,");
-
-        #[cfg(not(feature = "debug"))]
-        arg_bounds_list.push_str("    A0: Send + Sync + 'static,");
-        (1..N).fold(arg_bounds_list, |mut arg_bounds_list, n| {
-            #[cfg(feature = "debug")]
-            write!(
-                &mut arg_bounds_list,
-                "\n    A{n}: std::fmt::Debug + Send + Sync + 'static,"
-            )
-            .expect("Failed to append to args_csv string.");
-
-            #[cfg(not(feature = "debug"))]
-            write!(&mut arg_bounds_list, "\n    A{n}: Send + Sync + 'static,")
-                .expect("Failed to append to args_csv string.");
-            arg_bounds_list
-        })
+    fn arg_type_name_pushes(n: usize) -> String {
+        let mut arg_type_name_pushes = String::with_capacity(n * 80);
+        (0..n)
+            .try_for_each(|index| {
+                writeln!(
+                    &mut arg_type_name_pushes,
+                    "A{index}::borrow_type_names(borrows);\n\
+                    A{index}::borrow_mut_type_names(borrow_muts);",
+                )
+            })
+            .expect("Failed to append to `arg_type_name_pushes` string.");
+        arg_type_name_pushes
+    }
+
+    fn arg_type_name_calls(n: usize, fn_name: &str) -> String {
+        let mut arg_type_name_calls = String::with_capacity(n * 48);
+        (0..n)
+            .try_for_each(|index| {
+                writeln!(
+                    &mut arg_type_name_calls,
+                    "A{index}::{fn_name}(&mut type_names);"
+                )
+            })
+            .expect("Failed to append to `arg_type_name_calls` string.");
+        arg_type_name_calls
+    }
+
+    /// Returns the statement that enters the `tracing` span for a call to a
+    /// resource function.
+    ///
+    /// The statement is only compiled when the `"tracing"` feature is enabled.
+    pub fn call_span_enter(span_name: &str, arg_type_name_pushes: &str) -> String {
+        format!(
+            "#[cfg(feature = \"tracing\")]\n\
+            let _span = fn_res_call_span!(\"{span_name}\", Fun, |borrows, borrow_muts| {{\n\
+            {arg_type_name_pushes}\
+            }});\n"
+        )
     }
 
-    fn args_csv<const N: usize>() -> String {
-        let mut args_csv = String::with_capacity(N * 4);
+    #[cfg(feature = "fn_meta")]
+    fn arg_meta_calls(n: usize, fn_name: &str) -> String {
+        let mut arg_meta_calls = String::with_capacity(n * 36);
+        (0..n)
+            .try_for_each(|index| {
+                writeln!(&mut arg_meta_calls, "A{index}::{fn_name}(&mut type_ids);")
+            })
+            .expect("Failed to append to `arg_meta_calls` string.");
+        arg_meta_calls
+    }
+
+    fn arg_bounds(n: usize, bound: &str) -> String {
+        let mut arg_bounds_list = String::with_capacity(n * 24);
+        (0..n)
+            .try_for_each(|index| {
+                if index != 0 {
+                    arg_bounds_list.push('\n');
+                }
+                write!(&mut arg_bounds_list, "    A{index}: {bound},")
+            })
+            .expect("Failed to append to `arg_bounds_list` string.");
+        arg_bounds_list
+    }
+
+    fn tuple_of<F>(n: usize, fn_element: F) -> String
+    where
+        F: Fn(usize) -> String,
+    {
+        let mut tuple = String::with_capacity(n * 16);
+        tuple.push('(');
+        (0..n).for_each(|index| {
+            if index != 0 {
+                tuple.push_str(", ");
+            }
+            tuple.push_str(&fn_element(index));
+        });
+        if n == 1 {
+            tuple.push(',');
+        }
+        tuple.push(')');
+        tuple
+    }
+
+    fn arg_items_csv(n: usize) -> String {
+        let mut arg_items_csv = String::with_capacity(n * 14);
+        (0..n)
+            .try_for_each(|index| {
+                if index != 0 {
+                    arg_items_csv.push_str(", ");
+                }
+                write!(&mut arg_items_csv, "A{index}::Item<'f>")
+            })
+            .expect("Failed to append to `arg_items_csv` string.");
+        arg_items_csv
+    }
+
+    fn args_csv(n: usize) -> String {
+        let mut args_csv = String::with_capacity(n * 4);
         args_csv.push_str("A0");
-        (1..N).fold(args_csv, |mut args_csv, n| {
-            write!(&mut args_csv, ", A{n}").expect("Failed to append to args_csv string.");
+        (1..n).fold(args_csv, |mut args_csv, index| {
+            write!(&mut args_csv, ", A{index}").expect("Failed to append to args_csv string.");
             args_csv
         })
     }
@@ -284,36 +449,73 @@ mod fn_resource_impl {
         io::{BufWriter, Write},
     };
 
-    use super::common::ArgExprs;
+    use super::common::{call_span_enter, ArgExprs};
 
     pub fn write_fn_resource_impl(fn_resource_impl: &mut BufWriter<File>, arg_exprs: ArgExprs<'_>) {
         let ArgExprs {
             args_csv,
-            arg_refs_csv,
+            args_tuple,
+            arg_items_csv,
             arg_bounds_list,
-            resource_arg_borrows,
-            resource_arg_try_borrows,
+            arg_stateless_bounds_list,
+            arg_local_bounds_list,
+            #[cfg(any(feature = "fn_res_mut", feature = "fn_res_once"))]
+            arg_states_tuple,
+            arg_states_default,
+            arg_states_unit,
+            state_vars_pattern,
+            resource_arg_fetches,
+            resource_arg_try_fetches,
             resource_arg_vars,
+            resource_arg_local_fetches,
+            resource_arg_local_try_fetches,
+            resource_arg_local_vars,
+            arg_type_name_pushes,
+            ..
         } = arg_exprs;
 
+        #[cfg(feature = "fn_res_once")]
+        let call_once_span = call_span_enter("FnResOnce::call_once", arg_type_name_pushes);
+        #[cfg(feature = "fn_res_once")]
+        let try_call_once_span = call_span_enter("FnResOnce::try_call_once", arg_type_name_pushes);
+        #[cfg(feature = "fn_res_mut")]
+        let call_mut_span = call_span_enter("FnResMut::call_mut", arg_type_name_pushes);
+        #[cfg(feature = "fn_res_mut")]
+        let try_call_mut_span = call_span_enter("FnResMut::try_call_mut", arg_type_name_pushes);
+        let call_span = call_span_enter("FnRes::call", arg_type_name_pushes);
+        let try_call_span = call_span_enter("FnRes::try_call", arg_type_name_pushes);
+        let call_local_span = call_span_enter("FnResLocal::call_local", arg_type_name_pushes);
+        let try_call_local_span =
+            call_span_enter("FnResLocal::try_call_local", arg_type_name_pushes);
+
         #[cfg(feature = "fn_res_once")]
         write!(
             fn_resource_impl,
             r#"
-impl<Fun, Ret, {args_csv}> FnResource<Fun, Ret, ({arg_refs_csv})>
+impl<Fun, Ret, {args_csv}> FnResource<Fun, Ret, {args_tuple}>
 where
-    Fun: FnOnce({arg_refs_csv}) -> Ret + 'static,
+    Fun: for<'f> FnOnce({arg_items_csv}) -> Ret + 'static,
     Ret: 'static,
-    {arg_bounds_list}
+{arg_bounds_list}
 {{
     pub fn call_once(self, resources: &Resources) -> Ret {{
-        {resource_arg_borrows}
+        {call_once_span}
+        let mut state = self.state;
+        let {state_vars_pattern} = state
+            .downcast_mut::<{arg_states_tuple}>()
+            .expect(STATE_DOWNCAST_EXPECT);
+        {resource_arg_fetches}
 
         (self.func)({resource_arg_vars})
     }}
 
-    pub fn try_call_once(self, resources: &Resources) -> Result<Ret, BorrowFail> {{
-        {resource_arg_try_borrows}
+    pub fn try_call_once(self, resources: &Resources) -> Result<Ret, ResourceError> {{
+        {try_call_once_span}
+        let mut state = self.state;
+        let {state_vars_pattern} = state
+            .downcast_mut::<{arg_states_tuple}>()
+            .expect(STATE_DOWNCAST_EXPECT);
+        {resource_arg_try_fetches}
 
         let ret_value = (self.func)({resource_arg_vars});
         Ok(ret_value)
@@ -327,20 +529,30 @@ where
         write!(
             fn_resource_impl,
             r#"
-impl<Fun, Ret, {args_csv}> FnResource<Fun, Ret, ({arg_refs_csv})>
+impl<Fun, Ret, {args_csv}> FnResource<Fun, Ret, {args_tuple}>
 where
-    Fun: FnMut({arg_refs_csv}) -> Ret + 'static,
+    Fun: for<'f> FnMut({arg_items_csv}) -> Ret + 'static,
     Ret: 'static,
-    {arg_bounds_list}
+{arg_bounds_list}
 {{
     pub fn call_mut(&mut self, resources: &Resources) -> Ret {{
-        {resource_arg_borrows}
+        {call_mut_span}
+        let {state_vars_pattern} = self
+            .state
+            .downcast_mut::<{arg_states_tuple}>()
+            .expect(STATE_DOWNCAST_EXPECT);
+        {resource_arg_fetches}
 
         (self.func)({resource_arg_vars})
     }}
 
-    pub fn try_call_mut(&mut self, resources: &Resources) -> Result<Ret, BorrowFail> {{
-        {resource_arg_try_borrows}
+    pub fn try_call_mut(&mut self, resources: &Resources) -> Result<Ret, ResourceError> {{
+        {try_call_mut_span}
+        let {state_vars_pattern} = self
+            .state
+            .downcast_mut::<{arg_states_tuple}>()
+            .expect(STATE_DOWNCAST_EXPECT);
+        {resource_arg_try_fetches}
 
         let ret_value = (self.func)({resource_arg_vars});
         Ok(ret_value)
@@ -353,25 +565,67 @@ where
         write!(
             fn_resource_impl,
             r#"
-impl<Fun, Ret, {args_csv}> FnResource<Fun, Ret, ({arg_refs_csv})>
+impl<Fun, Ret, {args_csv}> FnResource<Fun, Ret, {args_tuple}>
 where
-    Fun: Fn({arg_refs_csv}) -> Ret + 'static,
+    Fun: for<'f> Fn({arg_items_csv}) -> Ret + 'static,
     Ret: 'static,
-    {arg_bounds_list}
+{arg_stateless_bounds_list}
 {{
     pub fn call(&self, resources: &Resources) -> Ret {{
-        {resource_arg_borrows}
+        {call_span}
+        let {state_vars_pattern} = &mut {arg_states_unit};
+        {resource_arg_fetches}
 
         (self.func)({resource_arg_vars})
     }}
 
-    pub fn try_call(&self, resources: &Resources) -> Result<Ret, BorrowFail> {{
-        {resource_arg_try_borrows}
+    pub fn try_call(&self, resources: &Resources) -> Result<Ret, ResourceError> {{
+        {try_call_span}
+        let {state_vars_pattern} = &mut {arg_states_unit};
+        {resource_arg_try_fetches}
 
         let ret_value = (self.func)({resource_arg_vars});
         Ok(ret_value)
     }}
 }}
+
+impl<Fun, Ret, {args_csv}> FnResource<Fun, Ret, {args_tuple}>
+where
+    Fun: for<'f> Fn({arg_items_csv}) -> Ret + 'static,
+    Ret: 'static,
+{arg_local_bounds_list}
+{{
+    pub fn call_local(&self, resources: &LocalResources) -> Ret {{
+        {call_local_span}
+        {resource_arg_local_fetches}
+
+        (self.func)({resource_arg_local_vars})
+    }}
+
+    pub fn try_call_local(&self, resources: &LocalResources) -> Result<Ret, ResourceError> {{
+        {try_call_local_span}
+        {resource_arg_local_try_fetches}
+
+        let ret_value = (self.func)({resource_arg_local_vars});
+        Ok(ret_value)
+    }}
+}}
+
+impl<Fun, Ret, {args_csv}> IntoFnResource<Fun, Ret, {args_tuple}> for Fun
+where
+    Fun: FnOnce({args_csv}) -> Ret + 'static,
+    Ret: 'static,
+{arg_bounds_list}
+{{
+    #[allow(clippy::type_complexity)]
+    fn into_fn_resource(self) -> FnResource<Fun, Ret, {args_tuple}> {{
+        FnResource {{
+            func: self,
+            state: Box::new({arg_states_default}),
+            marker: PhantomData,
+        }}
+    }}
+}}
 "#,
         )
         .expect("Failed to write to fn_resource_impl.rs");
@@ -390,7 +644,8 @@ mod fn_res_once_impl {
     pub fn write_fn_res_once_impl(fn_resource_impl: &mut BufWriter<File>, arg_exprs: ArgExprs<'_>) {
         let ArgExprs {
             args_csv,
-            arg_refs_csv,
+            args_tuple,
+            arg_items_csv,
             arg_bounds_list,
             ..
         } = arg_exprs;
@@ -398,11 +653,11 @@ mod fn_res_once_impl {
         write!(
             fn_resource_impl,
             r#"
-impl<Fun, Ret, {args_csv}> FnResOnce for FnResource<Fun, Ret, ({arg_refs_csv})>
+impl<Fun, Ret, {args_csv}> FnResOnce for FnResource<Fun, Ret, {args_tuple}>
 where
-    Fun: FnOnce({arg_refs_csv}) -> Ret + 'static,
+    Fun: for<'f> FnOnce({arg_items_csv}) -> Ret + 'static,
     Ret: 'static,
-    {arg_bounds_list}
+{arg_bounds_list}
 {{
     type Ret = Ret;
 
@@ -410,7 +665,7 @@ where
         Self::call_once(self, resources)
     }}
 
-    fn try_call_once(self, resources: &Resources) -> Result<Ret, BorrowFail> {{
+    fn try_call_once(self, resources: &Resources) -> Result<Ret, ResourceError> {{
         Self::try_call_once(self, resources)
     }}
 }}
@@ -432,7 +687,8 @@ mod fn_res_mut_impl {
     pub fn write_fn_res_mut_impl(fn_resource_impl: &mut BufWriter<File>, arg_exprs: ArgExprs<'_>) {
         let ArgExprs {
             args_csv,
-            arg_refs_csv,
+            args_tuple,
+            arg_items_csv,
             arg_bounds_list,
             ..
         } = arg_exprs;
@@ -445,18 +701,18 @@ mod fn_res_mut_impl {
         write!(
             fn_resource_impl,
             r#"
-impl<Fun, Ret, {args_csv}> FnResMut for FnResource<Fun, Ret, ({arg_refs_csv})>
+impl<Fun, Ret, {args_csv}> FnResMut for FnResource<Fun, Ret, {args_tuple}>
 where
-    Fun: FnMut({arg_refs_csv}) -> Ret + 'static,
+    Fun: for<'f> FnMut({arg_items_csv}) -> Ret + 'static,
     Ret: 'static,
-    {arg_bounds_list}
+{arg_bounds_list}
 {{
 {ret_type_str}
     fn call_mut(&mut self, resources: &Resources) -> Ret {{
         Self::call_mut(self, resources)
     }}
 
-    fn try_call_mut(&mut self, resources: &Resources) -> Result<Ret, BorrowFail> {{
+    fn try_call_mut(&mut self, resources: &Resources) -> Result<Ret, ResourceError> {{
         Self::try_call_mut(self, resources)
     }}
 }}
@@ -478,8 +734,11 @@ mod fn_res_impl {
     pub fn write_fn_res_impl(fn_resource_impl: &mut BufWriter<File>, arg_exprs: ArgExprs<'_>) {
         let ArgExprs {
             args_csv,
-            arg_refs_csv,
-            arg_bounds_list,
+            args_tuple,
+            arg_items_csv,
+            arg_stateless_bounds_list,
+            arg_borrow_type_names,
+            arg_borrow_mut_type_names,
             ..
         } = arg_exprs;
 
@@ -491,20 +750,41 @@ mod fn_res_impl {
         write!(
             fn_resource_impl,
             r#"
-impl<Fun, Ret, {args_csv}> FnRes for FnResource<Fun, Ret, ({arg_refs_csv})>
+impl<Fun, Ret, {args_csv}> FnRes for FnResource<Fun, Ret, {args_tuple}>
 where
-    Fun: Fn({arg_refs_csv}) -> Ret + 'static,
+    Fun: for<'f> Fn({arg_items_csv}) -> Ret + 'static,
     Ret: 'static,
-    {arg_bounds_list}
+{arg_stateless_bounds_list}
 {{
 {ret_type_str}
     fn call(&self, resources: &Resources) -> Ret {{
         Self::call(self, resources)
     }}
 
-    fn try_call(&self, resources: &Resources) -> Result<Ret, BorrowFail> {{
+    fn try_call(&self, resources: &Resources) -> Result<Ret, ResourceError> {{
         Self::try_call(self, resources)
     }}
+
+    fn borrow_type_names(&self) -> Vec<&'static str> {{
+        let mut type_names = Vec::new();
+        {arg_borrow_type_names}        type_names
+    }}
+
+    fn borrow_mut_type_names(&self) -> Vec<&'static str> {{
+        let mut type_names = Vec::new();
+        {arg_borrow_mut_type_names}        type_names
+    }}
+
+    #[cfg(all(feature = "fn_meta", feature = "std"))]
+    fn try_call_strict(&self, resources: &Resources) -> Result<Ret, ResourceError> {{
+        crate::borrow_restriction::BorrowRestriction::try_call(
+            resources,
+            core::any::type_name::<Fun>(),
+            fn_meta::FnMetaDyn::borrows(self),
+            fn_meta::FnMetaDyn::borrow_muts(self),
+            || Self::try_call(self, resources),
+        )
+    }}
 }}
 "#,
         )
@@ -512,6 +792,126 @@ where
     }
 }
 
+#[cfg(feature = "fn_res")]
+mod into_fn_res_impl {
+    use std::{
+        fs::File,
+        io::{BufWriter, Write},
+    };
+
+    use super::common::ArgExprs;
+
+    pub fn write_into_fn_res_impl(
+        into_fn_res_impl: &mut BufWriter<File>,
+        arg_exprs: ArgExprs<'_>,
+        into_trait_name: &str,
+        trait_name: &str,
+        fn_trait_name: &str,
+        fn_name: &str,
+    ) {
+        let ArgExprs {
+            args_csv,
+            args_tuple,
+            arg_static_bounds_list,
+            ..
+        } = arg_exprs;
+
+        write!(
+            into_fn_res_impl,
+            r#"
+impl<Fun, Ret, {args_csv}> {into_trait_name}<Fun, Ret, {args_tuple}> for Fun
+where
+    Fun: {fn_trait_name}({args_csv}) -> Ret + 'static,
+    Ret: 'static,
+{arg_static_bounds_list}
+    FnResource<Fun, Ret, {args_tuple}>: {trait_name}<Ret = Ret>,
+{{
+    fn {fn_name}(self) -> Box<dyn {trait_name}<Ret = Ret>> {{
+        Box::new(self.into_fn_resource())
+    }}
+}}
+"#,
+        )
+        .unwrap_or_else(|e| panic!("Failed to write {fn_name} impl. Error: {e}"));
+    }
+}
+
+#[cfg(feature = "fn_res")]
+mod fn_res_local_impl {
+    use std::{
+        fs::File,
+        io::{BufWriter, Write},
+    };
+
+    use super::common::ArgExprs;
+
+    pub fn write_fn_res_local_impl(
+        fn_res_local_impl: &mut BufWriter<File>,
+        arg_exprs: ArgExprs<'_>,
+    ) {
+        let ArgExprs {
+            args_csv,
+            args_tuple,
+            arg_items_csv,
+            arg_local_bounds_list,
+            ..
+        } = arg_exprs;
+
+        write!(
+            fn_res_local_impl,
+            r#"
+impl<Fun, Ret, {args_csv}> FnResLocal for FnResource<Fun, Ret, {args_tuple}>
+where
+    Fun: for<'f> Fn({arg_items_csv}) -> Ret + 'static,
+    Ret: 'static,
+{arg_local_bounds_list}
+{{
+    type Ret = Ret;
+
+    fn call_local(&self, resources: &LocalResources) -> Ret {{
+        Self::call_local(self, resources)
+    }}
+
+    fn try_call_local(&self, resources: &LocalResources) -> Result<Ret, ResourceError> {{
+        Self::try_call_local(self, resources)
+    }}
+}}
+"#,
+        )
+        .expect("Failed to write to fn_res_local_impl.rs");
+    }
+
+    pub fn write_into_fn_res_local_impl(
+        into_fn_res_local_impl: &mut BufWriter<File>,
+        arg_exprs: ArgExprs<'_>,
+    ) {
+        let ArgExprs {
+            args_csv,
+            args_tuple,
+            arg_local_static_bounds_list,
+            ..
+        } = arg_exprs;
+
+        write!(
+            into_fn_res_local_impl,
+            r#"
+impl<Fun, Ret, {args_csv}> IntoFnResLocal<Fun, Ret, {args_tuple}> for Fun
+where
+    Fun: Fn({args_csv}) -> Ret + 'static,
+    Ret: 'static,
+{arg_local_static_bounds_list}
+    FnResource<Fun, Ret, {args_tuple}>: FnResLocal<Ret = Ret>,
+{{
+    fn into_fn_res_local(self) -> Box<dyn FnResLocal<Ret = Ret>> {{
+        Box::new(FnResource::new_local(self))
+    }}
+}}
+"#,
+        )
+        .expect("Failed to write to into_fn_res_local_impl.rs");
+    }
+}
+
 #[cfg(all(feature = "fn_res", feature = "fn_meta"))]
 mod fn_resource_meta_impl {
     use std::{
@@ -527,43 +927,43 @@ mod fn_resource_meta_impl {
     ) {
         let ArgExprs {
             args_csv,
-            arg_refs_csv,
+            args_tuple,
             arg_bounds_list,
-            resource_arg_borrows: _,
-            resource_arg_try_borrows: _,
-            resource_arg_vars: _,
+            arg_borrows,
+            arg_borrow_muts,
+            ..
         } = arg_exprs;
 
         write!(
             fn_resource_meta_impl,
             r#"
-impl<Fun, Ret, {args_csv}> fn_meta::FnMeta for FnResource<Fun, Ret, ({arg_refs_csv})>
+impl<Fun, Ret, {args_csv}> fn_meta::FnMeta for FnResource<Fun, Ret, {args_tuple}>
 where
-    Fun: FnOnce({arg_refs_csv}) -> Ret + 'static,
-    Ret: 'static,
-    {arg_bounds_list}
+{arg_bounds_list}
 {{
     fn borrows() -> fn_meta::TypeIds {{
-        <fn_meta::FnMetadata<Fun, Ret, ({arg_refs_csv})> as fn_meta::FnMeta>::borrows()
+        let mut type_ids = fn_meta::TypeIds::new();
+        {arg_borrows}
+        type_ids
     }}
 
     fn borrow_muts() -> fn_meta::TypeIds {{
-        <fn_meta::FnMetadata<Fun, Ret, ({arg_refs_csv})> as fn_meta::FnMeta>::borrow_muts()
+        let mut type_ids = fn_meta::TypeIds::new();
+        {arg_borrow_muts}
+        type_ids
     }}
 }}
 
-impl<Fun, Ret, {args_csv}> fn_meta::FnMetaDyn for FnResource<Fun, Ret, ({arg_refs_csv})>
+impl<Fun, Ret, {args_csv}> fn_meta::FnMetaDyn for FnResource<Fun, Ret, {args_tuple}>
 where
-    Fun: FnOnce({arg_refs_csv}) -> Ret + 'static,
-    Ret: 'static,
-    {arg_bounds_list}
+{arg_bounds_list}
 {{
     fn borrows(&self) -> fn_meta::TypeIds {{
-        fn_meta::FnMetaExt::meta(&self.func).borrows()
+        <Self as fn_meta::FnMeta>::borrows()
     }}
 
     fn borrow_muts(&self) -> fn_meta::TypeIds {{
-        fn_meta::FnMetaExt::meta(&self.func).borrow_muts()
+        <Self as fn_meta::FnMeta>::borrow_muts()
     }}
 }}
 "#,
@@ -0,0 +1,19 @@
+use core::fmt;
+
+/// Whether a resource is borrowed immutably or mutably.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BorrowKind {
+    /// Borrowed through a `Ref`.
+    Immutable,
+    /// Borrowed through a `RefMut`.
+    Mutable,
+}
+
+impl fmt::Display for BorrowKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Immutable => write!(f, "immutably"),
+            Self::Mutable => write!(f, "mutably"),
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    any::TypeId,
+    cell::RefCell,
+    sync::atomic::{AtomicUsize, Ordering},
+    vec::Vec,
+};
+
+use fn_meta::TypeIds;
+
+use crate::{
+    borrow_restriction_guard::BorrowRestrictionGuard, resources_id::ResourcesId, BorrowKind,
+    ResourceError, ResourceErrorKind, Resources,
+};
+
+std::thread_local! {
+    /// Restrictions for the resource functions that are running in strict
+    /// mode on this thread, innermost last.
+    pub(crate) static BORROW_RESTRICTIONS: RefCell<Vec<BorrowRestriction>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Resources that a function running in strict mode is allowed to borrow.
+///
+/// While a restriction is entered, borrows from the restricted `Resources` map
+/// on the same thread are checked against it, including borrows through a
+/// `&Resources` that the function captured.
+///
+/// Each `Resources` map counts the strict calls running on it, and only
+/// looks up the restrictions while that count is not zero.
+#[derive(Debug)]
+pub(crate) struct BorrowRestriction {
+    /// ID of the restricted `Resources` map.
+    resources_id: ResourcesId,
+    /// Name of the function, used in the error message.
+    fn_name: &'static str,
+    /// Types the function borrows immutably.
+    borrows: TypeIds,
+    /// Types the function borrows mutably.
+    borrow_muts: TypeIds,
+    /// The first borrow that was denied, if any.
+    pub(crate) denied: Option<ResourceError>,
+}
+
+impl BorrowRestriction {
+    /// Runs `call` while restricting borrows from `resources` to the given
+    /// types.
+    ///
+    /// Returns an `AccessDenied` error if the function borrowed any other
+    /// resource, even if it handled the error from that borrow.
+    pub(crate) fn try_call<Ret, F>(
+        resources: &Resources,
+        fn_name: &'static str,
+        borrows: TypeIds,
+        borrow_muts: TypeIds,
+        call: F,
+    ) -> Result<Ret, ResourceError>
+    where
+        F: FnOnce() -> Result<Ret, ResourceError>,
+    {
+        let borrow_restriction = Self::enter(
+            resources.id(),
+            resources.strict_calls(),
+            fn_name,
+            borrows,
+            borrow_muts,
+        );
+        let result = call();
+
+        match borrow_restriction.denied() {
+            Some(error) => Err(error),
+            None => result,
+        }
+    }
+
+    /// Restricts borrows from the given `Resources` map until the returned
+    /// guard is dropped.
+    fn enter<'r>(
+        resources_id: ResourcesId,
+        strict_calls: &'r AtomicUsize,
+        fn_name: &'static str,
+        borrows: TypeIds,
+        borrow_muts: TypeIds,
+    ) -> BorrowRestrictionGuard<'r> {
+        let borrow_restriction = Self {
+            resources_id,
+            fn_name,
+            borrows,
+            borrow_muts,
+            denied: None,
+        };
+        BORROW_RESTRICTIONS
+            .with(|borrow_restrictions| borrow_restrictions.borrow_mut().push(borrow_restriction));
+        strict_calls.fetch_add(1, Ordering::Relaxed);
+
+        BorrowRestrictionGuard::new(strict_calls)
+    }
+
+    /// Returns an `AccessDenied` error if a function running in strict mode
+    /// does not allow the resource to be borrowed.
+    ///
+    /// The error is also recorded in the restriction, so that it is returned
+    /// from the strict call.
+    pub(crate) fn check(
+        resources_id: ResourcesId,
+        type_id: TypeId,
+        type_name: &'static str,
+        borrow_kind: BorrowKind,
+    ) -> Result<(), ResourceError> {
+        BORROW_RESTRICTIONS.with(|borrow_restrictions| {
+            let mut borrow_restrictions = borrow_restrictions.borrow_mut();
+            let denied_by = borrow_restrictions
+                .iter_mut()
+                .filter(|borrow_restriction| borrow_restriction.resources_id == resources_id)
+                .find(|borrow_restriction| !borrow_restriction.allows(type_id, borrow_kind));
+
+            match denied_by {
+                Some(borrow_restriction) => {
+                    let error = ResourceError::new(
+                        type_name,
+                        Some(borrow_kind),
+                        ResourceErrorKind::AccessDenied,
+                    )
+                    .with_fn_name(borrow_restriction.fn_name);
+                    borrow_restriction
+                        .denied
+                        .get_or_insert_with(|| error.clone());
+                    Err(error)
+                }
+                None => Ok(()),
+            }
+        })
+    }
+
+    /// Returns whether the functions running in strict mode allow the
+    /// resource to be borrowed, without recording a denial.
+    pub(crate) fn is_allowed(
+        resources_id: ResourcesId,
+        type_id: TypeId,
+        borrow_kind: BorrowKind,
+    ) -> bool {
+        BORROW_RESTRICTIONS.with(|borrow_restrictions| {
+            borrow_restrictions
+                .borrow()
+                .iter()
+                .filter(|borrow_restriction| borrow_restriction.resources_id == resources_id)
+                .all(|borrow_restriction| borrow_restriction.allows(type_id, borrow_kind))
+        })
+    }
+
+    fn allows(&self, type_id: TypeId, borrow_kind: BorrowKind) -> bool {
+        match borrow_kind {
+            BorrowKind::Immutable => {
+                self.borrows.contains(&type_id) || self.borrow_muts.contains(&type_id)
+            }
+            BorrowKind::Mutable => self.borrow_muts.contains(&type_id),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{borrow_restriction::BORROW_RESTRICTIONS, ResourceError};
+
+/// Removes the innermost `BorrowRestriction` when dropped.
+#[derive(Debug)]
+pub(crate) struct BorrowRestrictionGuard<'r> {
+    /// Number of strict calls running on the restricted `Resources` map.
+    strict_calls: &'r AtomicUsize,
+}
+
+impl<'r> BorrowRestrictionGuard<'r> {
+    /// Returns a new `BorrowRestrictionGuard`.
+    ///
+    /// Only `BorrowRestriction::enter` should call this.
+    pub(crate) fn new(strict_calls: &'r AtomicUsize) -> Self {
+        Self { strict_calls }
+    }
+
+    /// Returns the first borrow that the innermost restriction denied, if any.
+    pub(crate) fn denied(&self) -> Option<ResourceError> {
+        BORROW_RESTRICTIONS.with(|borrow_restrictions| {
+            borrow_restrictions
+                .borrow_mut()
+                .last_mut()
+                .and_then(|borrow_restriction| borrow_restriction.denied.take())
+        })
+    }
+}
+
+impl Drop for BorrowRestrictionGuard<'_> {
+    fn drop(&mut self) {
+        BORROW_RESTRICTIONS.with(|borrow_restrictions| borrow_restrictions.borrow_mut().pop());
+        self.strict_calls.fetch_sub(1, Ordering::Relaxed);
+    }
+}
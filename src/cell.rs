@@ -0,0 +1,259 @@
+use core::{
+    cell::UnsafeCell,
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{
+    cell_ref::{CellRef, REF_LIMIT_MAX},
+    BorrowFail, CellRefMut,
+};
+
+/// Cell with runtime checked borrowing, that can be shared between threads.
+///
+/// The borrow state is held in an atomic counter, so this only needs `core`:
+///
+/// * `0`: not borrowed.
+/// * `1..=isize::MAX`: borrowed immutably that many times.
+/// * `usize::MAX`: borrowed mutably.
+pub struct Cell<T> {
+    /// Borrow state of the value.
+    flag: AtomicUsize,
+    /// The value.
+    inner: UnsafeCell<T>,
+}
+
+impl<T> Cell<T> {
+    /// Returns a new `Cell` holding the given value.
+    pub fn new(inner: T) -> Self {
+        Self {
+            flag: AtomicUsize::new(0),
+            inner: UnsafeCell::new(inner),
+        }
+    }
+
+    /// Consumes this `Cell`, returning the value.
+    pub fn into_inner(self) -> T {
+        self.inner.into_inner()
+    }
+
+    /// Borrows the value immutably.
+    ///
+    /// See [`try_borrow`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is already borrowed mutably.
+    ///
+    /// [`try_borrow`]: Self::try_borrow
+    pub fn borrow(&self) -> CellRef<'_, T> {
+        self.try_borrow().unwrap_or_else(|_| {
+            panic!(
+                "Expected to borrow `{}` immutably, but it was already borrowed mutably.",
+                core::any::type_name::<T>()
+            )
+        })
+    }
+
+    /// Borrows the value immutably.
+    ///
+    /// Returns `BorrowConflictImm` if the value is already borrowed mutably.
+    pub fn try_borrow(&self) -> Result<CellRef<'_, T>, BorrowFail> {
+        if self.check_flag_read() {
+            Ok(CellRef {
+                flag: &self.flag,
+                // SAFETY: The flag was incremented, so there is no mutable
+                // borrow until the returned `CellRef` is dropped.
+                value: unsafe { &*self.inner.get() },
+            })
+        } else {
+            Err(BorrowFail::BorrowConflictImm)
+        }
+    }
+
+    /// Borrows the value mutably.
+    ///
+    /// See [`try_borrow_mut`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is already borrowed.
+    ///
+    /// [`try_borrow_mut`]: Self::try_borrow_mut
+    pub fn borrow_mut(&self) -> CellRefMut<'_, T> {
+        self.try_borrow_mut().unwrap_or_else(|_| {
+            panic!(
+                "Expected to borrow `{}` mutably, but it was already borrowed.",
+                core::any::type_name::<T>()
+            )
+        })
+    }
+
+    /// Borrows the value mutably.
+    ///
+    /// Returns `BorrowConflictMut` if the value is already borrowed.
+    pub fn try_borrow_mut(&self) -> Result<CellRefMut<'_, T>, BorrowFail> {
+        if self.check_flag_write() {
+            Ok(CellRefMut {
+                flag: &self.flag,
+                // SAFETY: The flag was set from `0` to `usize::MAX`, so there
+                // are no other borrows until the returned `CellRefMut` is
+                // dropped.
+                value: unsafe { &mut *self.inner.get() },
+            })
+        } else {
+            Err(BorrowFail::BorrowConflictMut)
+        }
+    }
+
+    /// Returns a mutable reference to the value.
+    ///
+    /// This does not need to check the borrow state, as `&mut self` means
+    /// there are no outstanding borrows.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.inner.get_mut()
+    }
+
+    /// Increments the immutable borrow count, returning whether it succeeded.
+    fn check_flag_read(&self) -> bool {
+        loop {
+            let val = self.flag.load(Ordering::Acquire);
+
+            if val >= REF_LIMIT_MAX {
+                return false;
+            }
+
+            if self
+                .flag
+                .compare_exchange_weak(val, val + 1, Ordering::AcqRel, Ordering::Acquire)
+                == Ok(val)
+            {
+                return true;
+            }
+        }
+    }
+
+    /// Marks the value as mutably borrowed, returning whether it succeeded.
+    fn check_flag_write(&self) -> bool {
+        self.flag
+            .compare_exchange(0, usize::MAX, Ordering::AcqRel, Ordering::Acquire)
+            == Ok(0)
+    }
+}
+
+impl<T> fmt::Debug for Cell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cell")
+            .field("flag", &self.flag)
+            .finish_non_exhaustive()
+    }
+}
+
+// SAFETY: Borrows are checked through the atomic flag, so a `&mut T` is only
+// handed out while there are no other borrows on any thread.
+unsafe impl<T> Sync for Cell<T> where T: Sync {}
+
+#[cfg(test)]
+mod tests {
+    use crate::BorrowFail;
+
+    use super::Cell;
+
+    #[test]
+    fn allow_multiple_reads() {
+        let cell = Cell::new(5);
+
+        let a = cell.borrow();
+        let b = cell.borrow();
+
+        assert_eq!(10, *a + *b);
+    }
+
+    #[test]
+    fn allow_clone_reads() {
+        let cell = Cell::new(5);
+
+        let a = cell.borrow();
+        let b = a.clone();
+
+        assert_eq!(10, *a + *b);
+    }
+
+    #[test]
+    fn allow_single_write() {
+        let cell = Cell::new(5);
+
+        {
+            let mut a = cell.borrow_mut();
+            *a += 2;
+            *a += 3;
+        }
+
+        assert_eq!(10, *cell.borrow());
+    }
+
+    #[test]
+    fn get_mut_and_into_inner_return_value() {
+        let mut cell = Cell::new(5);
+
+        *cell.get_mut() += 5;
+
+        assert_eq!(10, cell.into_inner());
+    }
+
+    #[test]
+    fn try_borrow_returns_err_when_borrowed_mutably() {
+        let cell = Cell::new(5);
+
+        let _a = cell.borrow_mut();
+
+        assert_eq!(Some(BorrowFail::BorrowConflictImm), cell.try_borrow().err());
+        assert_eq!(
+            Some(BorrowFail::BorrowConflictMut),
+            cell.try_borrow_mut().err()
+        );
+    }
+
+    #[test]
+    fn try_borrow_mut_returns_err_when_borrowed_immutably() {
+        let cell = Cell::new(5);
+
+        let _a = cell.borrow();
+
+        assert_eq!(
+            Some(BorrowFail::BorrowConflictMut),
+            cell.try_borrow_mut().err()
+        );
+        assert!(cell.try_borrow().is_ok());
+    }
+
+    #[test]
+    fn borrows_are_released_on_drop() {
+        let cell = Cell::new(5);
+
+        drop(cell.borrow());
+        drop(cell.borrow_mut());
+
+        assert!(cell.try_borrow_mut().is_ok());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `i32` immutably, but it was already borrowed mutably."
+    )]
+    fn borrow_panics_when_borrowed_mutably() {
+        let cell = Cell::new(5);
+
+        let _a = cell.borrow_mut();
+        let _b = cell.borrow();
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected to borrow `i32` mutably, but it was already borrowed.")]
+    fn borrow_mut_panics_when_borrowed() {
+        let cell = Cell::new(5);
+
+        let _a = cell.borrow();
+        let _b = cell.borrow_mut();
+    }
+}
@@ -0,0 +1,77 @@
+use core::{
+    fmt,
+    ops::Deref,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Maximum number of immutable borrows of a `Cell`.
+pub(crate) const REF_LIMIT_MAX: usize = isize::MAX as usize;
+
+/// Immutable borrow of the value in a [`Cell`].
+///
+/// The borrow is released when this is dropped.
+///
+/// [`Cell`]: crate::Cell
+pub struct CellRef<'a, T>
+where
+    T: ?Sized + 'a,
+{
+    /// Borrow state of the `Cell`.
+    pub(crate) flag: &'a AtomicUsize,
+    /// The borrowed value.
+    pub(crate) value: &'a T,
+}
+
+impl<T> Deref for CellRef<'_, T>
+where
+    T: ?Sized,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T> Drop for CellRef<'_, T>
+where
+    T: ?Sized,
+{
+    fn drop(&mut self) {
+        self.flag.fetch_sub(1, Ordering::Release);
+    }
+}
+
+impl<T> Clone for CellRef<'_, T>
+where
+    T: ?Sized,
+{
+    /// Returns another immutable borrow of the same value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is already borrowed `isize::MAX` times.
+    fn clone(&self) -> Self {
+        let previous_count = self.flag.fetch_add(1, Ordering::Relaxed);
+        if previous_count >= REF_LIMIT_MAX {
+            self.flag.fetch_sub(1, Ordering::Relaxed);
+            panic!("Failed to clone `CellRef`: borrow count exceeded `isize::MAX`.");
+        }
+
+        Self {
+            flag: self.flag,
+            value: self.value,
+        }
+    }
+}
+
+impl<T> fmt::Debug for CellRef<'_, T>
+where
+    T: ?Sized + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CellRef")
+            .field("value", &self.value)
+            .finish()
+    }
+}
@@ -0,0 +1,60 @@
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Mutable borrow of the value in a [`Cell`].
+///
+/// The borrow is released when this is dropped.
+///
+/// [`Cell`]: crate::Cell
+pub struct CellRefMut<'a, T>
+where
+    T: ?Sized + 'a,
+{
+    /// Borrow state of the `Cell`.
+    pub(crate) flag: &'a AtomicUsize,
+    /// The borrowed value.
+    pub(crate) value: &'a mut T,
+}
+
+impl<T> Deref for CellRefMut<'_, T>
+where
+    T: ?Sized,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T> DerefMut for CellRefMut<'_, T>
+where
+    T: ?Sized,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+    }
+}
+
+impl<T> Drop for CellRefMut<'_, T>
+where
+    T: ?Sized,
+{
+    fn drop(&mut self) {
+        self.flag.store(0, Ordering::Release);
+    }
+}
+
+impl<T> fmt::Debug for CellRefMut<'_, T>
+where
+    T: ?Sized + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CellRefMut")
+            .field("value", &self.value)
+            .finish()
+    }
+}
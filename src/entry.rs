@@ -1,57 +1,116 @@
-use std::{any::TypeId, marker::PhantomData};
+use crate::{EntryBackend, OccupiedEntry, Resources, VacantEntry};
 
-use crate::{RefMut, Resource};
+#[cfg(feature = "std")]
+use crate::KeyedEntryBackend;
 
-pub struct Entry<'a, R> {
-    inner: rt_map::Entry<'a, TypeId, Box<dyn Resource>>,
-    marker: PhantomData<R>,
-}
-
-/// An entry to a resource container.
+/// An entry to a resource in `Resources`, `LocalResources`, or
+/// `KeyedResources`.
 ///
-/// This is similar to the Entry API found in the standard library.
+/// This is similar to the Entry API found in the standard library. `B` is the
+/// [`EntryBackend`] for the map that the entry belongs to.
 ///
 /// ## Examples
 ///
 /// ```rust
-/// use rt_map::RtMap;
+/// use resman::{Entry, Resources};
 ///
-/// #[derive(Debug)]
+/// #[derive(Debug, Default)]
 /// struct Res(i32);
 ///
-/// let mut rt_map = RtMap::<u32, Res>::default();
+/// let mut resources = Resources::new();
 ///
-/// let value = rt_map.entry(0).or_insert(Res(4));
+/// let value = resources.entry::<Res>().or_insert(Res(4));
 /// println!("{:?}", value.0 * 2);
+/// drop(value);
+///
+/// resources
+///     .entry::<Res>()
+///     .and_modify(|res| res.0 += 1)
+///     .or_default();
+/// assert_eq!(5, resources.borrow::<Res>().0);
+///
+/// match resources.entry::<Res>() {
+///     Entry::Occupied(occupied) => assert_eq!(5, occupied.remove().0),
+///     Entry::Vacant(_) => unreachable!(),
+/// }
+/// assert!(!resources.contains::<Res>());
 /// ```
-impl<'a, R> Entry<'a, R>
+pub enum Entry<'a, R, B = &'a mut Resources> {
+    /// The resource exists.
+    Occupied(OccupiedEntry<'a, R, B>),
+    /// The resource does not exist.
+    Vacant(VacantEntry<'a, R, B>),
+}
+
+impl<'a, R, B> Entry<'a, R, B>
 where
-    R: Resource,
+    B: EntryBackend<'a, R>,
 {
-    /// Create new entry.
-    pub fn new(inner: rt_map::Entry<'a, TypeId, Box<dyn Resource>>) -> Self {
-        Self {
-            inner,
-            marker: PhantomData,
-        }
-    }
-
     /// Returns this entry's value, inserts and returns `v` otherwise.
     ///
     /// Please note that you should use `or_insert_with` in case the creation of
     /// the value is expensive.
-    pub fn or_insert(self, v: R) -> RefMut<'a, R> {
+    pub fn or_insert(self, v: R) -> B::RefMut<'a> {
         self.or_insert_with(move || v)
     }
 
     /// Returns this entry's value, inserts and returns the return value of `f`
     /// otherwise.
-    pub fn or_insert_with<F>(self, f: F) -> RefMut<'a, R>
+    pub fn or_insert_with<F>(self, f: F) -> B::RefMut<'a>
     where
         F: FnOnce() -> R,
     {
-        let inner = self.inner.or_insert_with(move || Box::new(f()));
+        match self {
+            Self::Occupied(occupied) => occupied.into_mut(),
+            Self::Vacant(vacant) => vacant.insert(f()),
+        }
+    }
 
-        RefMut::new(inner)
+    /// Returns this entry's value, inserts and returns the return value of `f`
+    /// otherwise.
+    ///
+    /// If `f` returns an error, nothing is inserted and the error is returned.
+    pub fn or_try_insert_with<F, E>(self, f: F) -> Result<B::RefMut<'a>, E>
+    where
+        F: FnOnce() -> Result<R, E>,
+    {
+        match self {
+            Self::Occupied(occupied) => Ok(occupied.into_mut()),
+            Self::Vacant(vacant) => f().map(|r| vacant.insert(r)),
+        }
+    }
+
+    /// Returns this entry's value, inserts and returns `R::default()`
+    /// otherwise.
+    pub fn or_default(self) -> B::RefMut<'a>
+    where
+        R: Default,
+    {
+        self.or_insert_with(R::default)
+    }
+
+    /// Runs `f` on the value if the resource exists, and returns the entry.
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut R),
+    {
+        match self {
+            Self::Occupied(mut occupied) => {
+                f(&mut occupied.get_mut());
+                Self::Occupied(occupied)
+            }
+            Self::Vacant(vacant) => Self::Vacant(vacant),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, K, R> Entry<'a, R, KeyedEntryBackend<'a, K>> {
+    /// Returns the key of this entry.
+    pub fn key(&self) -> &K {
+        match self {
+            Self::Occupied(occupied) => occupied.key(),
+            Self::Vacant(vacant) => vacant.key(),
+        }
     }
 }
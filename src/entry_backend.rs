@@ -0,0 +1,116 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::{LocalResource, LocalResources, Ref, RefMut, Resource, Resources};
+
+/// Map that an [`Entry`] accesses its resource through.
+///
+/// This lets [`Entry`], [`OccupiedEntry`], and [`VacantEntry`] be shared by
+/// [`Resources`], [`LocalResources`], and [`KeyedResources`], which borrow
+/// resources through different reference types.
+///
+/// Entries only hold a backend while its resource exists, for
+/// [`OccupiedEntry`], or while it does not exist, for [`VacantEntry`].
+///
+/// [`Entry`]: crate::Entry
+/// [`KeyedResources`]: crate::KeyedResources
+/// [`OccupiedEntry`]: crate::OccupiedEntry
+/// [`VacantEntry`]: crate::VacantEntry
+pub trait EntryBackend<'a, R>: 'a {
+    /// Immutable reference to the resource, e.g. `Ref<'b, R>`.
+    type Ref<'b>: Deref<Target = R>
+    where
+        Self: 'b;
+    /// Mutable reference to the resource, e.g. `RefMut<'b, R>`.
+    type RefMut<'b>: DerefMut<Target = R>
+    where
+        Self: 'b;
+
+    /// Returns the resource.
+    fn get(&self) -> Self::Ref<'_>;
+
+    /// Returns the resource mutably.
+    fn get_mut(&mut self) -> Self::RefMut<'_>;
+
+    /// Returns the resource mutably, with the lifetime of the map.
+    fn into_mut(self) -> Self::RefMut<'a>;
+
+    /// Inserts the resource, and returns it mutably.
+    fn insert(self, value: R) -> Self::RefMut<'a>;
+
+    /// Removes the resource from the map, and returns it.
+    fn remove(self) -> R;
+}
+
+impl<'a, R> EntryBackend<'a, R> for &'a mut Resources
+where
+    R: Resource,
+{
+    type Ref<'b>
+        = Ref<'b, R>
+    where
+        Self: 'b;
+    type RefMut<'b>
+        = RefMut<'b, R>
+    where
+        Self: 'b;
+
+    fn get(&self) -> Self::Ref<'_> {
+        self.borrow::<R>()
+    }
+
+    fn get_mut(&mut self) -> Self::RefMut<'_> {
+        self.borrow_mut::<R>()
+    }
+
+    fn into_mut(self) -> Self::RefMut<'a> {
+        let resources: &'a Resources = self;
+        resources.borrow_mut::<R>()
+    }
+
+    fn insert(self, value: R) -> Self::RefMut<'a> {
+        Resources::insert(self, value);
+        let resources: &'a Resources = self;
+        resources.borrow_mut::<R>()
+    }
+
+    fn remove(self) -> R {
+        Resources::remove::<R>(self)
+    }
+}
+
+impl<'a, R> EntryBackend<'a, R> for &'a mut LocalResources
+where
+    R: LocalResource,
+{
+    type Ref<'b>
+        = core::cell::Ref<'b, R>
+    where
+        Self: 'b;
+    type RefMut<'b>
+        = core::cell::RefMut<'b, R>
+    where
+        Self: 'b;
+
+    fn get(&self) -> Self::Ref<'_> {
+        self.borrow::<R>()
+    }
+
+    fn get_mut(&mut self) -> Self::RefMut<'_> {
+        self.borrow_mut::<R>()
+    }
+
+    fn into_mut(self) -> Self::RefMut<'a> {
+        let resources: &'a LocalResources = self;
+        resources.borrow_mut::<R>()
+    }
+
+    fn insert(self, value: R) -> Self::RefMut<'a> {
+        LocalResources::insert(self, value);
+        let resources: &'a LocalResources = self;
+        resources.borrow_mut::<R>()
+    }
+
+    fn remove(self) -> R {
+        LocalResources::remove::<R>(self)
+    }
+}
@@ -0,0 +1,128 @@
+use alloc::vec::Vec;
+
+use crate::{Ref, RefMut, Resource, ResourceError, Resources};
+
+/// Parameter of a resource function that is fetched from `Resources`.
+///
+/// Each parameter kind implements this trait once, so that `FnResource` only
+/// needs one implementation per argument count, instead of one per
+/// combination of parameter kinds.
+///
+/// This is implemented for:
+///
+/// * `&R`: Borrows `R` immutably from `Resources`.
+/// * `&mut R`: Borrows `R` mutably from `Resources`.
+/// * `Local<T>`: Borrows `T` from the function's own state.
+///
+/// Parameters whose `State` is not `()` can only be used in functions that are
+/// called through `&mut self` or `self`, i.e. `FnResMut` and `FnResOnce`.
+///
+/// See [`FetchParamLocal`] for parameters fetched from `LocalResources`.
+///
+/// [`FetchParamLocal`]: crate::FetchParamLocal
+pub trait FetchParam {
+    /// Value held for the duration of the function call, e.g. `Ref<'r, R>`.
+    type Fetched<'r>;
+    /// Argument passed to the function, e.g. `&'f R`.
+    type Item<'f>;
+    /// State stored in the `FnResource` across calls, e.g. `T` for
+    /// `Local<T>`.
+    type State: Default + Send + Sync + 'static;
+
+    /// Fetches the parameter from `resources`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameter cannot be fetched.
+    fn fetch<'r>(resources: &'r Resources, state: &'r mut Self::State) -> Self::Fetched<'r>;
+
+    /// Fetches the parameter from `resources`, returning an error if it cannot
+    /// be fetched.
+    fn try_fetch<'r>(
+        resources: &'r Resources,
+        state: &'r mut Self::State,
+    ) -> Result<Self::Fetched<'r>, ResourceError>;
+
+    /// Returns the function argument from the fetched value.
+    fn item<'f>(fetched: &'f mut Self::Fetched<'_>) -> Self::Item<'f>;
+
+    /// Appends the `TypeId` of the immutably borrowed resource, if any.
+    #[cfg(feature = "fn_meta")]
+    fn borrows(_type_ids: &mut fn_meta::TypeIds) {}
+
+    /// Appends the `TypeId` of the mutably borrowed resource, if any.
+    #[cfg(feature = "fn_meta")]
+    fn borrow_muts(_type_ids: &mut fn_meta::TypeIds) {}
+
+    /// Appends the type name of the immutably borrowed resource, if any.
+    fn borrow_type_names(_type_names: &mut Vec<&'static str>) {}
+
+    /// Appends the type name of the mutably borrowed resource, if any.
+    fn borrow_mut_type_names(_type_names: &mut Vec<&'static str>) {}
+}
+
+impl<R> FetchParam for &R
+where
+    R: Resource,
+{
+    type Fetched<'r> = Ref<'r, R>;
+    type Item<'f> = &'f R;
+    type State = ();
+
+    fn fetch<'r>(resources: &'r Resources, _state: &'r mut ()) -> Self::Fetched<'r> {
+        resources.borrow::<R>()
+    }
+
+    fn try_fetch<'r>(
+        resources: &'r Resources,
+        _state: &'r mut (),
+    ) -> Result<Self::Fetched<'r>, ResourceError> {
+        resources.try_borrow::<R>()
+    }
+
+    fn item<'f>(fetched: &'f mut Self::Fetched<'_>) -> Self::Item<'f> {
+        fetched
+    }
+
+    #[cfg(feature = "fn_meta")]
+    fn borrows(type_ids: &mut fn_meta::TypeIds) {
+        type_ids.push(core::any::TypeId::of::<R>());
+    }
+
+    fn borrow_type_names(type_names: &mut Vec<&'static str>) {
+        type_names.push(core::any::type_name::<R>());
+    }
+}
+
+impl<R> FetchParam for &mut R
+where
+    R: Resource,
+{
+    type Fetched<'r> = RefMut<'r, R>;
+    type Item<'f> = &'f mut R;
+    type State = ();
+
+    fn fetch<'r>(resources: &'r Resources, _state: &'r mut ()) -> Self::Fetched<'r> {
+        resources.borrow_mut::<R>()
+    }
+
+    fn try_fetch<'r>(
+        resources: &'r Resources,
+        _state: &'r mut (),
+    ) -> Result<Self::Fetched<'r>, ResourceError> {
+        resources.try_borrow_mut::<R>()
+    }
+
+    fn item<'f>(fetched: &'f mut Self::Fetched<'_>) -> Self::Item<'f> {
+        fetched
+    }
+
+    #[cfg(feature = "fn_meta")]
+    fn borrow_muts(type_ids: &mut fn_meta::TypeIds) {
+        type_ids.push(core::any::TypeId::of::<R>());
+    }
+
+    fn borrow_mut_type_names(type_names: &mut Vec<&'static str>) {
+        type_names.push(core::any::type_name::<R>());
+    }
+}
@@ -0,0 +1,91 @@
+use alloc::vec::Vec;
+use core::cell;
+
+use crate::{LocalResource, LocalResources, ResourceError};
+
+/// Parameter of a resource function that is fetched from `LocalResources`.
+///
+/// This is implemented for:
+///
+/// * `&R`: Borrows `R` immutably from `LocalResources`.
+/// * `&mut R`: Borrows `R` mutably from `LocalResources`.
+///
+/// `R` does not need to be `Send + Sync`, so functions with these parameters
+/// can only be called with `LocalResources`, through [`FnResLocal`].
+///
+/// [`FnResLocal`]: crate::FnResLocal
+pub trait FetchParamLocal {
+    /// Value held for the duration of the function call, e.g.
+    /// `core::cell::Ref<'r, R>`.
+    type Fetched<'r>;
+    /// Argument passed to the function, e.g. `&'f R`.
+    type Item<'f>;
+
+    /// Fetches the parameter from `resources`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameter cannot be fetched.
+    fn fetch_local(resources: &LocalResources) -> Self::Fetched<'_>;
+
+    /// Fetches the parameter from `resources`, returning an error if it cannot
+    /// be fetched.
+    fn try_fetch_local(resources: &LocalResources) -> Result<Self::Fetched<'_>, ResourceError>;
+
+    /// Returns the function argument from the fetched value.
+    fn item_local<'f>(fetched: &'f mut Self::Fetched<'_>) -> Self::Item<'f>;
+
+    /// Appends the type name of the immutably borrowed resource, if any.
+    fn borrow_type_names(_type_names: &mut Vec<&'static str>) {}
+
+    /// Appends the type name of the mutably borrowed resource, if any.
+    fn borrow_mut_type_names(_type_names: &mut Vec<&'static str>) {}
+}
+
+impl<R> FetchParamLocal for &R
+where
+    R: LocalResource,
+{
+    type Fetched<'r> = cell::Ref<'r, R>;
+    type Item<'f> = &'f R;
+
+    fn fetch_local(resources: &LocalResources) -> Self::Fetched<'_> {
+        resources.borrow::<R>()
+    }
+
+    fn try_fetch_local(resources: &LocalResources) -> Result<Self::Fetched<'_>, ResourceError> {
+        resources.try_borrow::<R>()
+    }
+
+    fn item_local<'f>(fetched: &'f mut Self::Fetched<'_>) -> Self::Item<'f> {
+        fetched
+    }
+
+    fn borrow_type_names(type_names: &mut Vec<&'static str>) {
+        type_names.push(core::any::type_name::<R>());
+    }
+}
+
+impl<R> FetchParamLocal for &mut R
+where
+    R: LocalResource,
+{
+    type Fetched<'r> = cell::RefMut<'r, R>;
+    type Item<'f> = &'f mut R;
+
+    fn fetch_local(resources: &LocalResources) -> Self::Fetched<'_> {
+        resources.borrow_mut::<R>()
+    }
+
+    fn try_fetch_local(resources: &LocalResources) -> Result<Self::Fetched<'_>, ResourceError> {
+        resources.try_borrow_mut::<R>()
+    }
+
+    fn item_local<'f>(fetched: &'f mut Self::Fetched<'_>) -> Self::Item<'f> {
+        fetched
+    }
+
+    fn borrow_mut_type_names(type_names: &mut Vec<&'static str>) {
+        type_names.push(core::any::type_name::<R>());
+    }
+}
@@ -1,8 +1,7 @@
-use std::ops::Deref;
+use alloc::{boxed::Box, vec::Vec};
+use core::ops::Deref;
 
-use rt_map::BorrowFail;
-
-use crate::Resources;
+use crate::{ResourceError, Resources};
 
 /// Function that gets its arguments / parameters from a `Resources` map.
 ///
@@ -18,7 +17,15 @@ pub trait FnRes {
     fn call(&self, resources: &Resources) -> Self::Ret;
 
     /// Runs the function.
-    fn try_call(&self, resources: &Resources) -> Result<Self::Ret, BorrowFail>;
+    fn try_call(&self, resources: &Resources) -> Result<Self::Ret, ResourceError>;
+
+    /// Returns the type names of the resources the function borrows
+    /// immutably, in parameter order.
+    fn borrow_type_names(&self) -> Vec<&'static str>;
+
+    /// Returns the type names of the resources the function borrows mutably,
+    /// in parameter order.
+    fn borrow_mut_type_names(&self) -> Vec<&'static str>;
 }
 
 /// Function that gets its arguments / parameters from a `Resources` map.
@@ -32,7 +39,15 @@ pub trait FnRes: crate::FnResMut {
     fn call(&self, resources: &Resources) -> Self::Ret;
 
     /// Runs the function.
-    fn try_call(&self, resources: &Resources) -> Result<Self::Ret, BorrowFail>;
+    fn try_call(&self, resources: &Resources) -> Result<Self::Ret, ResourceError>;
+
+    /// Returns the type names of the resources the function borrows
+    /// immutably, in parameter order.
+    fn borrow_type_names(&self) -> Vec<&'static str>;
+
+    /// Returns the type names of the resources the function borrows mutably,
+    /// in parameter order.
+    fn borrow_mut_type_names(&self) -> Vec<&'static str>;
 }
 
 /// Function that gets its arguments / parameters from a `Resources` map.
@@ -49,7 +64,50 @@ pub trait FnRes: fn_meta::FnMeta + fn_meta::FnMetaDyn {
     fn call(&self, resources: &Resources) -> Self::Ret;
 
     /// Runs the function.
-    fn try_call(&self, resources: &Resources) -> Result<Self::Ret, BorrowFail>;
+    fn try_call(&self, resources: &Resources) -> Result<Self::Ret, ResourceError>;
+
+    /// Returns the type names of the resources the function borrows
+    /// immutably, in parameter order.
+    fn borrow_type_names(&self) -> Vec<&'static str>;
+
+    /// Returns the type names of the resources the function borrows mutably,
+    /// in parameter order.
+    fn borrow_mut_type_names(&self) -> Vec<&'static str>;
+
+    /// Runs the function, only allowing it to borrow the resources in its
+    /// parameters.
+    ///
+    /// While the function runs, borrowing any other resource from
+    /// `resources` on this thread returns an `AccessDenied` error with the
+    /// function's name, so `Resources::borrow` panics. This includes borrows
+    /// through a `&Resources` that the function captured or received some
+    /// other way. The `Debug` output of `resources` only shows the type names
+    /// of other resources, not their values.
+    ///
+    /// See [`try_call_strict`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the function borrows a resource that is not in its
+    /// parameters.
+    ///
+    /// [`try_call_strict`]: Self::try_call_strict
+    #[cfg(feature = "std")]
+    fn call_strict(&self, resources: &Resources) -> Self::Ret {
+        self.try_call_strict(resources)
+            .unwrap_or_else(Resources::borrow_panic)
+    }
+
+    /// Runs the function, only allowing it to borrow the resources in its
+    /// parameters.
+    ///
+    /// See [`call_strict`] for details. If the function borrows a resource
+    /// that is not in its parameters, this returns the `AccessDenied` error
+    /// of the first such borrow, even if the function handled that error.
+    ///
+    /// [`call_strict`]: Self::call_strict
+    #[cfg(feature = "std")]
+    fn try_call_strict(&self, resources: &Resources) -> Result<Self::Ret, ResourceError>;
 }
 
 /// Function that gets its arguments / parameters from a `Resources` map.
@@ -63,7 +121,50 @@ pub trait FnRes: crate::FnResMut + fn_meta::FnMeta + fn_meta::FnMetaDyn {
     fn call(&self, resources: &Resources) -> Self::Ret;
 
     /// Runs the function.
-    fn try_call(&self, resources: &Resources) -> Result<Self::Ret, BorrowFail>;
+    fn try_call(&self, resources: &Resources) -> Result<Self::Ret, ResourceError>;
+
+    /// Returns the type names of the resources the function borrows
+    /// immutably, in parameter order.
+    fn borrow_type_names(&self) -> Vec<&'static str>;
+
+    /// Returns the type names of the resources the function borrows mutably,
+    /// in parameter order.
+    fn borrow_mut_type_names(&self) -> Vec<&'static str>;
+
+    /// Runs the function, only allowing it to borrow the resources in its
+    /// parameters.
+    ///
+    /// While the function runs, borrowing any other resource from
+    /// `resources` on this thread returns an `AccessDenied` error with the
+    /// function's name, so `Resources::borrow` panics. This includes borrows
+    /// through a `&Resources` that the function captured or received some
+    /// other way. The `Debug` output of `resources` only shows the type names
+    /// of other resources, not their values.
+    ///
+    /// See [`try_call_strict`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the function borrows a resource that is not in its
+    /// parameters.
+    ///
+    /// [`try_call_strict`]: Self::try_call_strict
+    #[cfg(feature = "std")]
+    fn call_strict(&self, resources: &Resources) -> Self::Ret {
+        self.try_call_strict(resources)
+            .unwrap_or_else(Resources::borrow_panic)
+    }
+
+    /// Runs the function, only allowing it to borrow the resources in its
+    /// parameters.
+    ///
+    /// See [`call_strict`] for details. If the function borrows a resource
+    /// that is not in its parameters, this returns the `AccessDenied` error
+    /// of the first such borrow, even if the function handled that error.
+    ///
+    /// [`call_strict`]: Self::call_strict
+    #[cfg(feature = "std")]
+    fn try_call_strict(&self, resources: &Resources) -> Result<Self::Ret, ResourceError>;
 }
 
 #[cfg(not(feature = "fn_res_mut"))]
@@ -77,9 +178,27 @@ where
         self.deref().call(resources)
     }
 
-    fn try_call(&self, resources: &Resources) -> Result<Self::Ret, BorrowFail> {
+    fn try_call(&self, resources: &Resources) -> Result<Self::Ret, ResourceError> {
         self.deref().try_call(resources)
     }
+
+    fn borrow_type_names(&self) -> Vec<&'static str> {
+        self.deref().borrow_type_names()
+    }
+
+    fn borrow_mut_type_names(&self) -> Vec<&'static str> {
+        self.deref().borrow_mut_type_names()
+    }
+
+    #[cfg(all(feature = "fn_meta", feature = "std"))]
+    fn call_strict(&self, resources: &Resources) -> Self::Ret {
+        self.deref().call_strict(resources)
+    }
+
+    #[cfg(all(feature = "fn_meta", feature = "std"))]
+    fn try_call_strict(&self, resources: &Resources) -> Result<Self::Ret, ResourceError> {
+        self.deref().try_call_strict(resources)
+    }
 }
 
 #[cfg(feature = "fn_res_mut")]
@@ -91,16 +210,35 @@ where
         self.deref().call(resources)
     }
 
-    fn try_call(&self, resources: &Resources) -> Result<Self::Ret, BorrowFail> {
+    fn try_call(&self, resources: &Resources) -> Result<Self::Ret, ResourceError> {
         self.deref().try_call(resources)
     }
+
+    fn borrow_type_names(&self) -> Vec<&'static str> {
+        self.deref().borrow_type_names()
+    }
+
+    fn borrow_mut_type_names(&self) -> Vec<&'static str> {
+        self.deref().borrow_mut_type_names()
+    }
+
+    #[cfg(all(feature = "fn_meta", feature = "std"))]
+    fn call_strict(&self, resources: &Resources) -> Self::Ret {
+        self.deref().call_strict(resources)
+    }
+
+    #[cfg(all(feature = "fn_meta", feature = "std"))]
+    fn try_call_strict(&self, resources: &Resources) -> Result<Self::Ret, ResourceError> {
+        self.deref().try_call_strict(resources)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use rt_map::BorrowFail;
+    #[cfg(all(feature = "fn_meta", feature = "std"))]
+    use std::sync::Arc;
 
-    use crate::{IntoFnRes, Resources};
+    use crate::{IntoFnRes, ResourceError, ResourceErrorKind, Resources};
 
     #[test]
     fn multiple_fn_usage() {
@@ -146,7 +284,7 @@ mod tests {
     }
 
     #[test]
-    fn try_call_no_overlap_returns_ok() -> Result<(), BorrowFail> {
+    fn try_call_no_overlap_returns_ok() -> Result<(), ResourceError> {
         let fn_reses = [
             f_r1.into_fn_res(),
             f_r2.into_fn_res(),
@@ -194,7 +332,7 @@ mod tests {
     }
 
     #[test]
-    fn try_call_with_overlap_returns_borrow_fail() -> Result<(), BorrowFail> {
+    fn try_call_with_overlap_returns_resource_error() -> Result<(), ResourceError> {
         let fn_reses = [
             f_r1.into_fn_res(),
             (|s0: &S0, s1: &mut S1| {
@@ -214,7 +352,10 @@ mod tests {
             fn_res.try_call(&resources).map(|ret| sum + ret)
         });
 
-        assert_eq!(Err(BorrowFail::BorrowConflictMut), result);
+        assert_eq!(
+            Err(ResourceErrorKind::ConflictShared),
+            result.map_err(|e| e.kind())
+        );
 
         Ok(())
     }
@@ -222,7 +363,7 @@ mod tests {
     #[cfg(feature = "fn_meta")]
     #[test]
     fn fn_meta_integration() {
-        use std::any::TypeId;
+        use core::any::TypeId;
 
         let fn_res = f_w1_r1_w1.into_fn_res();
         let borrows = fn_res.borrows();
@@ -235,10 +376,10 @@ mod tests {
         );
     }
 
-    #[cfg(all(feature = "fn_meta", feature = "high_arg_count"))]
+    #[cfg(feature = "fn_meta")]
     #[test]
     fn fn_meta_integration_high_arg_count() {
-        use std::any::TypeId;
+        use core::any::TypeId;
 
         let fn_res = f_w2_r2_w2_r1.into_fn_res();
         let borrows = fn_res.borrows();
@@ -259,6 +400,150 @@ mod tests {
         );
     }
 
+    #[cfg(all(feature = "fn_meta", feature = "std"))]
+    #[test]
+    fn call_strict_allows_borrowing_declared_resources() {
+        let resources = Arc::new(resources());
+        let fn_res = {
+            let resources = Arc::clone(&resources);
+            (move |s0: &S0, s1: &mut S1| {
+                s1.0 += 1;
+                s0.0 + s1.0 + resources.borrow::<S0>().0
+            })
+            .into_fn_res()
+        };
+
+        assert_eq!(2, fn_res.call_strict(&resources));
+        assert_eq!(Ok(3), fn_res.try_call_strict(&resources));
+
+        // Restriction is lifted after the call.
+        assert_eq!(0, resources.borrow::<S2>().0);
+    }
+
+    #[cfg(all(feature = "fn_meta", feature = "std"))]
+    #[test]
+    #[should_panic(
+        expected = "to only borrow the resources in its parameters, but it borrowed `resman::fn_res::tests::S2` immutably."
+    )]
+    fn call_strict_panics_when_borrowing_undeclared_resource() {
+        let resources = Arc::new(resources());
+        let fn_res = {
+            let resources = Arc::clone(&resources);
+            (move |s0: &S0| s0.0 + resources.borrow::<S2>().0).into_fn_res()
+        };
+
+        fn_res.call_strict(&resources);
+    }
+
+    #[cfg(all(feature = "fn_meta", feature = "std"))]
+    #[test]
+    #[should_panic(
+        expected = "to only borrow the resources in its parameters, but it borrowed `resman::fn_res::tests::S0` mutably."
+    )]
+    fn call_strict_panics_when_borrowing_declared_read_mutably() {
+        let resources = Arc::new(resources());
+        let fn_res = {
+            let resources = Arc::clone(&resources);
+            (move |s1: &S1| s1.0 + resources.borrow_mut::<S0>().0).into_fn_res()
+        };
+
+        fn_res.call_strict(&resources);
+    }
+
+    #[cfg(all(feature = "fn_meta", feature = "std"))]
+    #[test]
+    fn try_call_strict_returns_access_denied_with_fn_name() {
+        fn f_s0_s2(resources: &Resources, s0: &S0) -> usize {
+            s0.0 + resources.try_borrow::<S2>().map_or(0, |s2| s2.0)
+        }
+
+        let resources = Arc::new(resources());
+        let fn_res = {
+            let resources = Arc::clone(&resources);
+            (move |s0: &S0| f_s0_s2(&resources, s0)).into_fn_res()
+        };
+
+        let error = fn_res.try_call_strict(&resources).unwrap_err();
+
+        assert_eq!(ResourceErrorKind::AccessDenied, error.kind());
+        assert_eq!("resman::fn_res::tests::S2", error.type_name());
+        let fn_name = error.fn_name().expect("Expected the function name.");
+        assert!(
+            fn_name.starts_with("resman::fn_res::tests::try_call_strict_returns_access_denied_with_fn_name::{{closure}}"),
+            "{fn_name}"
+        );
+        assert!(!fn_name.contains("FnResource"), "{fn_name}");
+    }
+
+    #[cfg(all(feature = "fn_meta", feature = "std"))]
+    #[test]
+    fn call_strict_denies_raw_access_to_undeclared_resource() {
+        use core::any::TypeId;
+
+        let resources = Arc::new(resources());
+        let fn_res = {
+            let resources = Arc::clone(&resources);
+            (move |_s0: &S0| {
+                (
+                    resources.get_raw(&TypeId::of::<S0>()).is_some(),
+                    resources.get_raw(&TypeId::of::<S2>()).is_some(),
+                )
+            })
+            .into_fn_res()
+        };
+
+        assert!(fn_res.try_call_strict(&resources).is_err());
+        assert!(resources.get_raw(&TypeId::of::<S2>()).is_some());
+    }
+
+    #[cfg(all(feature = "fn_meta", feature = "std"))]
+    #[test]
+    fn call_strict_hides_values_of_undeclared_resources_in_debug() {
+        let mut resources = Resources::new();
+        resources.insert_debug(S0(3));
+        resources.insert_debug(S2(4));
+        let resources = Arc::new(resources);
+        let fn_res = {
+            let resources = Arc::clone(&resources);
+            (move |_s0: &S0| format!("{resources:?}")).into_fn_res()
+        };
+
+        let resources_dbg = fn_res.call_strict(&resources);
+
+        assert!(resources_dbg.contains("S0: S0(3)"), "{resources_dbg}");
+        assert!(resources_dbg.contains(r#"S2: "..""#), "{resources_dbg}");
+        assert!(format!("{resources:?}").contains("S2: S2(4)"));
+    }
+
+    #[cfg(all(feature = "fn_meta", feature = "std"))]
+    #[test]
+    fn call_strict_does_not_restrict_other_resources_maps() {
+        let resources = resources();
+        let other = Arc::new(resources_with_s2(5));
+        let fn_res = {
+            let other = Arc::clone(&other);
+            (move |s0: &S0| s0.0 + other.borrow::<S2>().0).into_fn_res()
+        };
+
+        assert_eq!(5, fn_res.call_strict(&resources));
+    }
+
+    #[cfg(all(feature = "fn_meta", feature = "std"))]
+    fn resources() -> Resources {
+        let mut resources = Resources::new();
+        resources.insert(S0(0));
+        resources.insert(S1(1));
+        resources.insert(S2(0));
+        resources
+    }
+
+    #[cfg(all(feature = "fn_meta", feature = "std"))]
+    fn resources_with_s2(value: usize) -> Resources {
+        let mut resources = Resources::new();
+        resources.insert(S2(value));
+        resources
+    }
+
     fn f_r1(s0: &S0) -> usize {
         s0.0
     }
@@ -345,7 +630,7 @@ mod tests {
 
         s0.0 + s1.0 + s2.0
     }
-    #[cfg(feature = "high_arg_count")]
+    #[cfg(feature = "fn_meta")]
     fn f_w2_r2_w2_r1(
         s0: &mut S0,
         s1: &mut S1,
@@ -375,7 +660,7 @@ mod tests {
     struct S4(usize);
     #[derive(Debug)]
     struct S5(usize);
-    #[cfg(feature = "high_arg_count")]
+    #[cfg(feature = "fn_meta")]
     #[derive(Debug)]
     struct S6(usize);
 }
@@ -0,0 +1,46 @@
+use alloc::string::String;
+use core::fmt;
+
+use crate::ResourceError;
+
+/// Error when calling a function from a [`FnResRegistry`].
+///
+/// [`FnResRegistry`]: crate::FnResRegistry
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FnResCallError {
+    /// No function is registered under the given name.
+    NotRegistered {
+        /// Name that was looked up.
+        name: String,
+    },
+    /// The function was found, but its resources could not be borrowed.
+    BorrowFail {
+        /// Name of the function.
+        name: String,
+        /// The underlying borrow error.
+        error: ResourceError,
+    },
+}
+
+impl fmt::Display for FnResCallError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotRegistered { name } => {
+                write!(f, "Function `{name}` is not registered.")
+            }
+            Self::BorrowFail { name, error: _ } => {
+                write!(f, "Failed to borrow resources for function `{name}`.")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FnResCallError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::NotRegistered { .. } => None,
+            Self::BorrowFail { error, .. } => Some(error),
+        }
+    }
+}
@@ -1,6 +1,6 @@
-use rt_map::BorrowFail;
+use alloc::vec::Vec;
 
-use crate::{FnRes, FnResource, Resources};
+use crate::{FetchParam, FnRes, FnResource, ResourceError, Resources};
 
 #[cfg(not(feature = "fn_res_mut"))]
 impl<Fun, Ret> FnRes for FnResource<Fun, Ret, ()>
@@ -14,9 +14,28 @@ where
         Self::call(self, resources)
     }
 
-    fn try_call(&self, resources: &Resources) -> Result<Ret, BorrowFail> {
+    fn try_call(&self, resources: &Resources) -> Result<Ret, ResourceError> {
         Self::try_call(self, resources)
     }
+
+    fn borrow_type_names(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    fn borrow_mut_type_names(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    #[cfg(all(feature = "fn_meta", feature = "std"))]
+    fn try_call_strict(&self, resources: &Resources) -> Result<Ret, ResourceError> {
+        crate::borrow_restriction::BorrowRestriction::try_call(
+            resources,
+            core::any::type_name::<Fun>(),
+            fn_meta::FnMetaDyn::borrows(self),
+            fn_meta::FnMetaDyn::borrow_muts(self),
+            || Self::try_call(self, resources),
+        )
+    }
 }
 
 #[cfg(feature = "fn_res_mut")]
@@ -29,9 +48,28 @@ where
         Self::call(self, resources)
     }
 
-    fn try_call(&self, resources: &Resources) -> Result<Ret, BorrowFail> {
+    fn try_call(&self, resources: &Resources) -> Result<Ret, ResourceError> {
         Self::try_call(self, resources)
     }
+
+    fn borrow_type_names(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    fn borrow_mut_type_names(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    #[cfg(all(feature = "fn_meta", feature = "std"))]
+    fn try_call_strict(&self, resources: &Resources) -> Result<Ret, ResourceError> {
+        crate::borrow_restriction::BorrowRestriction::try_call(
+            resources,
+            core::any::type_name::<Fun>(),
+            fn_meta::FnMetaDyn::borrows(self),
+            fn_meta::FnMetaDyn::borrow_muts(self),
+            || Self::try_call(self, resources),
+        )
+    }
 }
 
 // Unfortunately we have to `include!` instead of use a `#[path]` attribute.
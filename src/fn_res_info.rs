@@ -0,0 +1,33 @@
+use alloc::{string::String, vec::Vec};
+/// Name of a registered function, and the resources it borrows.
+///
+/// Returned by [`FnResRegistry::fn_res_infos`].
+///
+/// [`FnResRegistry::fn_res_infos`]: crate::FnResRegistry::fn_res_infos
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FnResInfo {
+    /// Name the function is registered under.
+    pub name: String,
+    /// Type names of the resources the function borrows immutably.
+    pub borrows: Vec<String>,
+    /// Type names of the resources the function borrows mutably.
+    pub borrow_muts: Vec<String>,
+}
+
+impl FnResInfo {
+    /// Returns the name the function is registered under.
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    /// Returns the type names of the resources the function borrows
+    /// immutably.
+    pub fn borrows(&self) -> &[String] {
+        self.borrows.as_ref()
+    }
+
+    /// Returns the type names of the resources the function borrows mutably.
+    pub fn borrow_muts(&self) -> &[String] {
+        self.borrow_muts.as_ref()
+    }
+}
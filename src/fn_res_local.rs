@@ -0,0 +1,97 @@
+use alloc::boxed::Box;
+use core::ops::Deref;
+
+use crate::{LocalResources, ResourceError};
+
+/// Function that gets its arguments / parameters from a [`LocalResources`]
+/// map.
+///
+/// This allows consumers of this library to hold onto multiple *resource
+/// functions* as `Box<dyn FnResLocal>`, even though their arguments may be
+/// `!Send` types.
+pub trait FnResLocal {
+    /// Return type of the function.
+    type Ret;
+
+    /// Runs the function.
+    fn call_local(&self, resources: &LocalResources) -> Self::Ret;
+
+    /// Runs the function.
+    fn try_call_local(&self, resources: &LocalResources) -> Result<Self::Ret, ResourceError>;
+}
+
+impl<T, Ret> FnResLocal for Box<T>
+where
+    T: FnResLocal<Ret = Ret> + ?Sized,
+{
+    type Ret = Ret;
+
+    fn call_local(&self, resources: &LocalResources) -> Self::Ret {
+        self.deref().call_local(resources)
+    }
+
+    fn try_call_local(&self, resources: &LocalResources) -> Result<Self::Ret, ResourceError> {
+        self.deref().try_call_local(resources)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{IntoFnResLocal, LocalResources, ResourceErrorKind};
+
+    #[test]
+    fn call_local_fetches_non_send_resources() {
+        let fn_reses = [
+            f_r1.into_fn_res_local(),
+            (|s0: &S0, counter: &mut Rc<RefCell<usize>>| {
+                *counter.borrow_mut() += 1;
+                s0.0 + *counter.borrow()
+            })
+            .into_fn_res_local(),
+        ];
+
+        let mut resources = LocalResources::new();
+        resources.insert(S0(1));
+        resources.insert(Rc::new(RefCell::new(10usize)));
+
+        let sum = fn_reses
+            .iter()
+            .fold(0, |sum, fn_res| sum + fn_res.call_local(&resources));
+
+        assert_eq!(13, sum);
+        assert_eq!(11, *resources.borrow::<Rc<RefCell<usize>>>().borrow());
+    }
+
+    #[test]
+    fn try_call_local_returns_resource_error() {
+        let fn_res = (|counter: &mut Rc<usize>| **counter).into_fn_res_local();
+
+        let mut local_resources = LocalResources::new();
+        local_resources.insert(Rc::new(1usize));
+
+        let counter = local_resources.borrow::<Rc<usize>>();
+        assert_eq!(
+            Err(ResourceErrorKind::ConflictShared),
+            fn_res
+                .try_call_local(&local_resources)
+                .map_err(|e| e.kind())
+        );
+        drop(counter);
+        assert_eq!(Ok(1), fn_res.try_call_local(&local_resources));
+        assert_eq!(
+            Err(ResourceErrorKind::NotFound),
+            fn_res
+                .try_call_local(&LocalResources::new())
+                .map_err(|e| e.kind())
+        );
+    }
+
+    fn f_r1(s0: &S0) -> usize {
+        s0.0
+    }
+
+    #[derive(Debug)]
+    struct S0(usize);
+}
@@ -0,0 +1,21 @@
+use crate::{FetchParamLocal, FnResLocal, FnResource, LocalResources, ResourceError};
+
+impl<Fun, Ret> FnResLocal for FnResource<Fun, Ret, ()>
+where
+    Fun: Fn() -> Ret + 'static,
+    Ret: 'static,
+{
+    type Ret = Ret;
+
+    fn call_local(&self, resources: &LocalResources) -> Ret {
+        Self::call_local(self, resources)
+    }
+
+    fn try_call_local(&self, resources: &LocalResources) -> Result<Ret, ResourceError> {
+        Self::try_call_local(self, resources)
+    }
+}
+
+// Unfortunately we have to `include!` instead of use a `#[path]` attribute.
+// Pending: <https://github.com/rust-lang/rust/issues/48250>
+include!(concat!(env!("OUT_DIR"), "/fn_res_local_impl.rs"));
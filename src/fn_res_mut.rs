@@ -1,8 +1,7 @@
-use std::ops::DerefMut;
+use alloc::boxed::Box;
+use core::ops::DerefMut;
 
-use rt_map::BorrowFail;
-
-use crate::Resources;
+use crate::{ResourceError, Resources};
 
 /// Function that gets its arguments / parameters from a `Resources` map.
 ///
@@ -18,7 +17,7 @@ pub trait FnResMut {
     fn call_mut(&mut self, resources: &Resources) -> Self::Ret;
 
     /// Runs the function.
-    fn try_call_mut(&mut self, resources: &Resources) -> Result<Self::Ret, BorrowFail>;
+    fn try_call_mut(&mut self, resources: &Resources) -> Result<Self::Ret, ResourceError>;
 }
 
 /// Function that gets its arguments / parameters from a `Resources` map.
@@ -32,7 +31,7 @@ pub trait FnResMut: crate::FnResOnce {
     fn call_mut(&mut self, resources: &Resources) -> Self::Ret;
 
     /// Runs the function.
-    fn try_call_mut(&mut self, resources: &Resources) -> Result<Self::Ret, BorrowFail>;
+    fn try_call_mut(&mut self, resources: &Resources) -> Result<Self::Ret, ResourceError>;
 }
 
 /// Function that gets its arguments / parameters from a `Resources` map.
@@ -49,7 +48,7 @@ pub trait FnResMut: fn_meta::FnMeta + fn_meta::FnMetaDyn {
     fn call_mut(&mut self, resources: &Resources) -> Self::Ret;
 
     /// Runs the function.
-    fn try_call_mut(&mut self, resources: &Resources) -> Result<Self::Ret, BorrowFail>;
+    fn try_call_mut(&mut self, resources: &Resources) -> Result<Self::Ret, ResourceError>;
 }
 
 /// Function that gets its arguments / parameters from a `Resources` map.
@@ -63,7 +62,7 @@ pub trait FnResMut: crate::FnResOnce + fn_meta::FnMeta + fn_meta::FnMetaDyn {
     fn call_mut(&mut self, resources: &Resources) -> Self::Ret;
 
     /// Runs the function.
-    fn try_call_mut(&mut self, resources: &Resources) -> Result<Self::Ret, BorrowFail>;
+    fn try_call_mut(&mut self, resources: &Resources) -> Result<Self::Ret, ResourceError>;
 }
 
 #[cfg(all(not(feature = "fn_res_once"), not(feature = "fn_meta")))]
@@ -77,7 +76,7 @@ where
         self.deref_mut().call_mut(resources)
     }
 
-    fn try_call_mut(&mut self, resources: &Resources) -> Result<Self::Ret, BorrowFail> {
+    fn try_call_mut(&mut self, resources: &Resources) -> Result<Self::Ret, ResourceError> {
         self.deref_mut().try_call_mut(resources)
     }
 }
@@ -93,7 +92,7 @@ where
         self.deref_mut().call_mut(resources)
     }
 
-    fn try_call_mut(&mut self, resources: &Resources) -> Result<Self::Ret, BorrowFail> {
+    fn try_call_mut(&mut self, resources: &Resources) -> Result<Self::Ret, ResourceError> {
         self.deref_mut().try_call_mut(resources)
     }
 }
@@ -107,7 +106,7 @@ where
         self.deref_mut().call_mut(resources)
     }
 
-    fn try_call_mut(&mut self, resources: &Resources) -> Result<Self::Ret, BorrowFail> {
+    fn try_call_mut(&mut self, resources: &Resources) -> Result<Self::Ret, ResourceError> {
         self.deref_mut().try_call_mut(resources)
     }
 }
@@ -121,14 +120,14 @@ where
         self.deref_mut().call_mut(resources)
     }
 
-    fn try_call_mut(&mut self, resources: &Resources) -> Result<Self::Ret, BorrowFail> {
+    fn try_call_mut(&mut self, resources: &Resources) -> Result<Self::Ret, ResourceError> {
         self.deref_mut().try_call_mut(resources)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{BorrowFail, FnResMut, IntoFnResource, Resources};
+    use crate::{FnResMut, IntoFnResource, ResourceErrorKind, Resources};
 
     #[cfg(feature = "fn_res_once")]
     use crate::FnResOnce;
@@ -160,8 +159,8 @@ mod tests {
 
         let _borrow = resources.borrow::<u32>();
         assert_eq!(
-            Err(BorrowFail::BorrowConflictMut),
-            fn_mut.try_call_mut(&resources)
+            Err(ResourceErrorKind::ConflictShared),
+            fn_mut.try_call_mut(&resources).map_err(|e| e.kind())
         );
     }
 
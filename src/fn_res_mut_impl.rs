@@ -1,6 +1,4 @@
-use rt_map::BorrowFail;
-
-use crate::{FnResMut, FnResource, Resources};
+use crate::{FetchParam, FnResMut, FnResource, ResourceError, Resources};
 
 #[cfg(not(feature = "fn_res_once"))]
 impl<Fun, Ret> FnResMut for FnResource<Fun, Ret, ()>
@@ -14,7 +12,7 @@ where
         Self::call_mut(self, resources)
     }
 
-    fn try_call_mut(&mut self, resources: &Resources) -> Result<Ret, BorrowFail> {
+    fn try_call_mut(&mut self, resources: &Resources) -> Result<Ret, ResourceError> {
         Self::try_call_mut(self, resources)
     }
 }
@@ -29,7 +27,7 @@ where
         Self::call_mut(self, resources)
     }
 
-    fn try_call_mut(&mut self, resources: &Resources) -> Result<Ret, BorrowFail> {
+    fn try_call_mut(&mut self, resources: &Resources) -> Result<Ret, ResourceError> {
         Self::try_call_mut(self, resources)
     }
 }
@@ -1,6 +1,6 @@
-use rt_map::BorrowFail;
+use alloc::boxed::Box;
 
-use crate::Resources;
+use crate::{ResourceError, Resources};
 
 /// Function that gets its arguments / parameters from a `Resources` map.
 ///
@@ -16,7 +16,7 @@ pub trait FnResOnce {
     fn call_once(self, resources: &Resources) -> Self::Ret;
 
     /// Runs the function.
-    fn try_call_once(self, resources: &Resources) -> Result<Self::Ret, BorrowFail>;
+    fn try_call_once(self, resources: &Resources) -> Result<Self::Ret, ResourceError>;
 }
 
 /// Function that gets its arguments / parameters from a `Resources` map.
@@ -33,7 +33,7 @@ pub trait FnResOnce: fn_meta::FnMeta + fn_meta::FnMetaDyn {
     fn call_once(self, resources: &Resources) -> Self::Ret;
 
     /// Runs the function.
-    fn try_call_once(self, resources: &Resources) -> Result<Self::Ret, BorrowFail>;
+    fn try_call_once(self, resources: &Resources) -> Result<Self::Ret, ResourceError>;
 }
 
 #[cfg(not(feature = "fn_meta"))]
@@ -48,7 +48,7 @@ where
         fn_res_once.call_once(resources)
     }
 
-    fn try_call_once(self, resources: &Resources) -> Result<Self::Ret, BorrowFail> {
+    fn try_call_once(self, resources: &Resources) -> Result<Self::Ret, ResourceError> {
         let fn_res_once = Box::into_inner(self);
         fn_res_once.try_call_once(resources)
     }
@@ -66,7 +66,7 @@ where
         fn_res_once.call_once(resources)
     }
 
-    fn try_call_once(self, resources: &Resources) -> Result<Self::Ret, BorrowFail> {
+    fn try_call_once(self, resources: &Resources) -> Result<Self::Ret, ResourceError> {
         let fn_res_once = Box::into_inner(self);
         fn_res_once.try_call_once(resources)
     }
@@ -1,6 +1,4 @@
-use rt_map::BorrowFail;
-
-use crate::{FnResOnce, FnResource, Resources};
+use crate::{FetchParam, FnResOnce, FnResource, ResourceError, Resources};
 
 impl<Fun, Ret> FnResOnce for FnResource<Fun, Ret, ()>
 where
@@ -13,7 +11,7 @@ where
         Self::call_once(self, resources)
     }
 
-    fn try_call_once(self, resources: &Resources) -> Result<Ret, BorrowFail> {
+    fn try_call_once(self, resources: &Resources) -> Result<Ret, ResourceError> {
         Self::try_call_once(self, resources)
     }
 }
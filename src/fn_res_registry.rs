@@ -0,0 +1,322 @@
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{FnRes, FnResCallError, Resources};
+
+/// Map of names to resource functions, which can be called by name.
+///
+/// This is useful when the function to run is only known at runtime, such as
+/// from an admin command or scripting hook.
+///
+/// # Examples
+///
+/// ```rust
+/// use resman::{FnResRegistry, IntoFnRes, Resources};
+///
+/// let mut fn_res_registry = FnResRegistry::<u32>::new();
+/// fn_res_registry.insert(
+///     "increment",
+///     (|a: &mut u32| {
+///         *a += 1;
+///         *a
+///     })
+///     .into_fn_res(),
+/// );
+/// fn_res_registry.insert("read", (|a: &u32| *a).into_fn_res());
+///
+/// let mut resources = Resources::new();
+/// resources.insert(0u32);
+///
+/// assert_eq!(1, fn_res_registry.call("increment", &resources));
+/// assert_eq!(1, fn_res_registry.call("read", &resources));
+/// assert!(fn_res_registry.try_call("missing", &resources).is_err());
+/// ```
+pub struct FnResRegistry<Ret> {
+    /// The registered functions, ordered by name.
+    fn_reses: BTreeMap<String, Box<dyn FnRes<Ret = Ret>>>,
+}
+
+impl<Ret> FnResRegistry<Ret> {
+    /// Returns an empty `FnResRegistry`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a function under the given name.
+    ///
+    /// If a function was already registered under that name, it is replaced
+    /// and returned.
+    pub fn insert<N>(
+        &mut self,
+        name: N,
+        fn_res: Box<dyn FnRes<Ret = Ret>>,
+    ) -> Option<Box<dyn FnRes<Ret = Ret>>>
+    where
+        N: Into<String>,
+    {
+        self.fn_reses.insert(name.into(), fn_res)
+    }
+
+    /// Removes and returns the function registered under the given name.
+    pub fn remove(&mut self, name: &str) -> Option<Box<dyn FnRes<Ret = Ret>>> {
+        self.fn_reses.remove(name)
+    }
+
+    /// Returns the function registered under the given name.
+    pub fn get(&self, name: &str) -> Option<&dyn FnRes<Ret = Ret>> {
+        self.fn_reses.get(name).map(|fn_res| &**fn_res)
+    }
+
+    /// Returns whether a function is registered under the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.fn_reses.contains_key(name)
+    }
+
+    /// Returns the number of registered functions.
+    pub fn len(&self) -> usize {
+        self.fn_reses.len()
+    }
+
+    /// Returns whether no functions are registered.
+    pub fn is_empty(&self) -> bool {
+        self.fn_reses.is_empty()
+    }
+
+    /// Returns an iterator over the registered names, in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.fn_reses.keys().map(String::as_str)
+    }
+
+    /// Runs the function registered under the given name.
+    ///
+    /// See [`try_call`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no function is registered under the given name.
+    /// Panics if the function's resources cannot be borrowed.
+    ///
+    /// [`try_call`]: Self::try_call
+    pub fn call(&self, name: &str, resources: &Resources) -> Ret {
+        self.get(name)
+            .unwrap_or_else(|| {
+                panic!(
+                    "Expected to call `{name}`, but it is not registered in the `FnResRegistry`."
+                )
+            })
+            .call(resources)
+    }
+
+    /// Runs the function registered under the given name.
+    ///
+    /// Returns an error if no function is registered under the given name, or
+    /// the function's resources cannot be borrowed.
+    pub fn try_call(&self, name: &str, resources: &Resources) -> Result<Ret, FnResCallError> {
+        let fn_res = self
+            .get(name)
+            .ok_or_else(|| FnResCallError::NotRegistered {
+                name: name.to_string(),
+            })?;
+
+        fn_res
+            .try_call(resources)
+            .map_err(|error| FnResCallError::BorrowFail {
+                name: name.to_string(),
+                error,
+            })
+    }
+
+    /// Returns the names of the registered functions, and the type names of
+    /// the resources they borrow.
+    ///
+    /// The type names come from the functions' parameter types, so this does
+    /// not need the resources to be present or borrowable.
+    pub fn fn_res_infos(&self) -> Vec<crate::FnResInfo> {
+        self.fn_reses
+            .iter()
+            .map(|(name, fn_res)| crate::FnResInfo {
+                name: name.clone(),
+                borrows: Self::type_names(fn_res.borrow_type_names()),
+                borrow_muts: Self::type_names(fn_res.borrow_mut_type_names()),
+            })
+            .collect()
+    }
+
+    fn type_names(type_names: Vec<&'static str>) -> Vec<String> {
+        type_names.into_iter().map(String::from).collect()
+    }
+}
+
+impl<Ret> Default for FnResRegistry<Ret> {
+    fn default() -> Self {
+        Self {
+            fn_reses: BTreeMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BorrowKind, FnResCallError, IntoFnRes, ResourceError, ResourceErrorKind, Resources,
+    };
+
+    use super::FnResRegistry;
+
+    #[test]
+    fn call_runs_function_registered_under_name() {
+        let fn_res_registry = fn_res_registry();
+        let mut resources = Resources::new();
+        resources.insert(S0(1));
+        resources.insert(S1(2));
+
+        assert_eq!(3, fn_res_registry.call("sum", &resources));
+        assert_eq!(4, fn_res_registry.call("increment", &resources));
+        assert_eq!(4, resources.borrow::<S1>().0);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to call `missing`, but it is not registered in the `FnResRegistry`."
+    )]
+    fn call_panics_when_name_not_registered() {
+        let fn_res_registry = fn_res_registry();
+        let resources = Resources::new();
+
+        fn_res_registry.call("missing", &resources);
+    }
+
+    #[test]
+    fn try_call_returns_err_when_name_not_registered() {
+        let fn_res_registry = fn_res_registry();
+        let resources = Resources::new();
+
+        assert_eq!(
+            Err(FnResCallError::NotRegistered {
+                name: String::from("missing")
+            }),
+            fn_res_registry.try_call("missing", &resources)
+        );
+    }
+
+    #[test]
+    fn try_call_returns_err_when_borrow_fails() {
+        let fn_res_registry = fn_res_registry();
+        let mut resources = Resources::new();
+        resources.insert(S0(1));
+        resources.insert(S1(2));
+
+        let _s1 = resources.borrow::<S1>();
+
+        assert_eq!(Ok(3), fn_res_registry.try_call("sum", &resources));
+        assert_eq!(
+            Err(FnResCallError::BorrowFail {
+                name: String::from("increment"),
+                error: ResourceError::new(
+                    core::any::type_name::<S1>(),
+                    Some(BorrowKind::Mutable),
+                    ResourceErrorKind::ConflictShared,
+                ),
+            }),
+            fn_res_registry.try_call("increment", &resources)
+        );
+    }
+
+    #[test]
+    fn insert_replaces_and_remove_removes() {
+        let mut fn_res_registry = fn_res_registry();
+        let resources = Resources::new();
+
+        assert!(fn_res_registry
+            .insert("sum", (|| 10).into_fn_res())
+            .is_some());
+        assert_eq!(10, fn_res_registry.call("sum", &resources));
+        assert!(fn_res_registry.remove("sum").is_some());
+        assert!(!fn_res_registry.contains("sum"));
+        assert_eq!(1, fn_res_registry.len());
+    }
+
+    #[test]
+    fn names_are_ordered() {
+        let fn_res_registry = fn_res_registry();
+
+        assert_eq!(
+            vec!["increment", "sum"],
+            fn_res_registry.names().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn fn_res_infos_lists_borrowed_type_names() {
+        use crate::FnResInfo;
+
+        let fn_res_registry = fn_res_registry();
+
+        assert_eq!(
+            vec![
+                FnResInfo {
+                    name: String::from("increment"),
+                    borrows: vec![String::from("resman::fn_res_registry::tests::S0")],
+                    borrow_muts: vec![String::from("resman::fn_res_registry::tests::S1")],
+                },
+                FnResInfo {
+                    name: String::from("sum"),
+                    borrows: vec![
+                        String::from("resman::fn_res_registry::tests::S0"),
+                        String::from("resman::fn_res_registry::tests::S1"),
+                    ],
+                    borrow_muts: vec![],
+                },
+            ],
+            fn_res_registry.fn_res_infos()
+        );
+    }
+
+    #[test]
+    fn fn_res_infos_lists_type_names_of_absent_resources() {
+        use crate::FnResInfo;
+
+        let mut fn_res_registry = FnResRegistry::<usize>::new();
+        fn_res_registry.insert("read", (|s0: &S0| s0.0).into_fn_res());
+        fn_res_registry.insert("none", (|| 0).into_fn_res());
+
+        assert_eq!(
+            vec![
+                FnResInfo {
+                    name: String::from("none"),
+                    borrows: vec![],
+                    borrow_muts: vec![],
+                },
+                FnResInfo {
+                    name: String::from("read"),
+                    borrows: vec![String::from("resman::fn_res_registry::tests::S0")],
+                    borrow_muts: vec![],
+                },
+            ],
+            fn_res_registry.fn_res_infos()
+        );
+    }
+
+    fn fn_res_registry() -> FnResRegistry<usize> {
+        let mut fn_res_registry = FnResRegistry::new();
+        fn_res_registry.insert("sum", (|s0: &S0, s1: &S1| s0.0 + s1.0).into_fn_res());
+        fn_res_registry.insert(
+            "increment",
+            (|s0: &S0, s1: &mut S1| {
+                s1.0 += s0.0 + 1;
+                s1.0
+            })
+            .into_fn_res(),
+        );
+        fn_res_registry
+    }
+
+    #[derive(Debug)]
+    struct S0(usize);
+    #[derive(Debug)]
+    struct S1(usize);
+}
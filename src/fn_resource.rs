@@ -1,17 +1,82 @@
-use core::marker::PhantomData;
+use alloc::boxed::Box;
+use core::{any::Any, marker::PhantomData};
+
+use crate::{FetchParam, FetchParamLocal, LocalResources, ResourceError, Resources};
+
+/// Message used when the stored parameter state is not of the expected type.
+#[cfg(any(feature = "fn_res_mut", feature = "fn_res_once"))]
+const STATE_DOWNCAST_EXPECT: &str =
+    "`FnResource` state type is determined by its `Args`, so downcasting should always succeed.";
+
+/// Returns an entered span named `$name` for calling a resource function.
+///
+/// The span has the function's type as the `fn_type` field, and its borrowed
+/// resource types as the `borrows` and `borrow_muts` fields.
+#[cfg(feature = "tracing")]
+macro_rules! fn_res_call_span {
+    ($name:literal, $fun:ty, $push_type_names:expr) => {
+        enter_with_type_names(
+            tracing::trace_span!(
+                $name,
+                fn_type = core::any::type_name::<$fun>(),
+                borrows = tracing::field::Empty,
+                borrow_muts = tracing::field::Empty,
+            ),
+            $push_type_names,
+        )
+    };
+}
+
+/// Records the borrowed resource type names on the span, and enters it.
+///
+/// Type names are only collected if the span is enabled.
+#[cfg(feature = "tracing")]
+fn enter_with_type_names<F>(span: tracing::Span, push_type_names: F) -> tracing::span::EnteredSpan
+where
+    F: FnOnce(&mut Vec<&'static str>, &mut Vec<&'static str>),
+{
+    if !span.is_disabled() {
+        let mut borrows = Vec::new();
+        let mut borrow_muts = Vec::new();
+        push_type_names(&mut borrows, &mut borrow_muts);
 
-use rt_map::BorrowFail;
+        span.record("borrows", tracing::field::debug(&borrows));
+        span.record("borrow_muts", tracing::field::debug(&borrow_muts));
+    }
 
-use crate::Resources;
+    span.entered()
+}
 
 /// Function that gets its arguments / parameters from a `Resources` map.
 pub struct FnResource<Fun, Ret, Args> {
     /// The actual function.
     pub func: Fun,
+    /// Tuple of each parameter's [`FetchParam::State`], persisted across
+    /// calls.
+    ///
+    /// This is only read by `call_mut` and `call_once`, as `call` requires
+    /// every parameter's state to be `()`.
+    #[cfg_attr(
+        not(any(feature = "fn_res_mut", feature = "fn_res_once")),
+        allow(dead_code)
+    )]
+    state: Box<dyn Any + Send + Sync>,
     /// Marker.
     marker: PhantomData<(Fun, Ret, Args)>,
 }
 
+impl<Fun, Ret, Args> FnResource<Fun, Ret, Args> {
+    /// Returns a `FnResource` whose parameters are only fetched from
+    /// `LocalResources`, so it holds no parameter state.
+    pub(crate) fn new_local(func: Fun) -> Self {
+        Self {
+            func,
+            state: Box::new(()),
+            marker: PhantomData,
+        }
+    }
+}
+
 #[cfg(feature = "fn_res_once")]
 impl<Fun, Ret> FnResource<Fun, Ret, ()>
 where
@@ -19,10 +84,16 @@ where
     Ret: 'static,
 {
     pub fn call_once(self, _resources: &Resources) -> Ret {
+        #[cfg(feature = "tracing")]
+        let _span = fn_res_call_span!("FnResOnce::call_once", Fun, |_, _| {});
+
         (self.func)()
     }
 
-    pub fn try_call_once(self, _resources: &Resources) -> Result<Ret, BorrowFail> {
+    pub fn try_call_once(self, _resources: &Resources) -> Result<Ret, ResourceError> {
+        #[cfg(feature = "tracing")]
+        let _span = fn_res_call_span!("FnResOnce::try_call_once", Fun, |_, _| {});
+
         let ret_value = (self.func)();
         Ok(ret_value)
     }
@@ -35,10 +106,16 @@ where
     Ret: 'static,
 {
     pub fn call_mut(&mut self, _resources: &Resources) -> Ret {
+        #[cfg(feature = "tracing")]
+        let _span = fn_res_call_span!("FnResMut::call_mut", Fun, |_, _| {});
+
         (self.func)()
     }
 
-    pub fn try_call_mut(&mut self, _resources: &Resources) -> Result<Ret, BorrowFail> {
+    pub fn try_call_mut(&mut self, _resources: &Resources) -> Result<Ret, ResourceError> {
+        #[cfg(feature = "tracing")]
+        let _span = fn_res_call_span!("FnResMut::try_call_mut", Fun, |_, _| {});
+
         let ret_value = (self.func)();
         Ok(ret_value)
     }
@@ -50,10 +127,31 @@ where
     Ret: 'static,
 {
     pub fn call(&self, _resources: &Resources) -> Ret {
+        #[cfg(feature = "tracing")]
+        let _span = fn_res_call_span!("FnRes::call", Fun, |_, _| {});
+
+        (self.func)()
+    }
+
+    pub fn try_call(&self, _resources: &Resources) -> Result<Ret, ResourceError> {
+        #[cfg(feature = "tracing")]
+        let _span = fn_res_call_span!("FnRes::try_call", Fun, |_, _| {});
+
+        let ret_value = (self.func)();
+        Ok(ret_value)
+    }
+
+    pub fn call_local(&self, _resources: &LocalResources) -> Ret {
+        #[cfg(feature = "tracing")]
+        let _span = fn_res_call_span!("FnResLocal::call_local", Fun, |_, _| {});
+
         (self.func)()
     }
 
-    pub fn try_call(&self, _resources: &Resources) -> Result<Ret, BorrowFail> {
+    pub fn try_call_local(&self, _resources: &LocalResources) -> Result<Ret, ResourceError> {
+        #[cfg(feature = "tracing")]
+        let _span = fn_res_call_span!("FnResLocal::try_call_local", Fun, |_, _| {});
+
         let ret_value = (self.func)();
         Ok(ret_value)
     }
@@ -103,118 +201,15 @@ where
     fn into_fn_resource(self) -> FnResource<Fun, Ret, ()> {
         FnResource {
             func: self,
+            state: Box::new(()),
             marker: PhantomData,
         }
     }
 }
 
-impl<Fun, Ret, A> IntoFnResource<Fun, Ret, (A,)> for Fun
-where
-    Fun: FnOnce(A) -> Ret + 'static,
-    Ret: 'static,
-{
-    fn into_fn_resource(self) -> FnResource<Fun, Ret, (A,)> {
-        FnResource {
-            func: self,
-            marker: PhantomData,
-        }
-    }
-}
-
-impl<Fun, Ret, A, B> IntoFnResource<Fun, Ret, (A, B)> for Fun
-where
-    Fun: FnOnce(A, B) -> Ret + 'static,
-    Ret: 'static,
-{
-    fn into_fn_resource(self) -> FnResource<Fun, Ret, (A, B)> {
-        FnResource {
-            func: self,
-            marker: PhantomData,
-        }
-    }
-}
-
-impl<Fun, Ret, A, B, C> IntoFnResource<Fun, Ret, (A, B, C)> for Fun
-where
-    Fun: FnOnce(A, B, C) -> Ret + 'static,
-    Ret: 'static,
-{
-    fn into_fn_resource(self) -> FnResource<Fun, Ret, (A, B, C)> {
-        FnResource {
-            func: self,
-            marker: PhantomData,
-        }
-    }
-}
-
-impl<Fun, Ret, A, B, C, D> IntoFnResource<Fun, Ret, (A, B, C, D)> for Fun
-where
-    Fun: FnOnce(A, B, C, D) -> Ret + 'static,
-    Ret: 'static,
-{
-    fn into_fn_resource(self) -> FnResource<Fun, Ret, (A, B, C, D)> {
-        FnResource {
-            func: self,
-            marker: PhantomData,
-        }
-    }
-}
-
-impl<Fun, Ret, A, B, C, D, E> IntoFnResource<Fun, Ret, (A, B, C, D, E)> for Fun
-where
-    Fun: FnOnce(A, B, C, D, E) -> Ret + 'static,
-    Ret: 'static,
-{
-    fn into_fn_resource(self) -> FnResource<Fun, Ret, (A, B, C, D, E)> {
-        FnResource {
-            func: self,
-            marker: PhantomData,
-        }
-    }
-}
-
-impl<Fun, Ret, A, B, C, D, E, F> IntoFnResource<Fun, Ret, (A, B, C, D, E, F)> for Fun
-where
-    Fun: FnOnce(A, B, C, D, E, F) -> Ret + 'static,
-    Ret: 'static,
-{
-    fn into_fn_resource(self) -> FnResource<Fun, Ret, (A, B, C, D, E, F)> {
-        FnResource {
-            func: self,
-            marker: PhantomData,
-        }
-    }
-}
-
-#[cfg(feature = "high_arg_count")]
-impl<Fun, Ret, A, B, C, D, E, F, G> IntoFnResource<Fun, Ret, (A, B, C, D, E, F, G)> for Fun
-where
-    Fun: FnOnce(A, B, C, D, E, F, G) -> Ret + 'static,
-    Ret: 'static,
-{
-    #[allow(clippy::type_complexity)]
-    fn into_fn_resource(self) -> FnResource<Fun, Ret, (A, B, C, D, E, F, G)> {
-        FnResource {
-            func: self,
-            marker: PhantomData,
-        }
-    }
-}
-
-#[cfg(feature = "high_arg_count")]
-impl<Fun, Ret, A, B, C, D, E, F, G, H> IntoFnResource<Fun, Ret, (A, B, C, D, E, F, G, H)> for Fun
-where
-    Fun: FnOnce(A, B, C, D, E, F, G, H) -> Ret + 'static,
-    Ret: 'static,
-{
-    #[allow(clippy::type_complexity)]
-    fn into_fn_resource(self) -> FnResource<Fun, Ret, (A, B, C, D, E, F, G, H)> {
-        FnResource {
-            func: self,
-            marker: PhantomData,
-        }
-    }
-}
+// Unfortunately we have to `include!` instead of use a `#[path]` attribute.
+// Pending: <https://github.com/rust-lang/rust/issues/48250>
+include!(concat!(env!("OUT_DIR"), "/fn_resource_impl.rs"));
 
 #[cfg(test)]
 mod tests {
@@ -541,7 +536,6 @@ mod tests {
         assert_eq!(5, sum);
     }
 
-    #[cfg(feature = "high_arg_count")]
     #[test]
     fn write_2_read_2_write_2_read_1() {
         let fn_res = f_w2_r2_w2_r1.into_fn_resource();
@@ -566,10 +560,40 @@ mod tests {
         assert_eq!(25, sum);
     }
 
-    #[cfg(all(feature = "fn_meta", feature = "high_arg_count"))]
+    #[test]
+    fn read_8_write_8() {
+        let fn_res = f_r8_w8.into_fn_resource();
+        let mut resources = Resources::new();
+        resources.insert(S0(0));
+        resources.insert(S1(1));
+        resources.insert(S2(2));
+        resources.insert(S3(3));
+        resources.insert(S4(4));
+        resources.insert(S5(5));
+        resources.insert(S6(6));
+        resources.insert(S7(7));
+        resources.insert(S8(8));
+        resources.insert(S9(9));
+        resources.insert(S10(10));
+        resources.insert(S11(11));
+        resources.insert(S12(12));
+        resources.insert(S13(13));
+        resources.insert(S14(14));
+        resources.insert(S15(15));
+
+        let sum = fn_res.call(&resources);
+
+        assert_eq!(0, resources.borrow::<S0>().0);
+        assert_eq!(2, resources.borrow::<S1>().0);
+        assert_eq!(14, resources.borrow::<S14>().0);
+        assert_eq!(16, resources.borrow::<S15>().0);
+        assert_eq!(128, sum);
+    }
+
+    #[cfg(feature = "fn_meta")]
     #[test]
     fn fn_meta_integration() {
-        use std::any::TypeId;
+        use core::any::TypeId;
 
         use fn_meta::FnMetaDyn;
 
@@ -592,6 +616,77 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "fn_meta")]
+    #[test]
+    fn fn_meta_integration_16_args() {
+        use core::any::TypeId;
+
+        use fn_meta::FnMetaDyn;
+
+        let fn_res = f_r8_w8.into_fn_resource();
+        let borrows = fn_res.borrows();
+        let borrow_muts = fn_res.borrow_muts();
+
+        assert_eq!(8, borrows.len());
+        assert_eq!(TypeId::of::<S0>(), borrows[0]);
+        assert_eq!(TypeId::of::<S14>(), borrows[7]);
+        assert_eq!(8, borrow_muts.len());
+        assert_eq!(TypeId::of::<S1>(), borrow_muts[0]);
+        assert_eq!(TypeId::of::<S15>(), borrow_muts[7]);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn call_enters_span_with_borrowed_type_names() {
+        use crate::test_subscriber::TestSubscriber;
+
+        let fn_res = f_r1_w1.into_fn_resource();
+        let mut resources = Resources::new();
+        resources.insert(S0(1));
+        resources.insert(S1(2));
+        let test_subscriber = TestSubscriber::default();
+
+        tracing::subscriber::with_default(test_subscriber.clone(), || {
+            fn_res.call(&resources);
+            let _s1 = resources.borrow::<S1>();
+            assert!(fn_res.try_call(&resources).is_err());
+        });
+
+        let records = test_subscriber.records();
+        assert_eq!(
+            r#"span FnRes::call: fn_type="resman::fn_resource::tests::f_r1_w1" borrows=["resman::fn_resource::tests::S0"] borrow_muts=["resman::fn_resource::tests::S1"]"#,
+            records[0]
+        );
+        assert_eq!(
+            r#"span FnRes::try_call: fn_type="resman::fn_resource::tests::f_r1_w1" borrows=["resman::fn_resource::tests::S0"] borrow_muts=["resman::fn_resource::tests::S1"]"#,
+            records[4]
+        );
+        assert_eq!(7, records.len());
+    }
+
+    #[cfg(all(feature = "tracing", feature = "fn_res_mut"))]
+    #[test]
+    fn call_mut_enters_span() {
+        use crate::test_subscriber::TestSubscriber;
+
+        let mut fn_res = (|| 1).into_fn_resource();
+        let resources = Resources::new();
+        let test_subscriber = TestSubscriber::default();
+
+        tracing::subscriber::with_default(test_subscriber.clone(), || {
+            fn_res.call_mut(&resources);
+        });
+
+        let records = test_subscriber.records();
+        assert_eq!(1, records.len());
+        assert!(
+            records[0].starts_with("span FnResMut::call_mut: fn_type=")
+                && records[0].ends_with(" borrows=[] borrow_muts=[]"),
+            "Expected `{}` to be a `FnResMut::call_mut` span.",
+            records[0]
+        );
+    }
+
     fn f_r1(s0: &S0) -> usize {
         s0.0
     }
@@ -678,7 +773,6 @@ mod tests {
 
         s0.0 + s1.0 + s2.0
     }
-    #[cfg(feature = "high_arg_count")]
     fn f_w2_r2_w2_r1(
         s0: &mut S0,
         s1: &mut S1,
@@ -696,6 +790,51 @@ mod tests {
         s0.0 + s1.0 + s2.0 + s3.0 + s4.0 + s5.0 + s6.0
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn f_r8_w8(
+        s0: &S0,
+        s1: &mut S1,
+        s2: &S2,
+        s3: &mut S3,
+        s4: &S4,
+        s5: &mut S5,
+        s6: &S6,
+        s7: &mut S7,
+        s8: &S8,
+        s9: &mut S9,
+        s10: &S10,
+        s11: &mut S11,
+        s12: &S12,
+        s13: &mut S13,
+        s14: &S14,
+        s15: &mut S15,
+    ) -> usize {
+        s1.0 += 1;
+        s3.0 += 1;
+        s5.0 += 1;
+        s7.0 += 1;
+        s9.0 += 1;
+        s11.0 += 1;
+        s13.0 += 1;
+        s15.0 += 1;
+
+        s0.0 + s1.0
+            + s2.0
+            + s3.0
+            + s4.0
+            + s5.0
+            + s6.0
+            + s7.0
+            + s8.0
+            + s9.0
+            + s10.0
+            + s11.0
+            + s12.0
+            + s13.0
+            + s14.0
+            + s15.0
+    }
+
     #[derive(Debug)]
     struct S0(usize);
     #[derive(Debug)]
@@ -708,7 +847,24 @@ mod tests {
     struct S4(usize);
     #[derive(Debug)]
     struct S5(usize);
-    #[cfg(feature = "high_arg_count")]
     #[derive(Debug)]
     struct S6(usize);
+    #[derive(Debug)]
+    struct S7(usize);
+    #[derive(Debug)]
+    struct S8(usize);
+    #[derive(Debug)]
+    struct S9(usize);
+    #[derive(Debug)]
+    struct S10(usize);
+    #[derive(Debug)]
+    struct S11(usize);
+    #[derive(Debug)]
+    struct S12(usize);
+    #[derive(Debug)]
+    struct S13(usize);
+    #[derive(Debug)]
+    struct S14(usize);
+    #[derive(Debug)]
+    struct S15(usize);
 }
@@ -1,4 +1,4 @@
-use crate::FnResource;
+use crate::{FetchParam, FnResource};
 
 // Unfortunately we have to `include!` instead of use a `#[path]` attribute.
 // Pending: <https://github.com/rust-lang/rust/issues/48250>
@@ -0,0 +1,23 @@
+use crate::{FetchParamLocal, FnResLocal, FnResource};
+use alloc::boxed::Box;
+
+/// Extension to return `Box<dyn FnResLocal>` for a function.
+pub trait IntoFnResLocal<Fun, Ret, Args> {
+    /// Returns the function wrapped as a `Box<dyn FnResLocal>`.
+    fn into_fn_res_local(self) -> Box<dyn FnResLocal<Ret = Ret>>;
+}
+
+impl<Fun, Ret> IntoFnResLocal<Fun, Ret, ()> for Fun
+where
+    Fun: Fn() -> Ret + 'static,
+    Ret: 'static,
+    FnResource<Fun, Ret, ()>: FnResLocal<Ret = Ret>,
+{
+    fn into_fn_res_local(self) -> Box<dyn FnResLocal<Ret = Ret>> {
+        Box::new(FnResource::new_local(self))
+    }
+}
+
+// Unfortunately we have to `include!` instead of use a `#[path]` attribute.
+// Pending: <https://github.com/rust-lang/rust/issues/48250>
+include!(concat!(env!("OUT_DIR"), "/into_fn_res_local_impl.rs"));
@@ -1,4 +1,5 @@
-use crate::{FnResMut, FnResource, IntoFnResource};
+use crate::{FetchParam, FnResMut, FnResource, IntoFnResource};
+use alloc::boxed::Box;
 
 /// Extension to return `Box<dyn FnResMut>` for a function.
 pub trait IntoFnResMut<Fun, Ret, Args> {
@@ -17,131 +18,9 @@ where
     }
 }
 
-impl<Fun, Ret, A> IntoFnResMut<Fun, Ret, (A,)> for Fun
-where
-    Fun: FnMut(A) -> Ret + 'static,
-    Ret: 'static,
-    A: 'static,
-    FnResource<Fun, Ret, (A,)>: FnResMut<Ret = Ret>,
-{
-    fn into_fn_res_mut(self) -> Box<dyn FnResMut<Ret = Ret>> {
-        Box::new(self.into_fn_resource())
-    }
-}
-
-impl<Fun, Ret, A, B> IntoFnResMut<Fun, Ret, (A, B)> for Fun
-where
-    Fun: FnMut(A, B) -> Ret + 'static,
-    Ret: 'static,
-    A: 'static,
-    B: 'static,
-    FnResource<Fun, Ret, (A, B)>: FnResMut<Ret = Ret>,
-{
-    fn into_fn_res_mut(self) -> Box<dyn FnResMut<Ret = Ret>> {
-        Box::new(self.into_fn_resource())
-    }
-}
-
-impl<Fun, Ret, A, B, C> IntoFnResMut<Fun, Ret, (A, B, C)> for Fun
-where
-    Fun: FnMut(A, B, C) -> Ret + 'static,
-    Ret: 'static,
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    FnResource<Fun, Ret, (A, B, C)>: FnResMut<Ret = Ret>,
-{
-    fn into_fn_res_mut(self) -> Box<dyn FnResMut<Ret = Ret>> {
-        Box::new(self.into_fn_resource())
-    }
-}
-
-impl<Fun, Ret, A, B, C, D> IntoFnResMut<Fun, Ret, (A, B, C, D)> for Fun
-where
-    Fun: FnMut(A, B, C, D) -> Ret + 'static,
-    Ret: 'static,
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    FnResource<Fun, Ret, (A, B, C, D)>: FnResMut<Ret = Ret>,
-{
-    fn into_fn_res_mut(self) -> Box<dyn FnResMut<Ret = Ret>> {
-        Box::new(self.into_fn_resource())
-    }
-}
-
-impl<Fun, Ret, A, B, C, D, E> IntoFnResMut<Fun, Ret, (A, B, C, D, E)> for Fun
-where
-    Fun: FnMut(A, B, C, D, E) -> Ret + 'static,
-    Ret: 'static,
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    E: 'static,
-    FnResource<Fun, Ret, (A, B, C, D, E)>: FnResMut<Ret = Ret>,
-{
-    fn into_fn_res_mut(self) -> Box<dyn FnResMut<Ret = Ret>> {
-        Box::new(self.into_fn_resource())
-    }
-}
-
-impl<Fun, Ret, A, B, C, D, E, F> IntoFnResMut<Fun, Ret, (A, B, C, D, E, F)> for Fun
-where
-    Fun: FnMut(A, B, C, D, E, F) -> Ret + 'static,
-    Ret: 'static,
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    E: 'static,
-    F: 'static,
-    FnResource<Fun, Ret, (A, B, C, D, E, F)>: FnResMut<Ret = Ret>,
-{
-    fn into_fn_res_mut(self) -> Box<dyn FnResMut<Ret = Ret>> {
-        Box::new(self.into_fn_resource())
-    }
-}
-
-#[cfg(feature = "high_arg_count")]
-impl<Fun, Ret, A, B, C, D, E, F, G> IntoFnResMut<Fun, Ret, (A, B, C, D, E, F, G)> for Fun
-where
-    Fun: FnMut(A, B, C, D, E, F, G) -> Ret + 'static,
-    Ret: 'static,
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    E: 'static,
-    F: 'static,
-    G: 'static,
-    FnResource<Fun, Ret, (A, B, C, D, E, F, G)>: FnResMut<Ret = Ret>,
-{
-    fn into_fn_res_mut(self) -> Box<dyn FnResMut<Ret = Ret>> {
-        Box::new(self.into_fn_resource())
-    }
-}
-
-#[cfg(feature = "high_arg_count")]
-impl<Fun, Ret, A, B, C, D, E, F, G, H> IntoFnResMut<Fun, Ret, (A, B, C, D, E, F, G, H)> for Fun
-where
-    Fun: FnMut(A, B, C, D, E, F, G, H) -> Ret + 'static,
-    Ret: 'static,
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    E: 'static,
-    F: 'static,
-    G: 'static,
-    H: 'static,
-    FnResource<Fun, Ret, (A, B, C, D, E, F, G, H)>: FnResMut<Ret = Ret>,
-{
-    fn into_fn_res_mut(self) -> Box<dyn FnResMut<Ret = Ret>> {
-        Box::new(self.into_fn_resource())
-    }
-}
+// Unfortunately we have to `include!` instead of use a `#[path]` attribute.
+// Pending: <https://github.com/rust-lang/rust/issues/48250>
+include!(concat!(env!("OUT_DIR"), "/into_fn_res_mut_impl.rs"));
 
 #[cfg(test)]
 mod tests {
@@ -194,7 +73,6 @@ mod tests {
         .into_fn_res_mut();
     }
 
-    #[cfg(feature = "high_arg_count")]
     #[test]
     fn into_fn_res_mut_high_arg_count() {
         let mut d7 = 7usize;
@@ -224,10 +102,8 @@ mod tests {
     struct S4;
     #[derive(Debug)]
     struct S5;
-    #[cfg(feature = "high_arg_count")]
     #[derive(Debug)]
     struct S6;
-    #[cfg(feature = "high_arg_count")]
     #[derive(Debug)]
     struct S7;
 }
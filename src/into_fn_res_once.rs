@@ -1,4 +1,5 @@
-use crate::{FnResOnce, FnResource, IntoFnResource};
+use crate::{FetchParam, FnResOnce, FnResource, IntoFnResource};
+use alloc::boxed::Box;
 
 /// Extension to return `Box<dyn FnResOnce>` for a function.
 pub trait IntoFnResOnce<Fun, Ret, Args> {
@@ -17,131 +18,9 @@ where
     }
 }
 
-impl<Fun, Ret, A> IntoFnResOnce<Fun, Ret, (A,)> for Fun
-where
-    Fun: FnOnce(A) -> Ret + 'static,
-    Ret: 'static,
-    A: 'static,
-    FnResource<Fun, Ret, (A,)>: FnResOnce<Ret = Ret>,
-{
-    fn into_fn_res_once(self) -> Box<dyn FnResOnce<Ret = Ret>> {
-        Box::new(self.into_fn_resource())
-    }
-}
-
-impl<Fun, Ret, A, B> IntoFnResOnce<Fun, Ret, (A, B)> for Fun
-where
-    Fun: FnOnce(A, B) -> Ret + 'static,
-    Ret: 'static,
-    A: 'static,
-    B: 'static,
-    FnResource<Fun, Ret, (A, B)>: FnResOnce<Ret = Ret>,
-{
-    fn into_fn_res_once(self) -> Box<dyn FnResOnce<Ret = Ret>> {
-        Box::new(self.into_fn_resource())
-    }
-}
-
-impl<Fun, Ret, A, B, C> IntoFnResOnce<Fun, Ret, (A, B, C)> for Fun
-where
-    Fun: FnOnce(A, B, C) -> Ret + 'static,
-    Ret: 'static,
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    FnResource<Fun, Ret, (A, B, C)>: FnResOnce<Ret = Ret>,
-{
-    fn into_fn_res_once(self) -> Box<dyn FnResOnce<Ret = Ret>> {
-        Box::new(self.into_fn_resource())
-    }
-}
-
-impl<Fun, Ret, A, B, C, D> IntoFnResOnce<Fun, Ret, (A, B, C, D)> for Fun
-where
-    Fun: FnOnce(A, B, C, D) -> Ret + 'static,
-    Ret: 'static,
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    FnResource<Fun, Ret, (A, B, C, D)>: FnResOnce<Ret = Ret>,
-{
-    fn into_fn_res_once(self) -> Box<dyn FnResOnce<Ret = Ret>> {
-        Box::new(self.into_fn_resource())
-    }
-}
-
-impl<Fun, Ret, A, B, C, D, E> IntoFnResOnce<Fun, Ret, (A, B, C, D, E)> for Fun
-where
-    Fun: FnOnce(A, B, C, D, E) -> Ret + 'static,
-    Ret: 'static,
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    E: 'static,
-    FnResource<Fun, Ret, (A, B, C, D, E)>: FnResOnce<Ret = Ret>,
-{
-    fn into_fn_res_once(self) -> Box<dyn FnResOnce<Ret = Ret>> {
-        Box::new(self.into_fn_resource())
-    }
-}
-
-impl<Fun, Ret, A, B, C, D, E, F> IntoFnResOnce<Fun, Ret, (A, B, C, D, E, F)> for Fun
-where
-    Fun: FnOnce(A, B, C, D, E, F) -> Ret + 'static,
-    Ret: 'static,
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    E: 'static,
-    F: 'static,
-    FnResource<Fun, Ret, (A, B, C, D, E, F)>: FnResOnce<Ret = Ret>,
-{
-    fn into_fn_res_once(self) -> Box<dyn FnResOnce<Ret = Ret>> {
-        Box::new(self.into_fn_resource())
-    }
-}
-
-#[cfg(feature = "high_arg_count")]
-impl<Fun, Ret, A, B, C, D, E, F, G> IntoFnResOnce<Fun, Ret, (A, B, C, D, E, F, G)> for Fun
-where
-    Fun: FnOnce(A, B, C, D, E, F, G) -> Ret + 'static,
-    Ret: 'static,
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    E: 'static,
-    F: 'static,
-    G: 'static,
-    FnResource<Fun, Ret, (A, B, C, D, E, F, G)>: FnResOnce<Ret = Ret>,
-{
-    fn into_fn_res_once(self) -> Box<dyn FnResOnce<Ret = Ret>> {
-        Box::new(self.into_fn_resource())
-    }
-}
-
-#[cfg(feature = "high_arg_count")]
-impl<Fun, Ret, A, B, C, D, E, F, G, H> IntoFnResOnce<Fun, Ret, (A, B, C, D, E, F, G, H)> for Fun
-where
-    Fun: FnOnce(A, B, C, D, E, F, G, H) -> Ret + 'static,
-    Ret: 'static,
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    E: 'static,
-    F: 'static,
-    G: 'static,
-    H: 'static,
-    FnResource<Fun, Ret, (A, B, C, D, E, F, G, H)>: FnResOnce<Ret = Ret>,
-{
-    fn into_fn_res_once(self) -> Box<dyn FnResOnce<Ret = Ret>> {
-        Box::new(self.into_fn_resource())
-    }
-}
+// Unfortunately we have to `include!` instead of use a `#[path]` attribute.
+// Pending: <https://github.com/rust-lang/rust/issues/48250>
+include!(concat!(env!("OUT_DIR"), "/into_fn_res_once_impl.rs"));
 
 #[cfg(test)]
 mod tests {
@@ -166,7 +45,6 @@ mod tests {
         let _ = (move |_: &S0, _: &S1, _: &S2, _: &S3, _: &S4, _: &S5| d6).into_fn_res_once();
     }
 
-    #[cfg(feature = "high_arg_count")]
     #[test]
     fn into_fn_res_once_high_arg_count() {
         let d7 = D;
@@ -192,10 +70,8 @@ mod tests {
     struct S4;
     #[derive(Debug)]
     struct S5;
-    #[cfg(feature = "high_arg_count")]
     #[derive(Debug)]
     struct S6;
-    #[cfg(feature = "high_arg_count")]
     #[derive(Debug)]
     struct S7;
 }
@@ -0,0 +1,69 @@
+use core::hash::Hash;
+
+use crate::{EntryBackend, KeyedResources, Ref, RefMut, Resource};
+
+/// [`EntryBackend`] for a resource under a key in [`KeyedResources`].
+///
+/// Returned as part of [`KeyedResources::entry`]. The entry's key is available
+/// through `Entry::key`.
+///
+/// [`KeyedResources::entry`]: crate::KeyedResources::entry
+pub struct KeyedEntryBackend<'a, K> {
+    /// The resources that contain, or will contain, the resource.
+    resources: &'a mut KeyedResources<K>,
+    /// The key of the resource.
+    key: K,
+}
+
+impl<'a, K> KeyedEntryBackend<'a, K> {
+    /// Returns a new `KeyedEntryBackend`.
+    pub(crate) fn new(resources: &'a mut KeyedResources<K>, key: K) -> Self {
+        Self { resources, key }
+    }
+
+    /// Returns the key of the resource.
+    pub(crate) fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Returns the key of the resource.
+    pub(crate) fn into_key(self) -> K {
+        self.key
+    }
+}
+
+impl<'a, K, R> EntryBackend<'a, R> for KeyedEntryBackend<'a, K>
+where
+    K: Hash + Eq,
+    R: Resource,
+{
+    type Ref<'b>
+        = Ref<'b, R>
+    where
+        Self: 'b;
+    type RefMut<'b>
+        = RefMut<'b, R>
+    where
+        Self: 'b;
+
+    fn get(&self) -> Self::Ref<'_> {
+        self.resources.borrow::<R>(&self.key)
+    }
+
+    fn get_mut(&mut self) -> Self::RefMut<'_> {
+        self.resources.borrow_mut::<R>(&self.key)
+    }
+
+    fn into_mut(self) -> Self::RefMut<'a> {
+        let resources: &'a KeyedResources<K> = self.resources;
+        resources.borrow_mut::<R>(&self.key)
+    }
+
+    fn insert(self, value: R) -> Self::RefMut<'a> {
+        self.resources.insert_vacant(self.key, value)
+    }
+
+    fn remove(self) -> R {
+        self.resources.remove::<R>(&self.key)
+    }
+}
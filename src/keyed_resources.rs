@@ -0,0 +1,528 @@
+use alloc::{boxed::Box, vec};
+use core::{any::TypeId, fmt, hash::Hash};
+use std::collections::HashMap;
+
+use crate::{
+    BorrowFail, BorrowKind, Cell, Entry, KeyedEntryBackend, OccupiedEntry, Ref, RefMut, Resource,
+    ResourceError, ResourceErrorKind, Resources, VacantEntry,
+};
+
+/// Map from a user-defined key to a resource.
+///
+/// Where [`Resources`] holds one value per type, `KeyedResources` holds one
+/// value per key, such as an asset ID or entity ID. Values under different
+/// keys may be of different types, and borrowing checks that the value under
+/// the key is of the requested type.
+///
+/// Borrows are checked at runtime in the same way as `Resources`, and return
+/// the same [`Ref`] and [`RefMut`] types.
+///
+/// # Examples
+///
+/// ```rust
+/// use resman::{KeyedResources, ResourceErrorKind};
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// struct AssetId(u32);
+///
+/// let mut assets = KeyedResources::new();
+/// assets.insert(AssetId(1), String::from("sprite.png"));
+/// assets.insert(AssetId(2), 64u32);
+///
+/// assets.borrow_mut::<String>(&AssetId(1)).push_str(".bak");
+/// assert_eq!("sprite.png.bak", *assets.borrow::<String>(&AssetId(1)));
+/// assert_eq!(
+///     Some(ResourceErrorKind::TypeMismatch),
+///     assets
+///         .try_borrow::<String>(&AssetId(2))
+///         .err()
+///         .map(|error| error.kind())
+/// );
+/// ```
+///
+/// [`Resources`]: crate::Resources
+pub struct KeyedResources<K> {
+    /// The resources, keyed by `K`.
+    inner: HashMap<K, Cell<Box<dyn Resource>>>,
+}
+
+impl<K> Default for KeyedResources<K> {
+    fn default() -> Self {
+        Self {
+            inner: HashMap::default(),
+        }
+    }
+}
+
+impl<K> KeyedResources<K>
+where
+    K: Hash + Eq,
+{
+    /// Creates an empty `KeyedResources` map.
+    ///
+    /// The map is initially created with a capacity of 0, so it will not
+    /// allocate until it is first inserted into.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use resman::KeyedResources;
+    /// let mut resources = KeyedResources::<u32>::new();
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty `KeyedResources` map with the specified capacity.
+    ///
+    /// The map will be able to hold at least capacity elements without
+    /// reallocating. If capacity is 0, the map will not allocate.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            inner: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of elements the map can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// Returns the number of resources in the map.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns true if the map contains no resources.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Returns an iterator over the keys in the map, in arbitrary order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.inner.keys()
+    }
+
+    /// Returns an entry for the resource under `key`.
+    ///
+    /// See [`try_entry`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value under `key` is not an `R`.
+    ///
+    /// [`try_entry`]: Self::try_entry
+    pub fn entry<R>(&mut self, key: K) -> Entry<'_, R, KeyedEntryBackend<'_, K>>
+    where
+        R: Resource,
+    {
+        self.try_entry::<R>(key).unwrap_or_else(|error| {
+            let type_name = error.type_name();
+            let type_name_actual = error.suggestions().join(", ");
+            panic!(
+                "Expected the entry to be `{type_name}`, but the value under the key is `{type_name_actual}`."
+            )
+        })
+    }
+
+    /// Returns an entry for the resource under `key`, or a `TypeMismatch`
+    /// error if the value under `key` is not an `R`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use resman::{KeyedResources, ResourceErrorKind};
+    ///
+    /// let mut resources = KeyedResources::new();
+    /// resources.insert(1u32, 2u64);
+    ///
+    /// *resources.try_entry::<u64>(1).unwrap().or_insert(0) += 1;
+    /// assert_eq!(3, *resources.borrow::<u64>(&1));
+    /// assert_eq!(
+    ///     Some(ResourceErrorKind::TypeMismatch),
+    ///     resources
+    ///         .try_entry::<u32>(1)
+    ///         .err()
+    ///         .map(|error| error.kind())
+    /// );
+    /// ```
+    pub fn try_entry<R>(
+        &mut self,
+        key: K,
+    ) -> Result<Entry<'_, R, KeyedEntryBackend<'_, K>>, ResourceError>
+    where
+        R: Resource,
+    {
+        match self.inner.get_mut(&key) {
+            Some(cell) => {
+                Self::type_check::<R>(&**cell.get_mut(), None)?;
+                Ok(Entry::Occupied(OccupiedEntry::new(KeyedEntryBackend::new(
+                    self, key,
+                ))))
+            }
+            None => Ok(Entry::Vacant(VacantEntry::new(KeyedEntryBackend::new(
+                self, key,
+            )))),
+        }
+    }
+
+    /// Inserts a resource under `key`. If a value existed under the key, it
+    /// is overwritten and returned.
+    ///
+    /// The previous value is returned boxed, as it may be of a different type.
+    pub fn insert<R>(&mut self, key: K, r: R) -> Option<Box<dyn Resource>>
+    where
+        R: Resource,
+    {
+        self.inner
+            .insert(key, Cell::new(Box::new(r)))
+            .map(Cell::into_inner)
+    }
+
+    /// Inserts a resource under `key`, which must not have a value, and
+    /// returns it mutably.
+    pub(crate) fn insert_vacant<R>(&mut self, key: K, r: R) -> RefMut<'_, R>
+    where
+        R: Resource,
+    {
+        let resource: Box<dyn Resource> = Box::new(r);
+        let cell: &Cell<Box<dyn Resource>> = self.inner.entry(key).or_insert(Cell::new(resource));
+        RefMut::new(cell.borrow_mut())
+    }
+
+    /// Removes the resource under `key`, and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no value under `key`, or it is not an `R`.
+    pub fn remove<R>(&mut self, key: &K) -> R
+    where
+        R: Resource,
+    {
+        self.try_remove::<R>(key)
+            .unwrap_or_else(Resources::borrow_panic)
+    }
+
+    /// Removes the resource under `key`, and returns it.
+    ///
+    /// Nothing is removed if the value under `key` is not an `R`.
+    pub fn try_remove<R>(&mut self, key: &K) -> Result<R, ResourceError>
+    where
+        R: Resource,
+    {
+        let resource = self
+            .inner
+            .get_mut(key)
+            .map(Cell::get_mut)
+            .ok_or_else(ResourceError::not_found::<R>)?;
+        Self::type_check::<R>(&**resource, None)?;
+
+        let resource: Box<R> = self
+            .inner
+            .remove(key)
+            .map(Cell::into_inner)
+            .and_then(|resource| resource.downcast().ok())
+            .expect("Expected resource to exist and be checked as `R`.");
+        Ok(*resource)
+    }
+
+    /// Returns true if the map contains a value under `key`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.inner.contains_key(key)
+    }
+
+    /// Returns the `R` resource under `key`.
+    ///
+    /// See [`try_borrow`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no value under `key`, or it is not an `R`.
+    /// Panics if the resource is being accessed mutably.
+    ///
+    /// [`try_borrow`]: Self::try_borrow
+    pub fn borrow<R>(&self, key: &K) -> Ref<'_, R>
+    where
+        R: Resource,
+    {
+        self.try_borrow::<R>(key)
+            .unwrap_or_else(Resources::borrow_panic)
+    }
+
+    /// Returns the `R` resource under `key`.
+    ///
+    /// Returns an error if there is no value under `key`, it is not an `R`,
+    /// or it cannot be borrowed.
+    pub fn try_borrow<R>(&self, key: &K) -> Result<Ref<'_, R>, ResourceError>
+    where
+        R: Resource,
+    {
+        let resource = self
+            .cell(key)
+            .and_then(Cell::try_borrow)
+            .map_err(|borrow_fail| {
+                self.borrow_error::<R>(key, BorrowKind::Immutable, borrow_fail)
+            })?;
+        Self::type_check::<R>(&**resource, Some(BorrowKind::Immutable))?;
+
+        Ok(Ref::new(resource))
+    }
+
+    /// Returns the `R` resource under `key` mutably.
+    ///
+    /// See [`try_borrow_mut`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no value under `key`, or it is not an `R`.
+    /// Panics if the resource is already accessed.
+    ///
+    /// [`try_borrow_mut`]: Self::try_borrow_mut
+    pub fn borrow_mut<R>(&self, key: &K) -> RefMut<'_, R>
+    where
+        R: Resource,
+    {
+        self.try_borrow_mut::<R>(key)
+            .unwrap_or_else(Resources::borrow_panic)
+    }
+
+    /// Returns the `R` resource under `key` mutably.
+    ///
+    /// Returns an error if there is no value under `key`, it is not an `R`,
+    /// or it cannot be borrowed.
+    pub fn try_borrow_mut<R>(&self, key: &K) -> Result<RefMut<'_, R>, ResourceError>
+    where
+        R: Resource,
+    {
+        let resource = self
+            .cell(key)
+            .and_then(Cell::try_borrow_mut)
+            .map_err(|borrow_fail| self.borrow_error::<R>(key, BorrowKind::Mutable, borrow_fail))?;
+        Self::type_check::<R>(&**resource, Some(BorrowKind::Mutable))?;
+
+        Ok(RefMut::new(resource))
+    }
+
+    /// Retrieves the resource under `key` without checking borrows, which is
+    /// cheaper, but only available with `&mut self`.
+    ///
+    /// Returns an error if there is no value under `key`, or it is not an `R`.
+    pub fn get_mut<R>(&mut self, key: &K) -> Result<&mut R, ResourceError>
+    where
+        R: Resource,
+    {
+        let resource = self.inner.get_mut(key).map(Cell::get_mut).ok_or_else(|| {
+            ResourceError::new(
+                core::any::type_name::<R>(),
+                Some(BorrowKind::Mutable),
+                ResourceErrorKind::NotFound,
+            )
+        })?;
+        Self::type_check::<R>(&**resource, Some(BorrowKind::Mutable))?;
+
+        Ok(resource
+            .downcast_mut::<R>()
+            .unwrap_or_else(|| panic!("Failed to downcast to {}", core::any::type_name::<R>())))
+    }
+
+    /// Returns the cell under `key`, or `ValueNotFound` if there is no value
+    /// under `key`.
+    fn cell(&self, key: &K) -> Result<&Cell<Box<dyn Resource>>, BorrowFail> {
+        self.inner.get(key).ok_or(BorrowFail::ValueNotFound)
+    }
+
+    /// Returns the `ResourceError` for a failed borrow of the resource under
+    /// `key`.
+    fn borrow_error<R>(
+        &self,
+        key: &K,
+        borrow_kind: BorrowKind,
+        borrow_fail: BorrowFail,
+    ) -> ResourceError
+    where
+        R: Resource,
+    {
+        ResourceError::borrow_failed(
+            core::any::type_name::<R>(),
+            borrow_kind,
+            borrow_fail,
+            || self.cell(key).and_then(Cell::try_borrow).is_err(),
+        )
+    }
+
+    /// Returns a `TypeMismatch` error if `resource` is not an `R`.
+    ///
+    /// The error's suggestion is the type of `resource`.
+    fn type_check<R>(
+        resource: &dyn Resource,
+        borrow_kind: Option<BorrowKind>,
+    ) -> Result<(), ResourceError>
+    where
+        R: Resource,
+    {
+        if Resource::type_id(resource) == TypeId::of::<R>() {
+            Ok(())
+        } else {
+            let error = ResourceError::new(
+                core::any::type_name::<R>(),
+                borrow_kind,
+                ResourceErrorKind::TypeMismatch,
+            );
+            Err(error.with_suggestions(vec![Resource::type_name(resource).as_str()]))
+        }
+    }
+}
+
+impl<K> fmt::Debug for KeyedResources<K>
+where
+    K: Hash + Eq + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut debug_map = f.debug_map();
+
+        self.inner.iter().for_each(|(key, cell)| {
+            let resource = &*cell.borrow();
+            debug_map.entry(key, &Resource::type_name(&**resource));
+        });
+
+        debug_map.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Entry, KeyedResources, ResourceErrorKind};
+
+    #[test]
+    fn insert_returns_previous_value_and_borrow_returns_value() {
+        let mut resources = KeyedResources::new();
+
+        assert!(resources.insert(1u8, A(1)).is_none());
+        let previous = resources.insert(1u8, B(2));
+        assert_eq!(
+            Some(&A(1)),
+            previous.as_ref().and_then(|r| r.downcast_ref::<A>())
+        );
+
+        resources.borrow_mut::<B>(&1).0 = 3;
+        assert_eq!(B(3), *resources.borrow::<B>(&1));
+        assert_eq!(1, resources.len());
+        assert_eq!(vec![&1], resources.keys().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn try_borrow_returns_resource_error() {
+        let mut resources = KeyedResources::new();
+        resources.insert("a", A(1));
+
+        assert_eq!(
+            Some(ResourceErrorKind::NotFound),
+            resources.try_borrow::<A>(&"b").err().map(|e| e.kind())
+        );
+
+        let error = resources.try_borrow_mut::<B>(&"a").unwrap_err();
+        assert_eq!(ResourceErrorKind::TypeMismatch, error.kind());
+        assert_eq!([core::any::type_name::<A>()], error.suggestions());
+
+        let a = resources.borrow::<A>(&"a");
+        assert_eq!(
+            Some(ResourceErrorKind::ConflictShared),
+            resources.try_borrow_mut::<A>(&"a").err().map(|e| e.kind())
+        );
+        drop(a);
+
+        let _a = resources.borrow_mut::<A>(&"a");
+        assert_eq!(
+            Some(ResourceErrorKind::ConflictMutable),
+            resources.try_borrow::<A>(&"a").err().map(|e| e.kind())
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `resman::keyed_resources::tests::B` immutably, but the value under the key is a different type. Did you mean: `resman::keyed_resources::tests::A`?"
+    )]
+    fn borrow_panics_when_type_mismatch() {
+        let mut resources = KeyedResources::new();
+        resources.insert(1u32, A(1));
+
+        resources.borrow::<B>(&1);
+    }
+
+    #[test]
+    fn try_remove_does_not_remove_on_type_mismatch() {
+        let mut resources = KeyedResources::new();
+        resources.insert(1u32, A(1));
+
+        assert_eq!(
+            Some(ResourceErrorKind::TypeMismatch),
+            resources.try_remove::<B>(&1).err().map(|e| e.kind())
+        );
+        assert!(resources.contains_key(&1));
+
+        assert_eq!(Ok(A(1)), resources.try_remove::<A>(&1));
+        assert!(resources.is_empty());
+        assert_eq!(
+            Some(ResourceErrorKind::NotFound),
+            resources.try_remove::<A>(&1).err().map(|e| e.kind())
+        );
+    }
+
+    #[test]
+    fn entry_get_mut_and_remove() {
+        let mut resources = KeyedResources::new();
+
+        resources.entry::<A>(1u32).or_insert(A(1)).0 += 1;
+        resources
+            .entry::<A>(1)
+            .and_modify(|a| a.0 += 1)
+            .or_default();
+        resources.get_mut::<A>(&1).unwrap().0 += 1;
+        assert_eq!(A(4), *resources.borrow::<A>(&1));
+
+        match resources.entry::<A>(1) {
+            Entry::Occupied(occupied) => {
+                assert_eq!(&1, occupied.key());
+                assert_eq!(A(4), occupied.remove());
+            }
+            Entry::Vacant(_) => unreachable!(),
+        }
+        match resources.entry::<A>(1) {
+            Entry::Occupied(_) => unreachable!(),
+            Entry::Vacant(vacant) => assert_eq!(1, vacant.into_key()),
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected the entry to be `resman::keyed_resources::tests::B`, but the value under the key is `resman::keyed_resources::tests::A`."
+    )]
+    fn entry_panics_when_type_mismatch() {
+        let mut resources = KeyedResources::new();
+        resources.insert(1u32, A(1));
+
+        let _entry = resources.entry::<B>(1);
+    }
+
+    #[test]
+    fn try_entry_returns_type_mismatch() {
+        let mut resources = KeyedResources::new();
+        resources.insert(1u32, A(1));
+
+        let error = resources.try_entry::<B>(1).err().unwrap();
+        assert_eq!(ResourceErrorKind::TypeMismatch, error.kind());
+        assert_eq!([core::any::type_name::<A>()], error.suggestions());
+
+        let entry = resources.try_entry::<A>(1).unwrap();
+        assert_eq!(&1, entry.key());
+        entry.or_default().0 += 1;
+        assert_eq!(A(2), *resources.borrow::<A>(&1));
+        assert!(matches!(resources.try_entry::<B>(2), Ok(Entry::Vacant(_))));
+    }
+
+    #[derive(Debug, Default, PartialEq)]
+    struct A(u32);
+
+    #[derive(Debug, PartialEq)]
+    struct B(u32);
+}
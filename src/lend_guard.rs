@@ -0,0 +1,93 @@
+use core::ptr;
+
+use crate::{Resource, Resources};
+
+/// Moves a lent resource back to the caller when dropped.
+///
+/// While the guard exists, the value behind `lent` has been moved into
+/// `resources`, and `lent` must not be read. Dropping the guard moves the value
+/// back into `lent`, whether the closure that borrowed it returned or
+/// panicked.
+///
+/// If `Resources` held an `R` before it was lent, that value is reinserted.
+/// Watchers and subscribers of `R` are not notified, as the map ends up as it
+/// was before.
+pub(crate) struct LendGuard<'a, 'l, R>
+where
+    R: Resource,
+{
+    /// The resources that `R` is lent to.
+    resources: &'a mut Resources,
+    /// Where the lent value is moved back to.
+    lent: &'l mut R,
+    /// The `R` that was in `resources` before the value was lent.
+    previous: Option<R>,
+}
+
+impl<'a, 'l, R> LendGuard<'a, 'l, R>
+where
+    R: Resource,
+{
+    /// Moves the value out of `lent` into `resources` until the guard is
+    /// dropped.
+    pub(crate) fn new(resources: &'a mut Resources, lent: &'l mut R) -> Self {
+        // SAFETY: The value is moved back into `lent` when the guard is
+        // dropped, and `lent` is mutably borrowed by the guard until then, so
+        // the moved-from value cannot be observed or dropped twice.
+        let value = unsafe { ptr::read(lent) };
+        let previous = resources.insert_unnotified(value);
+
+        Self {
+            resources,
+            lent,
+            previous,
+        }
+    }
+
+    /// Returns the resources that the value is lent to.
+    pub(crate) fn resources(&self) -> &Resources {
+        self.resources
+    }
+}
+
+impl<R> Drop for LendGuard<'_, '_, R>
+where
+    R: Resource,
+{
+    fn drop(&mut self) {
+        // Reinserting the previous value keeps `ResHandle`s to `R` valid.
+        let value = match self.previous.take() {
+            Some(previous) => self.resources.insert_unnotified(previous),
+            None => self.resources.try_remove::<R>().ok(),
+        };
+
+        // The closure only receives `&Resources`, which cannot remove `R`, so
+        // the value is always there.
+        let value = value.unwrap_or_else(|| lent_value_missing::<R>());
+
+        // SAFETY: `lent` was moved out of in `new`, so writing to it does not
+        // leak or drop a value.
+        unsafe { ptr::write(self.lent, value) };
+    }
+}
+
+/// Aborts, as `lent` cannot be left without a value.
+///
+/// Panicking while `AbortOnDrop` is dropped during unwinding aborts the
+/// process, which does not need `std`.
+#[cold]
+fn lent_value_missing<R>() -> ! {
+    struct AbortOnDrop;
+
+    impl Drop for AbortOnDrop {
+        fn drop(&mut self) {
+            panic!("Aborting, as the lent value cannot be returned.");
+        }
+    }
+
+    let _abort_on_drop = AbortOnDrop;
+    panic!(
+        "Expected lent `{}` to remain in `Resources` while lent.",
+        core::any::type_name::<R>()
+    );
+}
@@ -1,3 +1,4 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![cfg_attr(feature = "fn_res_once", feature(box_into_inner))]
 
 //! Runtime managed resource borrowing.
@@ -24,11 +25,17 @@
 //! resman = "0.19.0"
 //!
 //! # or
-//! resman = { version = "0.19.0", features = ["debug"] }
 //! resman = { version = "0.19.0", features = ["fn_res"] }
 //! resman = { version = "0.19.0", features = ["fn_res", "fn_res_mut"] }
 //! resman = { version = "0.19.0", features = ["fn_res", "fn_meta"] }
 //! resman = { version = "0.19.0", features = ["fn_res", "fn_res_mut", "fn_meta"] }
+//! resman = { version = "0.19.0", features = ["stats"] }
+//! resman = { version = "0.19.0", features = ["tracing"] }
+//! resman = { version = "0.19.0", features = ["testing"] }
+//!
+//! # no_std, requires alloc
+//! resman = { version = "0.19.0", default-features = false }
+//! resman = { version = "0.19.0", default-features = false, features = ["fn_res"] }
 //!
 //! # requires nightly
 //! resman = { version = "0.19.0", features = ["fn_res", "fn_res_mut", "fn_res_once"] }
@@ -80,30 +87,120 @@
 //! println!("a_try_borrow_mut: {}", exists); // prints "Err"
 //! ```
 //!
-//! ### Features
-//!
-//! #### `"debug"`:
-//!
-//! The `Debug` implementation for `Resources` will use the `Debug`
-//! implementation for the values when printed. This requires that all
-//! `Resources` to also implement `Debug`.
-//!
-//! Example:
+//! When a resource does not exist, the panic message and `ResourceError` list
+//! stored resources with a similar type name, such as `other_crate::Config` or
+//! `Arc<Config>` when `Config` was requested. This helps when the resource was
+//! inserted from a different crate version, or wrapped in another type.
+//!
+//! When resource types are only known by name at runtime, such as from a
+//! configuration file, register them in a [`TypeRegistry`] and use
+//! `Resources::insert_by_name`, `borrow_by_name`, `borrow_mut_by_name`, and
+//! `contains_by_name`. Borrowing by name returns `Ref<dyn Resource>`, and
+//! errors suggest similarly named registered types.
+//!
+//! `Resources::outstanding_borrows` lists resources that are still borrowed,
+//! with their borrow kind and count. `Resources::assert_no_outstanding_borrows`
+//! panics with that list, and `Resources::set_check_borrows_on_drop(true)` runs
+//! the same check when `Resources` is dropped. This helps find `Ref`s and
+//! `RefMut`s that are kept alive by mistake or `mem::forget`-ed.
+//!
+//! When `Resources` is dropped, resources are dropped in reverse insertion
+//! order, so a service inserted after the connection it uses is dropped first.
+//! Use `Resources::set_drop_order(&[TypeId::of::<A>(), ..])` to drop particular
+//! resources first, in the given order.
+//!
+//! For repeated access, `Resources::handle::<R>()` returns a [`ResHandle<R>`],
+//! which is borrowed with `handle.borrow(&resources)` and
+//! `handle.borrow_mut(&resources)` without looking up the resource's `TypeId`.
+//! Using a handle with a different `Resources` map, or after the resource was
+//! removed, returns an error instead of borrowing another value.
+//!
+//! `Resources::transaction(|tx| ..)` applies changes to multiple resources all
+//! or nothing. `tx.borrow_mut::<R>()` modifies a clone of `R`, and the clones
+//! replace the original resources only if the closure returns `Ok`.
+//!
+//! `Resources::with_lent(&mut r, |resources| ..)` lends a value that the caller
+//! only has through `&mut R`, so that resource functions can borrow it. The
+//! value is moved back into `r` when the closure returns or panics, and `R`
+//! does not need to implement `Default`.
+//!
+//! To limit what plugin code can access, `Resources::view(read, write)` returns
+//! a [`ResourcesView`] that only allows the listed types to be borrowed. Other
+//! borrows return a `ResourceError` of kind `ResourceErrorKind::AccessDenied`.
+//! `Resources::view_builder()` lists the types as type parameters instead. A
+//! view can only be narrowed, so plugin code cannot widen its own access.
+//!
+//! `Resources::insert_bundle((a, b, c))` inserts a tuple of up to 16 resources.
+//! `Resources::remove_bundle::<(A, B, C)>()` removes them all, or none if any
+//! is missing, and `Resources::contains_all::<(A, B)>()` checks that they all
+//! exist. A bundle that repeats a type, such as `(A, A)`, is rejected with a
+//! `ResourceErrorKind::DuplicateType` error.
+//!
+//! For resources that are `!Send` or `!Sync`, such as `Rc<T>`, use
+//! [`LocalResources`]. It has the same API as `Resources`, but uses `RefCell`s
+//! with non-atomic borrow counters, so the map stays on one thread.
+//!
+//! To key resources by a value instead of by type, such as an asset ID or
+//! entity ID, use [`KeyedResources<K>`]. `keyed.borrow::<R>(&key)` returns the
+//! same `Ref<R>` as `Resources`, and returns a
+//! `ResourceErrorKind::TypeMismatch` error from `try_borrow` if the value under
+//! the key is not an `R`.
+//!
+//! `Resources::subscribe::<R>(callback)` runs the callback with the new value
+//! whenever `R` is inserted, or a `RefMut<R>` that was written to is dropped.
+//! It returns a `Subscription`, which is passed to `Resources::unsubscribe` to
+//! stop the callback.
+//!
+//! `Resources::watch::<R>()` returns a [`Watcher`], whose `changed().await`
+//! completes when `R` is inserted or written through a `RefMut`. It does not
+//! depend on any async runtime, and changes that happen while the watcher is
+//! not waiting are combined into one notification.
+//!
+//! ### Debug
+//!
+//! Resources do not need to implement `Debug`. Values inserted with
+//! `Resources::insert_debug` are printed by the `Debug` implementation for
+//! `Resources`, and other values are printed as `".."`.
 //!
 //! ```rust
 //! # use resman::Resources;
 //! #
 //! let mut resources = Resources::default();
-//! resources.insert(1u32);
+//! resources.insert_debug(1u32);
+//! resources.insert(2u64);
 //! println!("{:?}", resources);
 //!
-//! // Without `"debug"` feature:
-//! // {u32: ".."}
-//!
-//! // With `"debug"` feature:
-//! // {u32: 1}
+//! // {u32: 1, u64: ".."}
 //! ```
 //!
+//! ### Features
+//!
+//! #### `"std"`:
+//!
+//! Enabled by default. Without this feature, `resman` is `#![no_std]` and only
+//! requires `alloc`. `Resources`, `Ref`, `RefMut`, `Entry`, and the `"fn_res"`
+//! traits are available without `std`.
+//!
+//! The following require `std`:
+//!
+//! * `std::error::Error` implementations for the error types.
+//! * The `"stats"`, `"testing"`, and `"tracing"` features, which enable
+//!   `"std"`.
+//! * `Resources::watch` and `Watcher`.
+//! * `KeyedResources`, which stores its values in a `HashMap`.
+//! * `Resources::into_inner`, which returns an [`rt_map::RtMap`].
+//! * Skipping the outstanding borrows check on drop while the thread is
+//!   panicking; without `std`, `Resources` always checks when
+//!   `set_check_borrows_on_drop(true)` is set.
+//!
+//! Borrows are checked with [`Cell`], which only uses `core` atomics, so
+//! targets without `std` such as `thumbv7em-none-eabi` can be built.
+//!
+//! #### `"debug"`:
+//!
+//! No longer has any effect, and is kept for compatibility. Use
+//! `Resources::insert_debug` instead.
+//!
 //! #### `"fn_res"`:
 //!
 //! Enables the [`FnRes`] trait, allowing dynamic functions invocation under a
@@ -116,7 +213,7 @@
 //!     - The functions should take `&T` or `&mut T` as parameters.
 //!     - The return type of all functions should be the same.
 //!
-//!     Currently there is a limit of 7 parameters.
+//!     Currently there is a limit of 16 parameters.
 //!
 //! 2. Call `my_function.into_fn_res()` to obtain a `Box<dyn FnRes>`.
 //! 3. Call `fn_res.call(&resources)` to automatically borrow `T` from
@@ -125,6 +222,8 @@
 //! Example:
 //!
 //! ```rust
+//! # #[cfg(feature = "fn_res")]
+//! # {
 //! use resman::{FnRes, IntoFnRes, Resources};
 //!
 //! /// Borrows `u32` mutably, and `u64` immutably.
@@ -146,8 +245,8 @@
 //! ];
 //!
 //! let mut resources = Resources::default();
-//! resources.insert(0u32);
-//! resources.insert(0u64);
+//! resources.insert_debug(0u32);
+//! resources.insert_debug(0u64);
 //!
 //! let sum = functions
 //!     .iter()
@@ -158,6 +257,7 @@
 //! let debug_str = format!("{:?}", resources);
 //! assert!(debug_str.contains("u32: 1"));
 //! assert!(debug_str.contains("u64: 1"));
+//! # }
 //! ```
 //!
 //! Since `Resources` has internal mutability, care must be taken to not run
@@ -165,7 +265,16 @@
 //! the same time when using [`FnRes::call`], otherwise it will panic.
 //!
 //! Use [`FnRes::try_call`] for a non-panicking version, which will return a
-//! [`BorrowFail`] error if there is an overlapping borrow conflict at runtime.
+//! [`ResourceError`] error if there is an overlapping borrow conflict at
+//! runtime.
+//!
+//! [`IntoFnResLocal::into_fn_res_local`] returns a `Box<dyn FnResLocal>`,
+//! whose `call_local` runs the function with a [`LocalResources`] map, so its
+//! parameters may also be `!Send`.
+//!
+//! [`FnResRegistry`] stores functions under names, so that they can be called
+//! by name at runtime. `FnResRegistry::fn_res_infos` lists the type names of
+//! the resources each function borrows, taken from its parameter types.
 //!
 //! #### `"fn_res_mut"`:
 //!
@@ -174,6 +283,9 @@
 //! `FnResMut` is implemented for functions and closures that `impl FnMut`, but
 //! not `Fn`.
 //!
+//! `FnResMut` functions may also take [`Local<T>`] parameters, which hold state
+//! that is stored in the function instead of in `Resources`.
+//!
 //! #### `"fn_res_once"`:
 //!
 //! ***Requires nightly***
@@ -188,15 +300,65 @@
 //! Adds [`FnMeta`] as an implied trait to [`FnRes`]. This means function
 //! metadata can be queried for any `FnRes`.
 //!
+//! `FnRes::call_strict` and `FnRes::try_call_strict` run the function while
+//! restricting borrows from that `Resources` map to the function's parameters.
+//! Borrowing any other resource on the same thread, even through a captured
+//! `&Resources`, returns an `AccessDenied` error with the function's name.
+//! `call_strict` panics with it, and `try_call_strict` returns it. These
+//! require the `"std"` feature.
+//!
+//! #### `"stats"`:
+//!
+//! Counts borrows for each resource, which is useful to see which resources are
+//! contended when splitting up systems. `Resources::stats()` returns, per
+//! resource:
+//!
+//! * Number of successful immutable and mutable borrows.
+//! * Number of failed borrows, for each `BorrowFail` variant.
+//! * Longest time a mutable borrow was held.
+//!
+//! `Resources::reset_stats()` resets the counters, e.g. at the start of each
+//! measurement period.
+//!
+//! #### `"tracing"`:
+//!
+//! Emits [`tracing`] events at the `TRACE` level when resources are borrowed
+//! through `Resources::borrow*` and `Resources::try_borrow*`, with the
+//! resource's type name, borrow kind, and `BorrowFail` if the borrow failed.
+//!
+//! With `"fn_res"`, every `call`, `call_mut`, and `call_once` on a resource
+//! function (and their `try_*` variants) enters a `TRACE` span named after the
+//! method, e.g. `FnRes::call`. The span records the function's type in the
+//! `fn_type` field, and the borrowed resource types in the `borrows` and
+//! `borrow_muts` fields.
+//!
+//! #### `"testing"`:
+//!
+//! Adds helpers for tests that use `Resources`, usually enabled in
+//! `[dev-dependencies]`:
+//!
+//! * `Resources::builder().with(A(1)).with_default::<B>().build()` builds a
+//!   `Resources` map.
+//! * `assert_resource_eq!(resources, A, A(1))` asserts a resource's value.
+//! * `assert_borrowable!(resources, A)` asserts that a resource can be
+//!   borrowed, and `assert_borrowable!(resources, mut A)` that it can be
+//!   borrowed mutably.
+//! * `Resources::track_changes(|resources| ..)` returns the type names of the
+//!   resources that were written to through a `RefMut`. `&mut` parameters of
+//!   resource functions are always counted as written.
+//! * With `"fn_res"`, `assert_fn_res_changes!(resources, fn_res, [A, B])` runs
+//!   a resource function, and asserts that the values of the listed resources
+//!   changed, by comparing clones from before the call. Resources listed in
+//!   `unchanged: [C]` are asserted to keep their values.
+//!
 //! #### `"high_arg_count"`:
 //!
-//! Raises the number of arguments that [`FnRes`], [`IntoFnRes`], and
-//! [`IntoFnResource`] are implemented for from 6 to 8.
+//! No longer has any effect, and is kept for compatibility.
 //!
-//! This is feature gated because compilation time increasing significantly with
-//! higher numbers of arguments -- as much as from 4 seconds for 6 arguments
-//! to 26 seconds for 8 arguments when only `"fn_res"` is enabled, and up to a
-//! minute when `"fn_mut"` and `"fn_once"` are enabled.
+//! [`FnRes`], [`IntoFnRes`], and [`IntoFnResource`] are implemented for up to
+//! 16 arguments. Each parameter type implements [`FetchParam`], so the number
+//! of implementations grows linearly with the number of arguments instead of
+//! with every combination of `&T` and `&mut T`.
 //!
 //!
 //! ## See Also
@@ -210,51 +372,155 @@
 //! [`mopa`]: https://github.com/chris-morgan/mopa
 //! [`rt_map`]: https://github.com/azriel91/rt_map
 //! [`shred`]: https://github.com/amethyst/shred
+//! [`tracing`]: https://docs.rs/tracing
 //!
 //! [`FnMeta`]: fn_meta::FnMeta
+//! [`TypeRegistry`]: crate::TypeRegistry
+//! [`Local<T>`]: crate::Local
+//! [`ResHandle<R>`]: crate::ResHandle
+//! [`ResourcesView`]: crate::ResourcesView
+//! [`LocalResources`]: crate::LocalResources
+//! [`KeyedResources<K>`]: crate::KeyedResources
+//! [`IntoFnResLocal::into_fn_res_local`]: crate::IntoFnResLocal::into_fn_res_local
+//! [`Watcher`]: crate::Watcher
+//! [`Cell`]: crate::Cell
+//! [`rt_map::RtMap`]: https://docs.rs/rt_map/latest/rt_map/struct.RtMap.html
+
+extern crate alloc;
 
 pub use crate::{
-    entry::Entry, r#ref::Ref, ref_mut::RefMut, resource::Resource,
-    resource_fetch_error::ResourceFetchError, resources::Resources,
+    borrow_fail::BorrowFail, borrow_kind::BorrowKind, cell::Cell, cell_ref::CellRef,
+    cell_ref_mut::CellRefMut, entry::Entry, entry_backend::EntryBackend,
+    local_resource::LocalResource, local_resources::LocalResources, occupied_entry::OccupiedEntry,
+    outstanding_borrow::OutstandingBorrow, r#ref::Ref, ref_mut::RefMut, res_handle::ResHandle,
+    resource::Resource, resource_bundle::ResourceBundle,
+    resource_by_name_error::ResourceByNameError, resource_error::ResourceError,
+    resource_error_kind::ResourceErrorKind, resources::Resources, resources_view::ResourcesView,
+    resources_view_builder::ResourcesViewBuilder, subscription::Subscription,
+    transaction::Transaction, type_registry::TypeRegistry, vacant_entry::VacantEntry,
 };
 
-pub use rt_map::BorrowFail;
+#[cfg(feature = "stats")]
+pub use crate::resource_stats::ResourceStats;
 
 #[cfg(feature = "fn_meta")]
 pub use fn_meta;
 
+mod borrow_fail;
+mod borrow_kind;
+mod cell;
+mod cell_ref;
+mod cell_ref_mut;
 mod entry;
+mod entry_backend;
+mod lend_guard;
+mod local_resource;
+mod local_resources;
+mod occupied_entry;
+mod outstanding_borrow;
 mod r#ref;
 mod ref_mut;
+mod res_handle;
 mod resource;
-mod resource_fetch_error;
+mod resource_bundle;
+mod resource_by_name_error;
+mod resource_debug;
+mod resource_error;
+mod resource_error_kind;
+mod resource_slot;
+mod resource_tracker;
 mod resources;
+mod resources_id;
+mod resources_view;
+mod resources_view_builder;
+mod similar_names;
+mod subscribers;
+mod subscription;
+mod transaction;
+mod type_registry;
+mod vacant_entry;
+
+#[cfg(feature = "stats")]
+mod resource_stats;
+
+#[cfg(feature = "std")]
+pub use crate::{
+    keyed_entry_backend::KeyedEntryBackend, keyed_resources::KeyedResources, watcher::Watcher,
+    watcher_closed::WatcherClosed,
+};
+
+#[cfg(feature = "testing")]
+pub use crate::resources_builder::ResourcesBuilder;
+
+#[cfg(feature = "testing")]
+mod resources_builder;
+#[cfg(feature = "testing")]
+mod testing_macros;
+
+#[cfg(feature = "std")]
+mod keyed_entry_backend;
+#[cfg(feature = "std")]
+mod keyed_resources;
+#[cfg(feature = "std")]
+mod watch_state;
+#[cfg(feature = "std")]
+mod watcher;
+#[cfg(feature = "std")]
+mod watcher_closed;
+
+#[cfg(all(test, feature = "tracing"))]
+mod test_subscriber;
 
 #[cfg(feature = "fn_res")]
 pub use crate::{
+    fetch_param::FetchParam,
+    fetch_param_local::FetchParamLocal,
     fn_res::FnRes,
+    fn_res_call_error::FnResCallError,
+    fn_res_info::FnResInfo,
+    fn_res_local::FnResLocal,
+    fn_res_registry::FnResRegistry,
     fn_resource::{FnResource, IntoFnResource},
     into_fn_res::IntoFnRes,
+    into_fn_res_local::IntoFnResLocal,
 };
 
+#[cfg(feature = "fn_res")]
+mod fetch_param;
+#[cfg(feature = "fn_res")]
+mod fetch_param_local;
 #[cfg(feature = "fn_res")]
 mod fn_res;
 #[cfg(feature = "fn_res")]
-mod fn_resource;
+mod fn_res_call_error;
+#[cfg(feature = "fn_res")]
+mod fn_res_info;
+#[cfg(feature = "fn_res")]
+mod fn_res_local;
 #[cfg(feature = "fn_res")]
-mod fn_resource_impl;
+mod fn_res_registry;
+#[cfg(feature = "fn_res")]
+mod fn_resource;
 #[cfg(feature = "fn_res")]
 mod into_fn_res;
+#[cfg(feature = "fn_res")]
+mod into_fn_res_local;
 
+#[cfg(all(feature = "fn_res", feature = "fn_meta", feature = "std"))]
+mod borrow_restriction;
+#[cfg(all(feature = "fn_res", feature = "fn_meta", feature = "std"))]
+mod borrow_restriction_guard;
 #[cfg(all(feature = "fn_res", feature = "fn_meta"))]
 mod fn_resource_meta_impl;
 
 #[cfg(feature = "fn_res_mut")]
-pub use crate::{fn_res_mut::FnResMut, into_fn_res_mut::IntoFnResMut};
+pub use crate::{fn_res_mut::FnResMut, into_fn_res_mut::IntoFnResMut, local::Local};
 #[cfg(feature = "fn_res_mut")]
 mod fn_res_mut;
 #[cfg(feature = "fn_res_mut")]
 mod into_fn_res_mut;
+#[cfg(feature = "fn_res_mut")]
+mod local;
 
 #[cfg(feature = "fn_res_once")]
 pub use crate::{fn_res_once::FnResOnce, into_fn_res_once::IntoFnResOnce};
@@ -265,6 +531,8 @@ mod into_fn_res_once;
 
 #[cfg(feature = "fn_res")]
 mod fn_res_impl;
+#[cfg(feature = "fn_res")]
+mod fn_res_local_impl;
 #[cfg(feature = "fn_res_mut")]
 mod fn_res_mut_impl;
 #[cfg(feature = "fn_res_once")]
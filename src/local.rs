@@ -0,0 +1,173 @@
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{FetchParam, ResourceError, Resources};
+
+/// State that belongs to a single resource function.
+///
+/// The value is stored in the `FnResource` instead of `Resources`, and is
+/// initialized with `T::default()` when the function is wrapped. Each call
+/// receives the value left by the previous call.
+///
+/// Since the value is mutated on every call, `Local` parameters can only be
+/// used with [`FnResMut`] and `FnResOnce` functions. They are not included
+/// in `fn_meta` borrows.
+///
+/// # Examples
+///
+/// ```rust
+/// use resman::{FnResMut, IntoFnResMut, Local, Resources};
+///
+/// fn count(mut calls: Local<u32>, total: &mut u32) -> u32 {
+///     *calls += 1;
+///     *total += 10;
+///     *calls
+/// }
+///
+/// let mut resources = Resources::new();
+/// resources.insert(0u32);
+///
+/// let mut fn_res = count.into_fn_res_mut();
+/// assert_eq!(1, fn_res.call_mut(&resources));
+/// assert_eq!(2, fn_res.call_mut(&resources));
+/// assert_eq!(20, *resources.borrow::<u32>());
+/// ```
+///
+/// [`FnResMut`]: crate::FnResMut
+pub struct Local<'a, T>(&'a mut T);
+
+impl<T> Deref for Local<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.0
+    }
+}
+
+impl<T> DerefMut for Local<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.0
+    }
+}
+
+impl<T> fmt::Debug for Local<'_, T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Local").field(&self.0).finish()
+    }
+}
+
+impl<T> FetchParam for Local<'_, T>
+where
+    T: Default + Send + Sync + 'static,
+{
+    type Fetched<'r> = &'r mut T;
+    type Item<'f> = Local<'f, T>;
+    type State = T;
+
+    fn fetch<'r>(_resources: &'r Resources, state: &'r mut T) -> Self::Fetched<'r> {
+        state
+    }
+
+    fn try_fetch<'r>(
+        _resources: &'r Resources,
+        state: &'r mut T,
+    ) -> Result<Self::Fetched<'r>, ResourceError> {
+        Ok(state)
+    }
+
+    fn item<'f>(fetched: &'f mut Self::Fetched<'_>) -> Self::Item<'f> {
+        Local(fetched)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FnResMut, IntoFnResource, Resources};
+
+    use super::Local;
+
+    #[test]
+    fn call_mut_persists_local_state_across_calls() {
+        let mut resources = Resources::new();
+        resources.insert(S0(1));
+        let mut fn_res = (|mut count: Local<usize>, s0: &mut S0| {
+            *count += 1;
+            s0.0 += *count;
+            *count
+        })
+        .into_fn_resource();
+
+        assert_eq!(1, fn_res.call_mut(&resources));
+        assert_eq!(2, fn_res.call_mut(&resources));
+        assert_eq!(3, fn_res.call_mut(&resources));
+        assert_eq!(7, resources.borrow::<S0>().0);
+    }
+
+    #[test]
+    fn try_call_mut_persists_local_state_across_calls() {
+        let mut resources = Resources::new();
+        resources.insert(S0(1));
+        let mut fn_res = f_local_w1_local.into_fn_resource();
+
+        assert_eq!(Ok(1), fn_res.try_call_mut(&resources));
+        assert_eq!(Ok(5), fn_res.try_call_mut(&resources));
+        assert_eq!(2, resources.borrow::<S0>().0);
+        assert_eq!(Ok(14), FnResMut::try_call_mut(&mut fn_res, &resources));
+    }
+
+    #[test]
+    fn local_state_is_separate_per_function() {
+        let resources = Resources::new();
+        let mut fn_res_0 = f_local.into_fn_resource();
+        let mut fn_res_1 = f_local.into_fn_resource();
+
+        assert_eq!(1, fn_res_0.call_mut(&resources));
+        assert_eq!(2, fn_res_0.call_mut(&resources));
+        assert_eq!(1, fn_res_1.call_mut(&resources));
+    }
+
+    #[cfg(feature = "fn_meta")]
+    #[test]
+    fn fn_meta_excludes_local() {
+        use core::any::TypeId;
+
+        use fn_meta::FnMetaDyn;
+
+        let fn_res = f_local_w1_local.into_fn_resource();
+
+        assert!(fn_res.borrows().is_empty());
+        assert_eq!(&[TypeId::of::<S0>()], fn_res.borrow_muts().as_slice());
+    }
+
+    #[test]
+    fn debug_includes_value() {
+        let mut value = 1u32;
+        let local = Local(&mut value);
+
+        assert_eq!("Local(1)", format!("{local:?}"));
+    }
+
+    fn f_local(mut count: Local<usize>) -> usize {
+        *count += 1;
+        *count
+    }
+
+    fn f_local_w1_local(
+        mut count: Local<usize>,
+        s0: &mut S0,
+        mut squares: Local<Vec<usize>>,
+    ) -> usize {
+        *count += 1;
+        s0.0 = *count;
+        squares.push(*count * *count);
+        squares.iter().sum()
+    }
+
+    #[derive(Debug)]
+    struct S0(usize);
+}
@@ -0,0 +1,32 @@
+use core::any::{Any, TypeId};
+
+use downcast_rs::Downcast;
+
+use crate::resource::TypeNameLit;
+
+/// Trait to represent any type that is `'static`, including `!Send` and
+/// `!Sync` types.
+///
+/// A local resource is a data slot in [`LocalResources`], which can only be
+/// accessed on the thread that owns the map.
+///
+/// [`LocalResources`]: crate::LocalResources
+pub trait LocalResource: Downcast + 'static {
+    fn type_id(&self) -> TypeId;
+    fn type_name(&self) -> TypeNameLit;
+}
+
+impl<T> LocalResource for T
+where
+    T: Any,
+{
+    fn type_id(&self) -> TypeId {
+        TypeId::of::<T>()
+    }
+
+    fn type_name(&self) -> TypeNameLit {
+        TypeNameLit(core::any::type_name::<T>())
+    }
+}
+
+downcast_rs::impl_downcast!(LocalResource);
@@ -0,0 +1,394 @@
+use alloc::{boxed::Box, collections::BTreeMap};
+use core::{
+    any::TypeId,
+    cell::{self, RefCell},
+    fmt,
+};
+
+use crate::{
+    resource_debug::{self, DebugFn, ResourceDebug},
+    BorrowKind, Entry, LocalResource, OccupiedEntry, ResourceError, ResourceErrorKind, Resources,
+    VacantEntry,
+};
+
+/// Map from `TypeId` to type, for resources that stay on one thread.
+///
+/// This has the same API as [`Resources`], but accepts `!Send` and `!Sync`
+/// values such as `Rc<T>`. Borrows are checked with [`RefCell`]s, which use
+/// non-atomic counters, so `LocalResources` is itself `!Send` and `!Sync`.
+///
+/// With the `"fn_res"` feature, resource functions can be run against it with
+/// `FnResLocal::call_local`.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::rc::Rc;
+///
+/// use resman::LocalResources;
+///
+/// let mut resources = LocalResources::new();
+/// resources.insert(Rc::new(1u32));
+/// resources.insert(2u64);
+///
+/// *resources.borrow_mut::<u64>() += **resources.borrow::<Rc<u32>>() as u64;
+/// assert_eq!(3, *resources.borrow::<u64>());
+/// ```
+#[derive(Default)]
+pub struct LocalResources {
+    /// The resources, keyed by `TypeId`.
+    inner: BTreeMap<TypeId, RefCell<Box<dyn LocalResource>>>,
+    /// Functions to format resources whose types are known to be `Debug`.
+    debug_fns: BTreeMap<TypeId, DebugFn<dyn LocalResource>>,
+}
+
+impl LocalResources {
+    /// Creates an empty `LocalResources` map.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use resman::LocalResources;
+    /// let mut resources = LocalResources::new();
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of resources in the map.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns true if the map contains no resources.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Returns an entry for the resource with type `R`.
+    pub fn entry<R>(&mut self) -> Entry<'_, R, &mut Self>
+    where
+        R: LocalResource,
+    {
+        if self.contains::<R>() {
+            Entry::Occupied(OccupiedEntry::new(self))
+        } else {
+            Entry::Vacant(VacantEntry::new(self))
+        }
+    }
+
+    /// Inserts a resource into the map. If the resource existed before,
+    /// it will be overwritten, and the previous value is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::rc::Rc;
+    ///
+    /// use resman::LocalResources;
+    ///
+    /// let mut resources = LocalResources::new();
+    /// assert!(resources.insert(Rc::new(5)).is_none());
+    /// assert_eq!(Some(Rc::new(5)), resources.insert(Rc::new(6)));
+    /// ```
+    pub fn insert<R>(&mut self, r: R) -> Option<R>
+    where
+        R: LocalResource,
+    {
+        self.inner
+            .insert(TypeId::of::<R>(), RefCell::new(Box::new(r)))
+            .map(|previous| Self::downcast_boxed::<R>(previous.into_inner()))
+    }
+
+    /// Inserts a resource into the map, and includes its value in the `Debug`
+    /// output of `LocalResources`. If the resource existed before, it will be
+    /// overwritten, and the previous value is returned.
+    ///
+    /// Resources inserted with [`insert`] are printed as `".."`.
+    ///
+    /// [`insert`]: Self::insert
+    pub fn insert_debug<R>(&mut self, r: R) -> Option<R>
+    where
+        R: LocalResource + fmt::Debug,
+    {
+        self.register_debug::<R>();
+        self.insert(r)
+    }
+
+    /// Includes the value of `R` in the `Debug` output of `LocalResources`.
+    ///
+    /// This is useful for resources that are inserted through [`entry`].
+    ///
+    /// [`entry`]: Self::entry
+    pub fn register_debug<R>(&mut self)
+    where
+        R: LocalResource + fmt::Debug,
+    {
+        self.debug_fns
+            .insert(TypeId::of::<R>(), resource_debug::local_debug_fn::<R>());
+    }
+
+    /// Removes a resource of type `R` from this container and returns its
+    /// ownership to the caller.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource doesn't exist in this container.
+    pub fn remove<R>(&mut self) -> R
+    where
+        R: LocalResource,
+    {
+        self.try_remove::<R>().unwrap()
+    }
+
+    /// Removes a resource of type `R` from this container and returns its
+    /// ownership to the caller.
+    pub fn try_remove<R>(&mut self) -> Result<R, ResourceError>
+    where
+        R: LocalResource,
+    {
+        self.inner
+            .remove(&TypeId::of::<R>())
+            .map(|resource| Self::downcast_boxed::<R>(resource.into_inner()))
+            .ok_or_else(ResourceError::not_found::<R>)
+    }
+
+    /// Returns true if the specified resource type `R` exists in `self`.
+    pub fn contains<R>(&self) -> bool
+    where
+        R: LocalResource,
+    {
+        self.inner.contains_key(&TypeId::of::<R>())
+    }
+
+    /// Returns an immutable reference to `R`.
+    ///
+    /// See [`try_borrow`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource doesn't exist.
+    /// Panics if the resource is being accessed mutably.
+    ///
+    /// [`try_borrow`]: Self::try_borrow
+    pub fn borrow<R>(&self) -> cell::Ref<'_, R>
+    where
+        R: LocalResource,
+    {
+        self.try_borrow::<R>()
+            .unwrap_or_else(Resources::borrow_panic)
+    }
+
+    /// Returns an immutable reference to `R`.
+    pub fn try_borrow<R>(&self) -> Result<cell::Ref<'_, R>, ResourceError>
+    where
+        R: LocalResource,
+    {
+        let resource = self
+            .cell::<R>(BorrowKind::Immutable)?
+            .try_borrow()
+            .map_err(|_| {
+                ResourceError::new(
+                    core::any::type_name::<R>(),
+                    Some(BorrowKind::Immutable),
+                    ResourceErrorKind::ConflictMutable,
+                )
+            })?;
+
+        Ok(cell::Ref::map(resource, |resource| {
+            (**resource)
+                .downcast_ref::<R>()
+                .unwrap_or_else(|| panic!("Failed to downcast to {}", core::any::type_name::<R>()))
+        }))
+    }
+
+    /// Returns a mutable reference to `R`.
+    ///
+    /// See [`try_borrow_mut`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource doesn't exist.
+    /// Panics if the resource is already accessed.
+    ///
+    /// [`try_borrow_mut`]: Self::try_borrow_mut
+    pub fn borrow_mut<R>(&self) -> cell::RefMut<'_, R>
+    where
+        R: LocalResource,
+    {
+        self.try_borrow_mut::<R>()
+            .unwrap_or_else(Resources::borrow_panic)
+    }
+
+    /// Returns a mutable reference to `R`.
+    pub fn try_borrow_mut<R>(&self) -> Result<cell::RefMut<'_, R>, ResourceError>
+    where
+        R: LocalResource,
+    {
+        let cell = self.cell::<R>(BorrowKind::Mutable)?;
+        let resource = cell.try_borrow_mut().map_err(|_| {
+            let kind = if cell.try_borrow().is_err() {
+                ResourceErrorKind::ConflictMutable
+            } else {
+                ResourceErrorKind::ConflictShared
+            };
+            ResourceError::new(core::any::type_name::<R>(), Some(BorrowKind::Mutable), kind)
+        })?;
+
+        Ok(cell::RefMut::map(resource, |resource| {
+            (**resource)
+                .downcast_mut::<R>()
+                .unwrap_or_else(|| panic!("Failed to downcast to {}", core::any::type_name::<R>()))
+        }))
+    }
+
+    /// Retrieves a resource without checking borrows, which is cheaper, but
+    /// only available with `&mut self`.
+    pub fn get_mut<R>(&mut self) -> Option<&mut R>
+    where
+        R: LocalResource,
+    {
+        self.inner
+            .get_mut(&TypeId::of::<R>())
+            .map(|resource| (**resource.get_mut()).downcast_mut::<R>().unwrap())
+    }
+
+    /// Returns the cell holding `R`, or a `NotFound` error.
+    fn cell<R>(
+        &self,
+        borrow_kind: BorrowKind,
+    ) -> Result<&RefCell<Box<dyn LocalResource>>, ResourceError>
+    where
+        R: LocalResource,
+    {
+        self.inner.get(&TypeId::of::<R>()).ok_or_else(|| {
+            ResourceError::new(
+                core::any::type_name::<R>(),
+                Some(borrow_kind),
+                ResourceErrorKind::NotFound,
+            )
+        })
+    }
+
+    /// Downcasts a boxed resource that is known to be `R`.
+    fn downcast_boxed<R>(resource: Box<dyn LocalResource>) -> R
+    where
+        R: LocalResource,
+    {
+        let resource: Box<R> = resource
+            .downcast()
+            .ok()
+            .expect("Expected resource to be stored under its own `TypeId`.");
+        *resource
+    }
+}
+
+impl fmt::Debug for LocalResources {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut debug_map = f.debug_map();
+
+        self.inner.iter().for_each(|(type_id, resource)| {
+            let resource = &*resource.borrow();
+            let type_name = resource.as_ref().type_name();
+            let resource_debug = ResourceDebug {
+                resource: resource.as_ref(),
+                debug_fn: self.debug_fns.get(type_id).copied(),
+            };
+
+            debug_map.entry(&type_name, &resource_debug);
+        });
+
+        debug_map.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::{Entry, LocalResources, ResourceErrorKind};
+
+    #[test]
+    fn insert_returns_previous_value_and_borrow_returns_value() {
+        let mut resources = LocalResources::new();
+
+        assert!(resources.insert(Rc::new(1u32)).is_none());
+        assert_eq!(Some(Rc::new(1u32)), resources.insert(Rc::new(2u32)));
+
+        *resources.borrow_mut::<Rc<u32>>() = Rc::new(3);
+        assert_eq!(3, **resources.borrow::<Rc<u32>>());
+        assert_eq!(1, resources.len());
+    }
+
+    #[test]
+    fn try_borrow_returns_resource_error() {
+        let mut resources = LocalResources::new();
+        resources.insert(Rc::new(1u32));
+
+        assert_eq!(
+            Some(ResourceErrorKind::NotFound),
+            resources.try_borrow::<Rc<u64>>().err().map(|e| e.kind())
+        );
+
+        let rc_u32 = resources.borrow_mut::<Rc<u32>>();
+        assert_eq!(
+            Some(ResourceErrorKind::ConflictMutable),
+            resources.try_borrow::<Rc<u32>>().err().map(|e| e.kind())
+        );
+        assert_eq!(
+            Some(ResourceErrorKind::ConflictMutable),
+            resources
+                .try_borrow_mut::<Rc<u32>>()
+                .err()
+                .map(|e| e.kind())
+        );
+        drop(rc_u32);
+
+        let _rc_u32 = resources.borrow::<Rc<u32>>();
+        assert_eq!(
+            Some(ResourceErrorKind::ConflictShared),
+            resources
+                .try_borrow_mut::<Rc<u32>>()
+                .err()
+                .map(|e| e.kind())
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `u32` mutably, but it was already borrowed immutably."
+    )]
+    fn borrow_mut_panics_when_already_borrowed() {
+        let mut resources = LocalResources::new();
+        resources.insert(1u32);
+
+        let _u32 = resources.borrow::<u32>();
+        resources.borrow_mut::<u32>();
+    }
+
+    #[test]
+    fn entry_get_mut_and_remove() {
+        let mut resources = LocalResources::new();
+
+        *resources.entry::<Rc<u32>>().or_insert(Rc::new(1)) = Rc::new(2);
+        if let Entry::Occupied(mut occupied) = resources.entry::<Rc<u32>>() {
+            assert_eq!(Rc::new(2), occupied.insert(Rc::new(3)));
+        }
+        *resources.get_mut::<Rc<u32>>().unwrap() = Rc::new(4);
+
+        assert_eq!(Rc::new(4), resources.remove::<Rc<u32>>());
+        assert!(resources.try_remove::<Rc<u32>>().is_err());
+        assert!(resources.is_empty());
+    }
+
+    #[test]
+    fn debug_uses_debug_impl_for_insert_debug() {
+        let mut resources = LocalResources::new();
+        resources.insert_debug(Rc::new(1u32));
+        resources.insert(2u64);
+
+        let resources_dbg = format!("{resources:?}");
+        assert!(resources_dbg.contains("alloc::rc::Rc<u32>: 1"));
+        assert!(resources_dbg.contains(r#"u64: "..""#));
+    }
+}
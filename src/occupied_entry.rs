@@ -0,0 +1,69 @@
+use core::marker::PhantomData;
+
+use crate::{EntryBackend, Resources};
+
+#[cfg(feature = "std")]
+use crate::KeyedEntryBackend;
+
+/// An entry to a resource that exists in its map.
+///
+/// Part of the [`Entry`] enum.
+///
+/// [`Entry`]: crate::Entry
+pub struct OccupiedEntry<'a, R, B = &'a mut Resources> {
+    /// The map that contains `R`.
+    backend: B,
+    marker: PhantomData<(&'a (), R)>,
+}
+
+impl<'a, R, B> OccupiedEntry<'a, R, B>
+where
+    B: EntryBackend<'a, R>,
+{
+    /// Returns a new `OccupiedEntry`.
+    ///
+    /// `backend` must contain `R`.
+    pub(crate) fn new(backend: B) -> Self {
+        Self {
+            backend,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the resource.
+    pub fn get(&self) -> B::Ref<'_> {
+        self.backend.get()
+    }
+
+    /// Returns the resource mutably.
+    ///
+    /// See [`into_mut`] to return a `RefMut` that outlives the entry.
+    ///
+    /// [`into_mut`]: Self::into_mut
+    pub fn get_mut(&mut self) -> B::RefMut<'_> {
+        self.backend.get_mut()
+    }
+
+    /// Returns the resource mutably, with the lifetime of the map.
+    pub fn into_mut(self) -> B::RefMut<'a> {
+        self.backend.into_mut()
+    }
+
+    /// Replaces the resource with `value`, and returns the previous value.
+    pub fn insert(&mut self, value: R) -> R {
+        core::mem::replace(&mut *self.get_mut(), value)
+    }
+
+    /// Removes the resource from its map, and returns it.
+    pub fn remove(self) -> R {
+        self.backend.remove()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, K, R> OccupiedEntry<'a, R, KeyedEntryBackend<'a, K>> {
+    /// Returns the key of this entry.
+    pub fn key(&self) -> &K {
+        self.backend.key()
+    }
+}
@@ -0,0 +1,50 @@
+use core::fmt;
+
+use crate::BorrowKind;
+
+/// A resource that is still borrowed.
+///
+/// Returned by [`Resources::outstanding_borrows`].
+///
+/// [`Resources::outstanding_borrows`]: crate::Resources::outstanding_borrows
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutstandingBorrow {
+    /// Full type name of the resource.
+    pub type_name: &'static str,
+    /// Whether the resource is borrowed immutably or mutably.
+    pub borrow_kind: BorrowKind,
+    /// Number of borrows that have not been dropped.
+    pub count: usize,
+}
+
+impl OutstandingBorrow {
+    /// Returns the full type name of the resource.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns whether the resource is borrowed immutably or mutably.
+    pub fn borrow_kind(&self) -> BorrowKind {
+        self.borrow_kind
+    }
+
+    /// Returns the number of borrows that have not been dropped.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl fmt::Display for OutstandingBorrow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            type_name,
+            borrow_kind,
+            count,
+        } = self;
+
+        write!(
+            f,
+            "`{type_name}` is borrowed {borrow_kind} (count: {count})"
+        )
+    }
+}
@@ -1,33 +1,82 @@
-use std::{fmt, marker::PhantomData, ops::Deref};
+use alloc::boxed::Box;
+use core::{any::Any, fmt, marker::PhantomData, ops::Deref};
 
-use crate::Resource;
+use crate::{resource_tracker::ResourceTracker, CellRef, Resource};
 
 /// Reference to a resource.
-#[derive(Clone)]
-pub struct Ref<'a, R: 'a> {
-    inner: rt_map::Ref<'a, Box<dyn Resource>>,
+///
+/// `R` may be `dyn Resource` when the resource type is only known at runtime.
+pub struct Ref<'a, R: 'a + ?Sized> {
+    inner: CellRef<'a, Box<dyn Resource>>,
+    /// Tracks outstanding borrows, if borrowed through `Resources`.
+    tracker: Option<&'a ResourceTracker>,
     phantom: PhantomData<&'a R>,
 }
 
-impl<'a, R> Ref<'a, R> {
-    pub fn new(inner: rt_map::Ref<'a, Box<dyn Resource>>) -> Self {
+impl<'a, R> Ref<'a, R>
+where
+    R: ?Sized,
+{
+    pub fn new(inner: CellRef<'a, Box<dyn Resource>>) -> Self {
+        Self::new_tracked(inner, None)
+    }
+
+    /// Returns a new `Ref`, recording the borrow in the given tracker.
+    pub(crate) fn new_tracked(
+        inner: CellRef<'a, Box<dyn Resource>>,
+        tracker: Option<&'a ResourceTracker>,
+    ) -> Self {
+        if let Some(tracker) = tracker {
+            tracker.ref_acquired();
+        }
+
         Self {
             inner,
+            tracker,
             phantom: PhantomData,
         }
     }
 }
 
+impl<'a, R> Clone for Ref<'a, R>
+where
+    R: 'a + ?Sized,
+{
+    fn clone(&self) -> Self {
+        Self::new_tracked(self.inner.clone(), self.tracker)
+    }
+}
+
+impl<'a, R> Drop for Ref<'a, R>
+where
+    R: 'a + ?Sized,
+{
+    fn drop(&mut self) {
+        if let Some(tracker) = self.tracker {
+            tracker.ref_released();
+        }
+    }
+}
+
 impl<R> Deref for Ref<'_, R>
 where
-    R: Resource,
+    R: Any,
 {
     type Target = R;
 
     fn deref(&self) -> &R {
-        (*self.inner)
+        (**self.inner)
+            .as_any()
             .downcast_ref::<R>()
-            .unwrap_or_else(|| panic!("Failed to downcast to {}", std::any::type_name::<R>()))
+            .unwrap_or_else(|| panic!("Failed to downcast to {}", core::any::type_name::<R>()))
+    }
+}
+
+impl Deref for Ref<'_, dyn Resource> {
+    type Target = dyn Resource;
+
+    fn deref(&self) -> &dyn Resource {
+        &**self.inner
     }
 }
 
@@ -54,11 +103,9 @@ where
 
 #[cfg(test)]
 mod tests {
-    use std::fmt::{self, Write};
-
-    use rt_map::Cell;
+    use core::fmt::{self, Write};
 
-    use crate::Resource;
+    use crate::{Cell, Resource};
 
     use super::Ref;
 
@@ -66,7 +113,7 @@ mod tests {
     fn debug_includes_inner_field() -> fmt::Result {
         let value: Box<dyn Resource> = Box::new(A(1));
         let cell = Cell::new(value);
-        let r#ref = Ref::<A>::new(rt_map::Ref::new(cell.borrow()));
+        let r#ref = Ref::<A>::new(cell.borrow());
 
         let mut debug_string = String::with_capacity(64);
         write!(&mut debug_string, "{:?}", r#ref)?;
@@ -79,14 +126,14 @@ mod tests {
     fn partial_eq_compares_value() -> fmt::Result {
         let value_0: Box<dyn Resource> = Box::new(A(1));
         let cell_0 = Cell::new(value_0);
-        let ref_0 = Ref::<A>::new(rt_map::Ref::new(cell_0.borrow()));
+        let ref_0 = Ref::<A>::new(cell_0.borrow());
 
         let value_1: Box<dyn Resource> = Box::new(A(1));
         let cell_1 = Cell::new(value_1);
-        let ref_1 = Ref::<A>::new(rt_map::Ref::new(cell_1.borrow()));
+        let ref_1 = Ref::<A>::new(cell_1.borrow());
 
         assert_eq!(ref_1, ref_0);
-        assert_eq!(Ref::<A>::new(rt_map::Ref::new(cell_0.borrow())), ref_0);
+        assert_eq!(Ref::<A>::new(cell_0.borrow()), ref_0);
 
         Ok(())
     }
@@ -1,15 +1,37 @@
-use std::{
+use alloc::boxed::Box;
+use core::{
+    any::Any,
     cmp::PartialEq,
     fmt,
     marker::PhantomData,
     ops::{Deref, DerefMut},
 };
 
+#[cfg(feature = "stats")]
+use std::time::Instant;
+
 pub use crate::Resource;
 
+use crate::{
+    resource_tracker::ResourceTracker, subscribers::Subscribers, Cell, CellRefMut, Resources,
+};
+
 /// Mutable reference to a resource.
-pub struct RefMut<'a, R: 'a> {
-    inner: rt_map::RefMut<'a, Box<dyn Resource>>,
+///
+/// `R` may be `dyn Resource` when the resource type is only known at runtime.
+pub struct RefMut<'a, R: 'a + ?Sized> {
+    /// The borrow, which is only `None` while the `RefMut` is being dropped.
+    inner: Option<CellRefMut<'a, Box<dyn Resource>>>,
+    /// Tracks outstanding borrows, if borrowed through `Resources`.
+    tracker: Option<&'a ResourceTracker>,
+    /// Subscribers to notify when the borrow is released, and the cell to
+    /// borrow the resource from to notify them.
+    subscribers: Option<(&'a Subscribers, &'a Cell<Box<dyn Resource>>)>,
+    /// Whether the resource was dereferenced mutably.
+    dirty: bool,
+    /// When the borrow was taken, if tracked.
+    #[cfg(feature = "stats")]
+    borrowed_at: Option<Instant>,
     phantom: PhantomData<&'a R>,
 }
 
@@ -34,46 +56,138 @@ where
     }
 }
 
-impl<'a, R> RefMut<'a, R> {
-    pub fn new(inner: rt_map::RefMut<'a, Box<dyn Resource>>) -> Self {
+impl<'a, R> RefMut<'a, R>
+where
+    R: ?Sized,
+{
+    pub fn new(inner: CellRefMut<'a, Box<dyn Resource>>) -> Self {
+        Self::new_tracked(inner, None)
+    }
+
+    /// Returns a new `RefMut`, recording the borrow in the given tracker.
+    pub(crate) fn new_tracked(
+        inner: CellRefMut<'a, Box<dyn Resource>>,
+        tracker: Option<&'a ResourceTracker>,
+    ) -> Self {
+        if let Some(tracker) = tracker {
+            tracker.ref_mut_acquired();
+        }
+
         Self {
-            inner,
+            inner: Some(inner),
+            tracker,
+            subscribers: None,
+            dirty: false,
+            #[cfg(feature = "stats")]
+            borrowed_at: tracker.map(|_| Instant::now()),
             phantom: PhantomData,
         }
     }
+
+    /// Notifies the given subscribers when this `RefMut` is dropped, if the
+    /// resource was dereferenced mutably.
+    pub(crate) fn with_subscribers(
+        mut self,
+        subscribers: &'a Subscribers,
+        cell: &'a Cell<Box<dyn Resource>>,
+    ) -> Self {
+        self.subscribers = Some((subscribers, cell));
+        self
+    }
+
+    fn inner(&self) -> &CellRefMut<'a, Box<dyn Resource>> {
+        self.inner
+            .as_ref()
+            .expect("Expected `RefMut` inner borrow to exist until dropped.")
+    }
+
+    fn inner_mut(&mut self) -> &mut CellRefMut<'a, Box<dyn Resource>> {
+        self.dirty = true;
+        self.inner
+            .as_mut()
+            .expect("Expected `RefMut` inner borrow to exist until dropped.")
+    }
+}
+
+impl<'a, R> Drop for RefMut<'a, R>
+where
+    R: 'a + ?Sized,
+{
+    fn drop(&mut self) {
+        if let Some(tracker) = self.tracker {
+            tracker.ref_mut_released();
+
+            #[cfg(feature = "stats")]
+            if let Some(borrowed_at) = self.borrowed_at {
+                tracker.ref_mut_held(borrowed_at.elapsed());
+            }
+        }
+
+        // Release the borrow before notifying subscribers, so they can read the
+        // new value.
+        self.inner = None;
+
+        if let Some((subscribers, cell)) = self.subscribers
+            && self.dirty
+            && !Resources::panicking()
+        {
+            #[cfg(feature = "std")]
+            subscribers.notify_watchers();
+
+            // If the resource was borrowed mutably in the meantime, that
+            // borrow notifies the subscribers when it is released.
+            if let Ok(resource) = cell.try_borrow() {
+                subscribers.notify(&**resource);
+            }
+        }
+    }
 }
 
 impl<R> Deref for RefMut<'_, R>
 where
-    R: Resource,
+    R: Any,
 {
     type Target = R;
 
     fn deref(&self) -> &R {
-        self.inner
+        (***self.inner())
+            .as_any()
             .downcast_ref::<R>()
-            .unwrap_or_else(|| panic!("Failed to downcast to {}", std::any::type_name::<R>()))
+            .unwrap_or_else(|| panic!("Failed to downcast to {}", core::any::type_name::<R>()))
     }
 }
 
 impl<R> DerefMut for RefMut<'_, R>
 where
-    R: Resource,
+    R: Any,
 {
     fn deref_mut(&mut self) -> &mut R {
-        self.inner
+        (***self.inner_mut())
+            .as_any_mut()
             .downcast_mut::<R>()
-            .unwrap_or_else(|| panic!("Failed to downcast to {}", std::any::type_name::<R>()))
+            .unwrap_or_else(|| panic!("Failed to downcast to {}", core::any::type_name::<R>()))
+    }
+}
+
+impl Deref for RefMut<'_, dyn Resource> {
+    type Target = dyn Resource;
+
+    fn deref(&self) -> &dyn Resource {
+        &***self.inner()
+    }
+}
+
+impl DerefMut for RefMut<'_, dyn Resource> {
+    fn deref_mut(&mut self) -> &mut dyn Resource {
+        &mut ***self.inner_mut()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::fmt::{self, Write};
-
-    use rt_map::Cell;
+    use core::fmt::{self, Write};
 
-    use crate::Resource;
+    use crate::{Cell, Resource};
 
     use super::RefMut;
 
@@ -81,7 +195,7 @@ mod tests {
     fn debug_includes_inner_field() -> fmt::Result {
         let value: Box<dyn Resource> = Box::new(A(1));
         let cell = Cell::new(value);
-        let ref_mut = RefMut::<A>::new(rt_map::RefMut::new(cell.borrow_mut()));
+        let ref_mut = RefMut::<A>::new(cell.borrow_mut());
 
         let mut debug_string = String::with_capacity(64);
         write!(&mut debug_string, "{:?}", ref_mut)?;
@@ -95,9 +209,9 @@ mod tests {
         let value_0: Box<dyn Resource> = Box::new(A(1));
         let value_1: Box<dyn Resource> = Box::new(A(1));
         let cell_0 = Cell::new(value_0);
-        let ref_mut_0 = RefMut::<A>::new(rt_map::RefMut::new(cell_0.borrow_mut()));
+        let ref_mut_0 = RefMut::<A>::new(cell_0.borrow_mut());
         let cell_1 = Cell::new(value_1);
-        let ref_mut_1 = RefMut::<A>::new(rt_map::RefMut::new(cell_1.borrow_mut()));
+        let ref_mut_1 = RefMut::<A>::new(cell_1.borrow_mut());
 
         assert_eq!(ref_mut_1, ref_mut_0);
 
@@ -108,7 +222,7 @@ mod tests {
     fn deref_mut_returns_value() -> fmt::Result {
         let value: Box<dyn Resource> = Box::new(A(1));
         let cell = Cell::new(value);
-        let mut ref_mut = RefMut::<A>::new(rt_map::RefMut::new(cell.borrow_mut()));
+        let mut ref_mut = RefMut::<A>::new(cell.borrow_mut());
 
         assert_eq!(&mut A(1), &*ref_mut);
 
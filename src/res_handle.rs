@@ -0,0 +1,152 @@
+use core::{fmt, marker::PhantomData};
+
+use crate::{resources_id::ResourcesId, Ref, RefMut, Resource, ResourceError, Resources};
+
+/// Handle to a resource, resolved once from a `Resources` map.
+///
+/// Created by [`Resources::handle`]. The handle refers to the resource by its
+/// slot index in the `Resources` map that created it, and checks that:
+///
+/// * It is used with the same `Resources` map.
+/// * The resource has not been removed since the handle was created.
+///
+/// Replacing the resource through `Resources::insert` keeps the handle valid.
+///
+/// Borrowing through the handle indexes into the slot directly, so unlike
+/// `Resources::borrow`, it does not look up the resource's `TypeId`.
+///
+/// # Examples
+///
+/// ```rust
+/// use resman::{ResourceErrorKind, Resources};
+///
+/// let mut resources = Resources::new();
+/// resources.insert(1u32);
+///
+/// let handle = resources.handle::<u32>();
+/// for _ in 0..3 {
+///     *handle.borrow_mut(&resources) += 1;
+/// }
+/// assert_eq!(4, *handle.borrow(&resources));
+///
+/// let _ = resources.remove::<u32>();
+/// resources.insert(5u32);
+///
+/// assert_eq!(
+///     Some(ResourceErrorKind::HandleRemoved),
+///     handle
+///         .try_borrow(&resources)
+///         .err()
+///         .map(|error| error.kind())
+/// );
+/// ```
+///
+/// [`Resources::handle`]: crate::Resources::handle
+pub struct ResHandle<R> {
+    /// ID of the `Resources` map that created this handle.
+    resources_id: ResourcesId,
+    /// Index of the resource's slot.
+    index: usize,
+    /// Generation of the slot when this handle was created.
+    generation: usize,
+    marker: PhantomData<fn() -> R>,
+}
+
+impl<R> ResHandle<R>
+where
+    R: Resource,
+{
+    /// Returns a new `ResHandle`.
+    pub(crate) fn new(resources_id: ResourcesId, index: usize, generation: usize) -> Self {
+        Self {
+            resources_id,
+            index,
+            generation,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the ID of the `Resources` map that created this handle.
+    pub(crate) fn resources_id(&self) -> ResourcesId {
+        self.resources_id
+    }
+
+    /// Returns the index of the resource's slot.
+    pub(crate) fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the generation of the slot when this handle was created.
+    pub(crate) fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Returns the resource.
+    ///
+    /// See [`try_borrow`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle was created by a different `Resources` map.
+    /// Panics if the resource was removed after the handle was created.
+    /// Panics if the resource is being accessed mutably.
+    ///
+    /// [`try_borrow`]: Self::try_borrow
+    pub fn borrow<'r>(&self, resources: &'r Resources) -> Ref<'r, R> {
+        resources.borrow_handle(self)
+    }
+
+    /// Returns the resource.
+    ///
+    /// Returns a `HandleMismatch` or `HandleRemoved` error if the handle is not
+    /// valid for `resources`, or another error if the resource cannot be
+    /// borrowed.
+    pub fn try_borrow<'r>(&self, resources: &'r Resources) -> Result<Ref<'r, R>, ResourceError> {
+        resources.try_borrow_handle(self)
+    }
+
+    /// Returns the resource mutably.
+    ///
+    /// See [`try_borrow_mut`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle was created by a different `Resources` map.
+    /// Panics if the resource was removed after the handle was created.
+    /// Panics if the resource is already accessed.
+    ///
+    /// [`try_borrow_mut`]: Self::try_borrow_mut
+    pub fn borrow_mut<'r>(&self, resources: &'r Resources) -> RefMut<'r, R> {
+        resources.borrow_mut_handle(self)
+    }
+
+    /// Returns the resource mutably.
+    ///
+    /// Returns a `HandleMismatch` or `HandleRemoved` error if the handle is not
+    /// valid for `resources`, or another error if the resource cannot be
+    /// borrowed.
+    pub fn try_borrow_mut<'r>(
+        &self,
+        resources: &'r Resources,
+    ) -> Result<RefMut<'r, R>, ResourceError> {
+        resources.try_borrow_mut_handle(self)
+    }
+}
+
+impl<R> Clone for ResHandle<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R> Copy for ResHandle<R> {}
+
+impl<R> fmt::Debug for ResHandle<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResHandle")
+            .field("type_name", &core::any::type_name::<R>())
+            .field("index", &self.index)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
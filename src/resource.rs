@@ -1,4 +1,4 @@
-use std::any::{Any, TypeId};
+use core::any::{Any, TypeId};
 
 use downcast_rs::DowncastSync;
 
@@ -7,13 +7,17 @@ use downcast_rs::DowncastSync;
 /// A resource is a data slot which lives in the `World` can only be accessed
 /// according to Rust's typical borrowing model (one writer xor multiple
 /// readers).
-#[cfg(not(feature = "debug"))]
+///
+/// Resources do not need to implement `Debug`. Use
+/// [`Resources::insert_debug`] to include a resource's value in the `Debug`
+/// output of `Resources`.
+///
+/// [`Resources::insert_debug`]: crate::Resources::insert_debug
 pub trait Resource: DowncastSync + 'static {
     fn type_id(&self) -> TypeId;
     fn type_name(&self) -> TypeNameLit;
 }
 
-#[cfg(not(feature = "debug"))]
 impl<T> Resource for T
 where
     T: Any + Send + Sync,
@@ -23,41 +27,23 @@ where
     }
 
     fn type_name(&self) -> TypeNameLit {
-        TypeNameLit(std::any::type_name::<T>())
+        TypeNameLit(core::any::type_name::<T>())
     }
 }
 
-/// Trait to represent any type that is `Send + Sync + 'static`.
-///
-/// A resource is a data slot which lives in the `World` can only be accessed
-/// according to Rust's typical borrowing model (one writer xor multiple
-/// readers).
-#[cfg(feature = "debug")]
-pub trait Resource: DowncastSync + std::fmt::Debug + 'static {
-    fn type_id(&self) -> TypeId;
-    fn type_name(&self) -> TypeNameLit;
-}
+downcast_rs::impl_downcast!(sync Resource);
 
-#[cfg(feature = "debug")]
-impl<T> Resource for T
-where
-    T: Any + std::fmt::Debug + Send + Sync,
-{
-    fn type_id(&self) -> TypeId {
-        TypeId::of::<T>()
-    }
+use core::fmt;
 
-    fn type_name(&self) -> TypeNameLit {
-        TypeNameLit(std::any::type_name::<T>())
+pub struct TypeNameLit(pub(crate) &'static str);
+
+impl TypeNameLit {
+    /// Returns the type name.
+    pub(crate) fn as_str(&self) -> &'static str {
+        self.0
     }
 }
 
-downcast_rs::impl_downcast!(sync Resource);
-
-use std::fmt;
-
-pub struct TypeNameLit(&'static str);
-
 impl fmt::Debug for TypeNameLit {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -0,0 +1,224 @@
+use core::any::TypeId;
+
+use crate::{Resource, ResourceError, ResourceErrorKind, Resources};
+
+/// Tuple of resources that are inserted and removed together.
+///
+/// Implemented for tuples of up to 16 resources. Used by
+/// [`Resources::insert_bundle`], [`Resources::remove_bundle`], and
+/// [`Resources::contains_all`].
+///
+/// The resource types in a bundle must be distinct. Inserting or removing a
+/// bundle that contains the same type more than once returns a
+/// `ResourceErrorKind::DuplicateType` error.
+///
+/// [`Resources::insert_bundle`]: crate::Resources::insert_bundle
+/// [`Resources::remove_bundle`]: crate::Resources::remove_bundle
+/// [`Resources::contains_all`]: crate::Resources::contains_all
+pub trait ResourceBundle: Sized {
+    /// Inserts each resource into `resources`.
+    ///
+    /// If the bundle contains the same type more than once, none are
+    /// inserted, and an error is returned for the first repeated type.
+    fn insert_into(self, resources: &mut Resources) -> Result<(), ResourceError>;
+
+    /// Returns true if every resource type in the bundle exists in
+    /// `resources`.
+    fn contained_in(resources: &Resources) -> bool;
+
+    /// Removes every resource in the bundle from `resources`.
+    ///
+    /// If the bundle contains the same type more than once, or any resource
+    /// does not exist, none are removed, and an error is returned for the
+    /// first repeated or missing resource.
+    fn remove_from(resources: &mut Resources) -> Result<Self, ResourceError>;
+}
+
+/// Returns a `DuplicateType` error for the first type that appears more than
+/// once in `types`.
+fn ensure_distinct(types: &[(TypeId, &'static str)]) -> Result<(), ResourceError> {
+    types
+        .iter()
+        .enumerate()
+        .find(|(index, (type_id, _))| {
+            types[..*index]
+                .iter()
+                .any(|(type_id_earlier, _)| type_id_earlier == type_id)
+        })
+        .map_or(Ok(()), |(_, (_, type_name))| {
+            Err(ResourceError::new(
+                type_name,
+                None,
+                ResourceErrorKind::DuplicateType,
+            ))
+        })
+}
+
+macro_rules! resource_bundle_impl {
+    ($($R:ident $r:ident),+) => {
+        impl<$($R),+> ResourceBundle for ($($R,)+)
+        where
+            $($R: Resource),+
+        {
+            fn insert_into(self, resources: &mut Resources) -> Result<(), ResourceError> {
+                ensure_distinct(&[$((TypeId::of::<$R>(), core::any::type_name::<$R>())),+])?;
+
+                let ($($r,)+) = self;
+                $(resources.insert($r);)+
+                Ok(())
+            }
+
+            fn contained_in(resources: &Resources) -> bool {
+                $(resources.contains::<$R>())&&+
+            }
+
+            fn remove_from(resources: &mut Resources) -> Result<Self, ResourceError> {
+                ensure_distinct(&[$((TypeId::of::<$R>(), core::any::type_name::<$R>())),+])?;
+
+                $(
+                    if !resources.contains::<$R>() {
+                        return Err(resources.not_found_error::<$R>());
+                    }
+                )+
+
+                Ok(($(resources.remove::<$R>(),)+))
+            }
+        }
+    };
+}
+
+resource_bundle_impl!(R0 r0);
+resource_bundle_impl!(R0 r0, R1 r1);
+resource_bundle_impl!(R0 r0, R1 r1, R2 r2);
+resource_bundle_impl!(R0 r0, R1 r1, R2 r2, R3 r3);
+resource_bundle_impl!(R0 r0, R1 r1, R2 r2, R3 r3, R4 r4);
+resource_bundle_impl!(R0 r0, R1 r1, R2 r2, R3 r3, R4 r4, R5 r5);
+resource_bundle_impl!(R0 r0, R1 r1, R2 r2, R3 r3, R4 r4, R5 r5, R6 r6);
+resource_bundle_impl!(R0 r0, R1 r1, R2 r2, R3 r3, R4 r4, R5 r5, R6 r6, R7 r7);
+resource_bundle_impl!(R0 r0, R1 r1, R2 r2, R3 r3, R4 r4, R5 r5, R6 r6, R7 r7, R8 r8);
+resource_bundle_impl!(R0 r0, R1 r1, R2 r2, R3 r3, R4 r4, R5 r5, R6 r6, R7 r7, R8 r8, R9 r9);
+resource_bundle_impl!(R0 r0, R1 r1, R2 r2, R3 r3, R4 r4, R5 r5, R6 r6, R7 r7, R8 r8, R9 r9, R10 r10);
+resource_bundle_impl!(R0 r0, R1 r1, R2 r2, R3 r3, R4 r4, R5 r5, R6 r6, R7 r7, R8 r8, R9 r9, R10 r10, R11 r11);
+resource_bundle_impl!(R0 r0, R1 r1, R2 r2, R3 r3, R4 r4, R5 r5, R6 r6, R7 r7, R8 r8, R9 r9, R10 r10, R11 r11, R12 r12);
+resource_bundle_impl!(R0 r0, R1 r1, R2 r2, R3 r3, R4 r4, R5 r5, R6 r6, R7 r7, R8 r8, R9 r9, R10 r10, R11 r11, R12 r12, R13 r13);
+resource_bundle_impl!(R0 r0, R1 r1, R2 r2, R3 r3, R4 r4, R5 r5, R6 r6, R7 r7, R8 r8, R9 r9, R10 r10, R11 r11, R12 r12, R13 r13, R14 r14);
+resource_bundle_impl!(R0 r0, R1 r1, R2 r2, R3 r3, R4 r4, R5 r5, R6 r6, R7 r7, R8 r8, R9 r9, R10 r10, R11 r11, R12 r12, R13 r13, R14 r14, R15 r15);
+
+#[cfg(test)]
+mod tests {
+    use crate::{ResourceErrorKind, Resources};
+
+    #[test]
+    fn insert_bundle_inserts_each_resource() {
+        let mut resources = Resources::new();
+        resources.insert_bundle((A(1), B(2)));
+
+        assert!(resources.contains_all::<(A, B)>());
+        assert!(!resources.contains_all::<(A, B, C)>());
+        assert_eq!(A(1), *resources.borrow::<A>());
+        assert_eq!(B(2), *resources.borrow::<B>());
+    }
+
+    #[test]
+    fn try_remove_bundle_removes_none_when_any_missing() {
+        let mut resources = Resources::new();
+        resources.insert_bundle((A(1), B(2)));
+
+        assert_eq!(
+            Err(core::any::type_name::<C>()),
+            resources
+                .try_remove_bundle::<(A, B, C)>()
+                .map_err(|error| error.type_name())
+        );
+        assert!(resources.contains_all::<(A, B)>());
+
+        assert_eq!(Ok((B(2), A(1))), resources.try_remove_bundle::<(B, A)>());
+        assert!(!resources.contains::<A>());
+        assert!(!resources.contains::<B>());
+    }
+
+    #[test]
+    fn try_insert_bundle_returns_err_when_type_is_repeated() {
+        let mut resources = Resources::new();
+
+        let error = resources.try_insert_bundle((A(1), B(2), A(3))).unwrap_err();
+
+        assert_eq!(ResourceErrorKind::DuplicateType, error.kind());
+        assert_eq!(core::any::type_name::<A>(), error.type_name());
+        assert!(!resources.contains::<A>());
+        assert!(!resources.contains::<B>());
+    }
+
+    #[test]
+    #[should_panic(expected = "appears more than once")]
+    fn insert_bundle_panics_when_type_is_repeated() {
+        let mut resources = Resources::new();
+
+        resources.insert_bundle((A(1), A(2)));
+    }
+
+    #[test]
+    fn try_remove_bundle_returns_err_when_type_is_repeated() {
+        let mut resources = Resources::new();
+        resources.insert(A(1));
+
+        let error = resources.try_remove_bundle::<(A, A)>().unwrap_err();
+
+        assert_eq!(ResourceErrorKind::DuplicateType, error.kind());
+        assert_eq!(core::any::type_name::<A>(), error.type_name());
+        assert_eq!(A(1), *resources.borrow::<A>());
+    }
+
+    #[test]
+    fn bundle_supports_16_elements() {
+        let mut resources = Resources::new();
+        resources.insert_bundle((
+            0u8,
+            1u16,
+            2u32,
+            3u64,
+            4u128,
+            5usize,
+            6i8,
+            7i16,
+            8i32,
+            9i64,
+            10i128,
+            11isize,
+            A(12),
+            B(13),
+            C(14),
+            'f',
+        ));
+
+        type Bundle = (
+            u8,
+            u16,
+            u32,
+            u64,
+            u128,
+            usize,
+            i8,
+            i16,
+            i32,
+            i64,
+            i128,
+            isize,
+            A,
+            B,
+            C,
+            char,
+        );
+        assert!(resources.contains_all::<Bundle>());
+        let removed = resources.remove_bundle::<Bundle>();
+        assert_eq!((0, A(12), 'f'), (removed.0, removed.12, removed.15));
+        assert!(resources.is_empty());
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct A(u32);
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct B(u32);
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct C(u32);
+}
@@ -0,0 +1,52 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::{similar_names::did_you_mean, ResourceError};
+
+/// Error when accessing a resource by its type name.
+///
+/// Returned by the `Resources::*_by_name` methods.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ResourceByNameError {
+    /// No type is registered in the `TypeRegistry` under the given name.
+    NotRegistered {
+        /// Name that was looked up.
+        name: String,
+        /// Registered names that are similar to `name`.
+        suggestions: Vec<String>,
+    },
+    /// The type was found, but the resource could not be borrowed.
+    BorrowFail {
+        /// Name that was looked up.
+        name: String,
+        /// The underlying borrow error.
+        error: ResourceError,
+    },
+}
+
+impl fmt::Display for ResourceByNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotRegistered { name, suggestions } => {
+                let did_you_mean = did_you_mean(suggestions);
+                write!(
+                    f,
+                    "Type `{name}` is not registered in the `TypeRegistry`.{did_you_mean}"
+                )
+            }
+            Self::BorrowFail { name, error: _ } => {
+                write!(f, "Failed to borrow `{name}` by name.")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ResourceByNameError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::NotRegistered { .. } => None,
+            Self::BorrowFail { error, .. } => Some(error),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use core::fmt;
+
+use crate::{LocalResource, Resource};
+
+/// Formats a resource using its type's `Debug` implementation.
+///
+/// `T` is `dyn LocalResource` for resources in `LocalResources`.
+pub(crate) type DebugFn<T = dyn Resource> = fn(&T, &mut fmt::Formatter<'_>) -> fmt::Result;
+
+/// Returns the [`DebugFn`] for `R`.
+pub(crate) fn debug_fn<R>() -> DebugFn
+where
+    R: Resource + fmt::Debug,
+{
+    |resource, f| match resource.downcast_ref::<R>() {
+        Some(resource) => fmt::Debug::fmt(resource, f),
+        None => fmt::Debug::fmt("..", f),
+    }
+}
+
+/// Returns the [`DebugFn`] for `R` in `LocalResources`.
+pub(crate) fn local_debug_fn<R>() -> DebugFn<dyn LocalResource>
+where
+    R: LocalResource + fmt::Debug,
+{
+    |resource, f| match resource.downcast_ref::<R>() {
+        Some(resource) => fmt::Debug::fmt(resource, f),
+        None => fmt::Debug::fmt("..", f),
+    }
+}
+
+/// Formats a resource with its `DebugFn`, or as `".."` if its type is not
+/// known to implement `Debug`.
+pub(crate) struct ResourceDebug<'r, T: ?Sized = dyn Resource> {
+    /// The resource to format.
+    pub(crate) resource: &'r T,
+    /// Function to format the resource, if its type implements `Debug`.
+    pub(crate) debug_fn: Option<DebugFn<T>>,
+}
+
+impl<T> fmt::Debug for ResourceDebug<'_, T>
+where
+    T: ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.debug_fn {
+            Some(debug_fn) => debug_fn(self.resource, f),
+            // At runtime, we are unable to determine if the resource is `Debug`.
+            None => fmt::Debug::fmt("..", f),
+        }
+    }
+}
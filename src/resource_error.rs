@@ -0,0 +1,248 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{
+    similar_names::{self, did_you_mean},
+    BorrowFail, BorrowKind, ResourceErrorKind,
+};
+
+/// Error when a resource cannot be accessed.
+///
+/// Returned by the `try_*` methods of `Resources`, `LocalResources`,
+/// `ResourcesView`, `Transaction` and `ResHandle`, and by `FnRes::try_call`.
+///
+/// The `Display` output is the same as the panic message of the panicking
+/// version of each method. When a resource is not found, stored resources
+/// with a similar type name are listed as suggestions, such as `app::Config`
+/// or `Arc<Config>` when `Config` was requested.
+///
+/// # Examples
+///
+/// ```rust
+/// use resman::{BorrowKind, ResourceErrorKind, Resources};
+///
+/// let mut resources = Resources::new();
+/// resources.insert(1u32);
+///
+/// let _a = resources.borrow::<u32>();
+/// let error = resources.try_borrow_mut::<u32>().unwrap_err();
+///
+/// assert_eq!(ResourceErrorKind::ConflictShared, error.kind());
+/// assert_eq!("u32", error.type_name());
+/// assert_eq!(Some(BorrowKind::Mutable), error.borrow_kind());
+/// assert_eq!(
+///     "Expected to borrow `u32` mutably, but it was already borrowed immutably.",
+///     error.to_string()
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResourceError {
+    /// Full type name of the resource.
+    type_name: &'static str,
+    /// How the resource was being borrowed, or `None` if it was being removed.
+    borrow_kind: Option<BorrowKind>,
+    /// Reason that the resource could not be accessed.
+    kind: ResourceErrorKind,
+    /// Type names of stored resources that are similar to `type_name`.
+    suggestions: Vec<&'static str>,
+    /// Name of the function running in strict mode that denied access.
+    fn_name: Option<&'static str>,
+}
+
+impl ResourceError {
+    /// Returns a new `ResourceError`.
+    pub(crate) fn new(
+        type_name: &'static str,
+        borrow_kind: Option<BorrowKind>,
+        kind: ResourceErrorKind,
+    ) -> Self {
+        Self {
+            type_name,
+            borrow_kind,
+            kind,
+            suggestions: Vec::new(),
+            fn_name: None,
+        }
+    }
+
+    /// Sets the type names of stored resources that are similar to the
+    /// requested type.
+    pub(crate) fn with_suggestions(mut self, suggestions: Vec<&'static str>) -> Self {
+        self.suggestions = suggestions;
+        self
+    }
+
+    /// Sets the name of the function running in strict mode that denied
+    /// access.
+    #[cfg(all(feature = "fn_res", feature = "fn_meta", feature = "std"))]
+    pub(crate) fn with_fn_name(mut self, fn_name: &'static str) -> Self {
+        self.fn_name = Some(fn_name);
+        self
+    }
+
+    /// Returns a `NotFound` error for `R`, which was not being borrowed.
+    pub(crate) fn not_found<R>() -> Self {
+        Self::new(
+            core::any::type_name::<R>(),
+            None,
+            ResourceErrorKind::NotFound,
+        )
+    }
+
+    /// Returns the error for a failed borrow.
+    ///
+    /// `BorrowFail::BorrowConflictMut` does not say whether the resource is
+    /// borrowed immutably or mutably, so `is_borrowed_mutably` is called to
+    /// find out.
+    pub(crate) fn borrow_failed<F>(
+        type_name: &'static str,
+        borrow_kind: BorrowKind,
+        borrow_fail: BorrowFail,
+        is_borrowed_mutably: F,
+    ) -> Self
+    where
+        F: FnOnce() -> bool,
+    {
+        let kind = match borrow_fail {
+            BorrowFail::ValueNotFound => ResourceErrorKind::NotFound,
+            BorrowFail::BorrowConflictImm => ResourceErrorKind::ConflictMutable,
+            BorrowFail::BorrowConflictMut => {
+                if is_borrowed_mutably() {
+                    ResourceErrorKind::ConflictMutable
+                } else {
+                    ResourceErrorKind::ConflictShared
+                }
+            }
+        };
+
+        Self::new(type_name, Some(borrow_kind), kind)
+    }
+
+    /// Returns the full type name, e.g. `std::string::String`.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns the type name without its module path, e.g. `String`.
+    ///
+    /// Generic parameters keep their full type names, e.g.
+    /// `Option<std::string::String>`.
+    pub fn type_name_short(&self) -> &'static str {
+        similar_names::short_name(self.type_name)
+    }
+
+    /// Returns how the resource was being borrowed, or `None` if it was being
+    /// removed.
+    pub fn borrow_kind(&self) -> Option<BorrowKind> {
+        self.borrow_kind
+    }
+
+    /// Returns the reason that the resource could not be accessed.
+    pub fn kind(&self) -> ResourceErrorKind {
+        self.kind
+    }
+
+    /// Returns the full type names of stored resources that are similar to
+    /// the requested type, most similar first.
+    ///
+    /// This is filled in for `NotFound` errors from `Resources`, and holds the
+    /// type of the stored value for `TypeMismatch` errors.
+    pub fn suggestions(&self) -> &[&'static str] {
+        &self.suggestions
+    }
+
+    /// Returns the name of the function that denied access, for
+    /// `AccessDenied` errors from `FnRes::call_strict` and
+    /// `FnRes::try_call_strict`.
+    pub fn fn_name(&self) -> Option<&'static str> {
+        self.fn_name
+    }
+
+    /// Returns the equivalent `BorrowFail`, or `None` for errors that
+    /// `BorrowFail` has no variant for, such as `AccessDenied`.
+    pub fn borrow_fail(&self) -> Option<BorrowFail> {
+        match self.kind {
+            ResourceErrorKind::NotFound => Some(BorrowFail::ValueNotFound),
+            ResourceErrorKind::ConflictShared => Some(BorrowFail::BorrowConflictMut),
+            ResourceErrorKind::ConflictMutable => match self.borrow_kind {
+                Some(BorrowKind::Mutable) => Some(BorrowFail::BorrowConflictMut),
+                Some(BorrowKind::Immutable) | None => Some(BorrowFail::BorrowConflictImm),
+            },
+            ResourceErrorKind::AccessDenied
+            | ResourceErrorKind::TypeMismatch
+            | ResourceErrorKind::DuplicateType
+            | ResourceErrorKind::HandleMismatch
+            | ResourceErrorKind::HandleRemoved => None,
+        }
+    }
+}
+
+impl fmt::Display for ResourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let type_name = self.type_name;
+        let borrow_kind = self.borrow_kind.unwrap_or(BorrowKind::Immutable);
+        match (self.kind, self.borrow_kind) {
+            (ResourceErrorKind::NotFound, Some(_)) => {
+                let did_you_mean = did_you_mean(&self.suggestions);
+                write!(
+                    f,
+                    "Expected to borrow `{type_name}`, but it does not exist.{did_you_mean}"
+                )
+            }
+            (ResourceErrorKind::NotFound, None) => {
+                let did_you_mean = did_you_mean(&self.suggestions);
+                write!(
+                    f,
+                    "Expected to fetch `{type_name}`, but it does not exist.{did_you_mean}"
+                )
+            }
+            (ResourceErrorKind::ConflictShared, _) => write!(
+                f,
+                "Expected to borrow `{type_name}` mutably, but it was already borrowed immutably."
+            ),
+            (ResourceErrorKind::ConflictMutable, _) => write!(
+                f,
+                "Expected to borrow `{type_name}` {borrow_kind}, but it was already borrowed mutably."
+            ),
+            (ResourceErrorKind::AccessDenied, _) => match self.fn_name {
+                Some(fn_name) => write!(
+                    f,
+                    "Expected `{fn_name}` to only borrow the resources in its parameters, but it borrowed `{type_name}` {borrow_kind}."
+                ),
+                None => write!(
+                    f,
+                    "Expected to borrow `{type_name}` {borrow_kind}, but access was denied by the `ResourcesView`."
+                ),
+            },
+            (ResourceErrorKind::TypeMismatch, Some(_)) => {
+                let did_you_mean = did_you_mean(&self.suggestions);
+                write!(
+                    f,
+                    "Expected to borrow `{type_name}` {borrow_kind}, but the value under the key is a different type.{did_you_mean}"
+                )
+            }
+            (ResourceErrorKind::TypeMismatch, None) => {
+                let did_you_mean = did_you_mean(&self.suggestions);
+                write!(
+                    f,
+                    "Expected to fetch `{type_name}`, but the value under the key is a different type.{did_you_mean}"
+                )
+            }
+            (ResourceErrorKind::DuplicateType, _) => write!(
+                f,
+                "Expected each resource type in the bundle to be distinct, but `{type_name}` appears more than once."
+            ),
+            (ResourceErrorKind::HandleMismatch, _) => write!(
+                f,
+                "Expected to borrow `{type_name}` through a handle, but the handle was created by a different `Resources` map."
+            ),
+            (ResourceErrorKind::HandleRemoved, _) => write!(
+                f,
+                "Expected to borrow `{type_name}` through a handle, but it was removed after the handle was created."
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ResourceError {}
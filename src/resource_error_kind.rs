@@ -0,0 +1,26 @@
+/// Reason that a resource could not be accessed.
+///
+/// Part of [`ResourceError`].
+///
+/// [`ResourceError`]: crate::ResourceError
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ResourceErrorKind {
+    /// The resource does not exist.
+    NotFound,
+    /// The resource could not be borrowed mutably, as it is already borrowed
+    /// immutably.
+    ConflictShared,
+    /// The resource could not be borrowed, as it is already borrowed mutably.
+    ConflictMutable,
+    /// A `ResourcesView` does not allow the resource to be borrowed this way.
+    AccessDenied,
+    /// The value under a `KeyedResources` key is of a different type.
+    TypeMismatch,
+    /// A `ResourceBundle` contains the same resource type more than once.
+    DuplicateType,
+    /// The `ResHandle` was created by a different `Resources` map.
+    HandleMismatch,
+    /// The resource was removed after the `ResHandle` was created.
+    HandleRemoved,
+}
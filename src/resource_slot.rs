@@ -0,0 +1,61 @@
+use alloc::boxed::Box;
+use core::{any::TypeId, fmt};
+
+use crate::{
+    resource_tracker::ResourceTracker, subscribers::Subscribers, BorrowFail, Cell, Resource,
+};
+
+/// Slot in `Resources` that holds the resource of one type.
+///
+/// A slot is kept when its resource is removed, so that its index stays
+/// valid, and `ResHandle`s can borrow the resource through the index instead
+/// of looking up its `TypeId`.
+pub(crate) struct ResourceSlot {
+    /// `TypeId` of the resource.
+    pub(crate) type_id: TypeId,
+    /// The resource, if it exists.
+    pub(crate) cell: Option<Cell<Box<dyn Resource>>>,
+    /// Outstanding borrow counts and stats of the resource.
+    pub(crate) tracker: ResourceTracker,
+    /// Callbacks to run when the resource is changed.
+    pub(crate) subscribers: Subscribers,
+    /// Incremented when the resource is removed, so that existing handles are
+    /// detected as stale.
+    pub(crate) generation: usize,
+}
+
+impl ResourceSlot {
+    /// Returns a new empty `ResourceSlot`.
+    pub(crate) fn new(type_id: TypeId, type_name: &'static str) -> Self {
+        Self {
+            type_id,
+            cell: None,
+            tracker: ResourceTracker::new(type_name),
+            subscribers: Subscribers::default(),
+            generation: 0,
+        }
+    }
+
+    /// Returns the resource's cell, or `ValueNotFound` if it doesn't exist.
+    pub(crate) fn cell(&self) -> Result<&Cell<Box<dyn Resource>>, BorrowFail> {
+        self.cell.as_ref().ok_or(BorrowFail::ValueNotFound)
+    }
+
+    /// Removes the resource, and discards its stats.
+    pub(crate) fn take(&mut self) -> Option<Box<dyn Resource>> {
+        let resource = self.cell.take().map(Cell::into_inner)?;
+        self.tracker = ResourceTracker::new(self.tracker.type_name());
+        self.generation += 1;
+        Some(resource)
+    }
+}
+
+impl fmt::Debug for ResourceSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResourceSlot")
+            .field("type_name", &self.tracker.type_name())
+            .field("occupied", &self.cell.is_some())
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
@@ -0,0 +1,72 @@
+use core::time::Duration;
+
+use crate::BorrowFail;
+
+/// Usage and contention counters for a resource.
+///
+/// Returned by [`Resources::stats`].
+///
+/// [`Resources::stats`]: crate::Resources::stats
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResourceStats {
+    /// Full type name of the resource.
+    pub type_name: &'static str,
+    /// Number of successful immutable borrows.
+    pub borrow_count: usize,
+    /// Number of successful mutable borrows.
+    pub borrow_mut_count: usize,
+    /// Number of borrows that failed because the resource did not exist.
+    pub value_not_found_count: usize,
+    /// Number of immutable borrows that failed because the resource was
+    /// borrowed mutably.
+    pub borrow_conflict_imm_count: usize,
+    /// Number of mutable borrows that failed because the resource was already
+    /// borrowed.
+    pub borrow_conflict_mut_count: usize,
+    /// Longest time a mutable borrow was held.
+    pub borrow_mut_held_max: Duration,
+}
+
+impl ResourceStats {
+    /// Returns a new `ResourceStats` with all counters at zero.
+    pub(crate) fn new(type_name: &'static str) -> Self {
+        Self {
+            type_name,
+            borrow_count: 0,
+            borrow_mut_count: 0,
+            value_not_found_count: 0,
+            borrow_conflict_imm_count: 0,
+            borrow_conflict_mut_count: 0,
+            borrow_mut_held_max: Duration::ZERO,
+        }
+    }
+
+    /// Returns the full type name of the resource.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns the number of successful immutable borrows.
+    pub fn borrow_count(&self) -> usize {
+        self.borrow_count
+    }
+
+    /// Returns the number of successful mutable borrows.
+    pub fn borrow_mut_count(&self) -> usize {
+        self.borrow_mut_count
+    }
+
+    /// Returns the number of borrows that failed with the given reason.
+    pub fn borrow_fail_count(&self, borrow_fail: BorrowFail) -> usize {
+        match borrow_fail {
+            BorrowFail::ValueNotFound => self.value_not_found_count,
+            BorrowFail::BorrowConflictImm => self.borrow_conflict_imm_count,
+            BorrowFail::BorrowConflictMut => self.borrow_conflict_mut_count,
+        }
+    }
+
+    /// Returns the longest time a mutable borrow was held.
+    pub fn borrow_mut_held_max(&self) -> Duration {
+        self.borrow_mut_held_max
+    }
+}
@@ -0,0 +1,155 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(feature = "stats")]
+use core::{sync::atomic::AtomicU64, time::Duration};
+
+#[cfg(feature = "stats")]
+use crate::{BorrowFail, BorrowKind, ResourceStats};
+
+/// Bookkeeping for a resource entry, updated by its `Ref`s and `RefMut`s.
+#[derive(Debug)]
+pub(crate) struct ResourceTracker {
+    /// Full type name of the resource.
+    type_name: &'static str,
+    /// Number of `Ref`s to the resource that have not been dropped.
+    ref_count: AtomicUsize,
+    /// Number of `RefMut`s to the resource that have not been dropped.
+    ref_mut_count: AtomicUsize,
+    /// Number of successful immutable borrows.
+    #[cfg(feature = "stats")]
+    borrow_count: AtomicUsize,
+    /// Number of successful mutable borrows.
+    #[cfg(feature = "stats")]
+    borrow_mut_count: AtomicUsize,
+    /// Number of immutable borrows that failed due to a mutable borrow.
+    #[cfg(feature = "stats")]
+    borrow_conflict_imm_count: AtomicUsize,
+    /// Number of mutable borrows that failed due to an existing borrow.
+    #[cfg(feature = "stats")]
+    borrow_conflict_mut_count: AtomicUsize,
+    /// Longest time a `RefMut` was held, in nanoseconds.
+    #[cfg(feature = "stats")]
+    borrow_mut_held_max_nanos: AtomicU64,
+}
+
+impl ResourceTracker {
+    /// Returns a new `ResourceTracker` with no outstanding borrows.
+    pub(crate) fn new(type_name: &'static str) -> Self {
+        Self {
+            type_name,
+            ref_count: AtomicUsize::new(0),
+            ref_mut_count: AtomicUsize::new(0),
+            #[cfg(feature = "stats")]
+            borrow_count: AtomicUsize::new(0),
+            #[cfg(feature = "stats")]
+            borrow_mut_count: AtomicUsize::new(0),
+            #[cfg(feature = "stats")]
+            borrow_conflict_imm_count: AtomicUsize::new(0),
+            #[cfg(feature = "stats")]
+            borrow_conflict_mut_count: AtomicUsize::new(0),
+            #[cfg(feature = "stats")]
+            borrow_mut_held_max_nanos: AtomicU64::new(0),
+        }
+    }
+
+    /// Resets the outstanding borrow counts, keeping the stats.
+    ///
+    /// Used when the resource is replaced, as borrows of the previous value
+    /// no longer count.
+    pub(crate) fn reset_borrow_counts(&mut self) {
+        *self.ref_count.get_mut() = 0;
+        *self.ref_mut_count.get_mut() = 0;
+    }
+
+    /// Returns the full type name of the resource.
+    pub(crate) fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Records that a `Ref` was created.
+    pub(crate) fn ref_acquired(&self) {
+        self.ref_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records that a `Ref` was dropped.
+    pub(crate) fn ref_released(&self) {
+        self.ref_count.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Records that a `RefMut` was created.
+    pub(crate) fn ref_mut_acquired(&self) {
+        self.ref_mut_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records that a `RefMut` was dropped.
+    pub(crate) fn ref_mut_released(&self) {
+        self.ref_mut_count.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Returns the number of `Ref`s that have not been dropped.
+    pub(crate) fn ref_count(&self) -> usize {
+        self.ref_count.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of `RefMut`s that have not been dropped.
+    pub(crate) fn ref_mut_count(&self) -> usize {
+        self.ref_mut_count.load(Ordering::Relaxed)
+    }
+
+    /// Records a successful borrow.
+    #[cfg(feature = "stats")]
+    pub(crate) fn borrow_succeeded(&self, borrow_kind: BorrowKind) {
+        let count = match borrow_kind {
+            BorrowKind::Immutable => &self.borrow_count,
+            BorrowKind::Mutable => &self.borrow_mut_count,
+        };
+        count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a failed borrow.
+    ///
+    /// `ValueNotFound` failures are not recorded here, as they are tracked by
+    /// `Resources` for resources that may not have a tracker.
+    #[cfg(feature = "stats")]
+    pub(crate) fn borrow_failed(&self, borrow_fail: BorrowFail) {
+        let count = match borrow_fail {
+            BorrowFail::ValueNotFound => return,
+            BorrowFail::BorrowConflictImm => &self.borrow_conflict_imm_count,
+            BorrowFail::BorrowConflictMut => &self.borrow_conflict_mut_count,
+        };
+        count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records how long a `RefMut` was held.
+    #[cfg(feature = "stats")]
+    pub(crate) fn ref_mut_held(&self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        self.borrow_mut_held_max_nanos
+            .fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    /// Returns a snapshot of the stats.
+    #[cfg(feature = "stats")]
+    pub(crate) fn stats(&self) -> ResourceStats {
+        ResourceStats {
+            borrow_count: self.borrow_count.load(Ordering::Relaxed),
+            borrow_mut_count: self.borrow_mut_count.load(Ordering::Relaxed),
+            borrow_conflict_imm_count: self.borrow_conflict_imm_count.load(Ordering::Relaxed),
+            borrow_conflict_mut_count: self.borrow_conflict_mut_count.load(Ordering::Relaxed),
+            borrow_mut_held_max: Duration::from_nanos(
+                self.borrow_mut_held_max_nanos.load(Ordering::Relaxed),
+            ),
+            ..ResourceStats::new(self.type_name)
+        }
+    }
+
+    /// Resets the stats to zero.
+    #[cfg(feature = "stats")]
+    pub(crate) fn reset_stats(&self) {
+        self.borrow_count.store(0, Ordering::Relaxed);
+        self.borrow_mut_count.store(0, Ordering::Relaxed);
+        self.borrow_conflict_imm_count.store(0, Ordering::Relaxed);
+        self.borrow_conflict_mut_count.store(0, Ordering::Relaxed);
+        self.borrow_mut_held_max_nanos.store(0, Ordering::Relaxed);
+    }
+}
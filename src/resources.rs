@@ -1,16 +1,93 @@
-use std::{
-    any::TypeId,
-    fmt,
-    ops::{Deref, DerefMut},
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
 };
+use core::{any::TypeId, fmt};
+
+#[cfg(any(feature = "stats", feature = "testing"))]
+use std::sync::{Mutex, PoisonError};
+
+#[cfg(feature = "std")]
+use alloc::sync::Arc;
+
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::{
+    lend_guard::LendGuard,
+    resource::TypeNameLit,
+    resource_debug::{self, DebugFn, ResourceDebug},
+    resource_slot::ResourceSlot,
+    resource_tracker::ResourceTracker,
+    resources_id::ResourcesId,
+    similar_names::{did_you_mean, similar_names},
+    BorrowFail, BorrowKind, Cell, Entry, OccupiedEntry, OutstandingBorrow, Ref, RefMut, ResHandle,
+    Resource, ResourceBundle, ResourceByNameError, ResourceError, ResourceErrorKind, ResourcesView,
+    ResourcesViewBuilder, Subscription, Transaction, TypeRegistry, VacantEntry,
+};
+
+#[cfg(feature = "stats")]
+use crate::ResourceStats;
+
+#[cfg(feature = "std")]
+use crate::{watch_state::WatchState, Watcher};
+
+#[cfg(feature = "testing")]
+use crate::ResourcesBuilder;
 
-use rt_map::{BorrowFail, Cell, RtMap};
+#[cfg(feature = "testing")]
+use alloc::collections::BTreeSet;
 
-use crate::{Entry, Ref, RefMut, Resource, ResourceFetchError};
+#[cfg(all(feature = "fn_res", feature = "fn_meta", feature = "std"))]
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(all(feature = "fn_res", feature = "fn_meta", feature = "std"))]
+use crate::borrow_restriction::BorrowRestriction;
+
+/// Index of each resource type's slot.
+///
+/// Without `std`, there is no `HashMap`, so a `BTreeMap` is used instead.
+#[cfg(feature = "std")]
+type SlotIndices = HashMap<TypeId, usize>;
+#[cfg(not(feature = "std"))]
+type SlotIndices = BTreeMap<TypeId, usize>;
 
 /// Map from `TypeId` to type.
 #[derive(Default)]
-pub struct Resources(RtMap<TypeId, Box<dyn Resource>>);
+pub struct Resources {
+    /// Slot for each resource type, which holds the resource if it exists.
+    slots: Vec<ResourceSlot>,
+    /// Index of each resource type's slot in `slots`.
+    slot_indices: SlotIndices,
+    /// Functions to format resources whose types are known to be `Debug`.
+    debug_fns: BTreeMap<TypeId, DebugFn>,
+    /// Whether to panic on drop if any resource is still borrowed.
+    check_borrows_on_drop: bool,
+    /// Unique ID of this map, used to check `ResHandle`s.
+    id: ResourcesId,
+    /// Resources in the order they were first inserted.
+    ///
+    /// Each resource in the map is listed exactly once, so this also counts
+    /// the resources.
+    insertion_order: Vec<TypeId>,
+    /// Resources to drop first when this map is dropped, in order.
+    drop_order: Vec<TypeId>,
+    /// Type names and counts of borrows that failed with `ValueNotFound`.
+    ///
+    /// These are kept separately from the slots' trackers, as resources that
+    /// were never inserted do not have a slot.
+    #[cfg(feature = "stats")]
+    value_not_found_counts: Mutex<BTreeMap<TypeId, (&'static str, usize)>>,
+    /// Number of `FnRes::call_strict` calls running on this map.
+    ///
+    /// Borrows only check the thread's `BorrowRestriction`s while this is not
+    /// zero.
+    #[cfg(all(feature = "fn_res", feature = "fn_meta", feature = "std"))]
+    strict_calls: AtomicUsize,
+}
 
 /// A [Resource] container, which provides methods to insert, access and manage
 /// the contained resources.
@@ -51,12 +128,75 @@ impl Resources {
     /// let resources: Resources = Resources::with_capacity(10);
     /// ```
     pub fn with_capacity(capacity: usize) -> Self {
-        Self(RtMap::with_capacity(capacity))
+        Self {
+            slots: Vec::with_capacity(capacity),
+            slot_indices: SlotIndices::default(),
+            debug_fns: BTreeMap::new(),
+            check_borrows_on_drop: false,
+            id: ResourcesId::default(),
+            insertion_order: Vec::new(),
+            drop_order: Vec::new(),
+            #[cfg(feature = "stats")]
+            value_not_found_counts: Mutex::default(),
+            #[cfg(all(feature = "fn_res", feature = "fn_meta", feature = "std"))]
+            strict_calls: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns a [`ResourcesBuilder`] to build a `Resources` map.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use resman::Resources;
+    ///
+    /// let resources = Resources::builder()
+    ///     .with(1u32)
+    ///     .with_default::<u64>()
+    ///     .build();
+    ///
+    /// assert_eq!(1, *resources.borrow::<u32>());
+    /// assert_eq!(0, *resources.borrow::<u64>());
+    /// ```
+    #[cfg(feature = "testing")]
+    pub fn builder() -> ResourcesBuilder {
+        ResourcesBuilder::new()
+    }
+
+    /// Returns the resources in an [`RtMap`].
+    ///
+    /// [`RtMap`]: rt_map::RtMap
+    #[cfg(feature = "std")]
+    pub fn into_inner(self) -> rt_map::RtMap<TypeId, Box<dyn Resource>> {
+        let mut rt_map = rt_map::RtMap::with_capacity(self.len());
+        self.into_resources().for_each(|(type_id, resource)| {
+            rt_map.insert(type_id, resource);
+        });
+        rt_map
+    }
+
+    /// Returns the resources with their `TypeId`s, in insertion order.
+    pub(crate) fn into_resources(mut self) -> impl Iterator<Item = (TypeId, Box<dyn Resource>)> {
+        let mut slots = core::mem::take(&mut self.slots);
+        let slot_indices = core::mem::take(&mut self.slot_indices);
+        core::mem::take(&mut self.insertion_order)
+            .into_iter()
+            .filter_map(move |type_id| {
+                let slot = &mut slots[slot_indices[&type_id]];
+                slot.take().map(|resource| (type_id, resource))
+            })
     }
 
-    /// Returns the inner [`RtMap`].
-    pub fn into_inner(self) -> RtMap<TypeId, Box<dyn Resource>> {
-        self.0
+    /// Returns the unique ID of this map.
+    #[cfg(all(feature = "fn_res", feature = "fn_meta", feature = "std"))]
+    pub(crate) fn id(&self) -> ResourcesId {
+        self.id
+    }
+
+    /// Returns the number of `FnRes::call_strict` calls running on this map.
+    #[cfg(all(feature = "fn_res", feature = "fn_meta", feature = "std"))]
+    pub(crate) fn strict_calls(&self) -> &AtomicUsize {
+        &self.strict_calls
     }
 
     /// Returns the number of elements the map can hold without reallocating.
@@ -72,19 +212,33 @@ impl Resources {
     /// assert!(resources.capacity() >= 100);
     /// ```
     pub fn capacity(&self) -> usize {
-        self.0.capacity()
+        self.slots.capacity()
+    }
+
+    /// Returns the number of resources in the map.
+    pub fn len(&self) -> usize {
+        self.insertion_order.len()
+    }
+
+    /// Returns true if the map contains no resources.
+    pub fn is_empty(&self) -> bool {
+        self.insertion_order.is_empty()
     }
 
     /// Returns an entry for the resource with type `R`.
-    pub fn entry<R>(&mut self) -> Entry<R>
+    pub fn entry<R>(&mut self) -> Entry<'_, R>
     where
         R: Resource,
     {
-        Entry::new(self.0.entry(TypeId::of::<R>()))
+        if self.contains::<R>() {
+            Entry::Occupied(OccupiedEntry::new(self))
+        } else {
+            Entry::Vacant(VacantEntry::new(self))
+        }
     }
 
     /// Inserts a resource into the map. If the resource existed before,
-    /// it will be overwritten.
+    /// it will be overwritten, and the previous value is returned.
     ///
     /// # Examples
     ///
@@ -104,22 +258,123 @@ impl Resources {
     /// use resman::Resources;
     ///
     /// let mut resources = Resources::default();
-    /// resources.insert(MyRes(5));
+    /// assert!(resources.insert(MyRes(5)).is_none());
+    /// assert_eq!(Some(5), resources.insert(MyRes(6)).map(|my_res| my_res.0));
     /// ```
-    pub fn insert<R>(&mut self, r: R)
+    pub fn insert<R>(&mut self, r: R) -> Option<R>
     where
         R: Resource,
     {
-        self.0.insert(TypeId::of::<R>(), Box::new(r));
+        self.insert_tracked(TypeId::of::<R>(), Box::new(r))
+            .map(|previous| Self::downcast_boxed::<R>(previous))
+    }
+
+    /// Inserts a resource into the map, and includes its value in the `Debug`
+    /// output of `Resources`. If the resource existed before, it will be
+    /// overwritten, and the previous value is returned.
+    ///
+    /// Resources inserted with [`insert`] are printed as `".."`, as `Resources`
+    /// is unable to determine at runtime if they implement `Debug`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use resman::Resources;
+    ///
+    /// #[derive(Debug)]
+    /// struct MyRes(i32);
+    /// struct Opaque;
+    ///
+    /// let mut resources = Resources::default();
+    /// resources.insert_debug(MyRes(5));
+    /// resources.insert(Opaque);
+    ///
+    /// let resources_dbg = format!("{resources:?}");
+    /// assert!(resources_dbg.contains("MyRes: MyRes(5)"));
+    /// assert!(resources_dbg.contains(r#"Opaque: "..""#));
+    /// ```
+    ///
+    /// [`insert`]: Self::insert
+    pub fn insert_debug<R>(&mut self, r: R) -> Option<R>
+    where
+        R: Resource + fmt::Debug,
+    {
+        self.register_debug::<R>();
+        self.insert(r)
+    }
+
+    /// Includes the value of `R` in the `Debug` output of `Resources`.
+    ///
+    /// This is useful for resources that are inserted through other means,
+    /// such as [`entry`] or [`insert_by_name`].
+    ///
+    /// [`entry`]: Self::entry
+    /// [`insert_by_name`]: Self::insert_by_name
+    pub fn register_debug<R>(&mut self)
+    where
+        R: Resource + fmt::Debug,
+    {
+        self.debug_fns
+            .insert(TypeId::of::<R>(), resource_debug::debug_fn::<R>());
     }
 
-    /// Inserts an already boxed resource into the map.
-    pub fn insert_raw(&mut self, type_id: TypeId, resource: Box<dyn Resource>) {
+    /// Inserts an already boxed resource into the map, and returns the
+    /// previous value if any.
+    pub fn insert_raw(
+        &mut self,
+        type_id: TypeId,
+        resource: Box<dyn Resource>,
+    ) -> Option<Box<dyn Resource>> {
         if type_id != Resource::type_id(&*resource) {
             let type_name = Resource::type_name(&*resource);
             panic!("`Resources::insert_raw` type_id does not match `{type_name:?}.type_id()`.");
         }
-        self.0.insert(type_id, resource);
+        self.insert_tracked(type_id, resource)
+    }
+
+    /// Inserts the resource, and notifies its watchers and subscribers.
+    fn insert_tracked(
+        &mut self,
+        type_id: TypeId,
+        resource: Box<dyn Resource>,
+    ) -> Option<Box<dyn Resource>> {
+        let resource_previous = self.insert_tracked_unnotified(type_id, resource);
+        self.notify_changed(type_id);
+
+        resource_previous
+    }
+
+    /// Inserts a resource without notifying its watchers or subscribers, and
+    /// returns the previous value if any.
+    ///
+    /// Used to lend a value temporarily, where the map is restored afterwards.
+    pub(crate) fn insert_unnotified<R>(&mut self, r: R) -> Option<R>
+    where
+        R: Resource,
+    {
+        self.insert_tracked_unnotified(TypeId::of::<R>(), Box::new(r))
+            .map(|previous| Self::downcast_boxed::<R>(previous))
+    }
+
+    /// Inserts the resource, and resets its outstanding borrow counts.
+    ///
+    /// Any previous borrows are of the replaced value, so they no longer
+    /// count.
+    fn insert_tracked_unnotified(
+        &mut self,
+        type_id: TypeId,
+        resource: Box<dyn Resource>,
+    ) -> Option<Box<dyn Resource>> {
+        let type_name = Resource::type_name(&*resource).as_str();
+        let index = self.slot_index_or_insert(type_id, type_name);
+        let slot = &mut self.slots[index];
+        slot.tracker.reset_borrow_counts();
+        let resource_previous = slot.cell.replace(Cell::new(resource)).map(Cell::into_inner);
+        if resource_previous.is_none() {
+            self.insertion_order.push(type_id);
+        }
+
+        resource_previous
     }
 
     /// Removes a resource of type `R` from this container and returns its
@@ -149,16 +404,199 @@ impl Resources {
     /// this resource still exists. Thus, only use this if you're sure no
     /// system will try to access this resource after you removed it (or else
     /// you will get a panic).
-    pub fn try_remove<R>(&mut self) -> Result<R, ResourceFetchError>
+    pub fn try_remove<R>(&mut self) -> Result<R, ResourceError>
+    where
+        R: Resource,
+    {
+        let type_id = TypeId::of::<R>();
+        self.insertion_order
+            .retain(|type_id_inserted| *type_id_inserted != type_id);
+        self.slot_mut(type_id)
+            .and_then(ResourceSlot::take)
+            .map(Self::downcast_boxed::<R>)
+            .ok_or_else(|| self.not_found_error::<R>())
+    }
+
+    /// Replaces the `R` resource with `r`, and returns the previous value.
+    ///
+    /// Unlike [`insert`], this only needs `&self`, so it can be used while
+    /// other resources are borrowed.
+    ///
+    /// See [`try_replace`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource doesn't exist.
+    /// Panics if the resource is already accessed.
+    ///
+    /// [`insert`]: Self::insert
+    /// [`try_replace`]: Self::try_replace
+    pub fn replace<R>(&self, r: R) -> R
+    where
+        R: Resource,
+    {
+        core::mem::replace(&mut *self.borrow_mut::<R>(), r)
+    }
+
+    /// Replaces the `R` resource with `r`, and returns the previous value.
+    ///
+    /// Returns an error if the resource doesn't exist, or is already accessed.
+    pub fn try_replace<R>(&self, r: R) -> Result<R, ResourceError>
+    where
+        R: Resource,
+    {
+        self.try_borrow_mut::<R>()
+            .map(|mut r_current| core::mem::replace(&mut *r_current, r))
+    }
+
+    /// Takes the `R` resource, leaving `R::default()` in its place.
+    ///
+    /// See [`try_take`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource doesn't exist.
+    /// Panics if the resource is already accessed.
+    ///
+    /// [`try_take`]: Self::try_take
+    pub fn take<R>(&self) -> R
+    where
+        R: Resource + Default,
+    {
+        core::mem::take(&mut *self.borrow_mut::<R>())
+    }
+
+    /// Takes the `R` resource, leaving `R::default()` in its place.
+    ///
+    /// Returns an error if the resource doesn't exist, or is already accessed.
+    pub fn try_take<R>(&self) -> Result<R, ResourceError>
+    where
+        R: Resource + Default,
+    {
+        self.try_borrow_mut::<R>()
+            .map(|mut r_current| core::mem::take(&mut *r_current))
+    }
+
+    /// Swaps the `R` resource in `self` with the one in `other`.
+    ///
+    /// If only one of the maps contains `R`, it is moved to the other map.
+    /// If neither contains `R`, this does nothing.
+    pub fn swap<R>(&mut self, other: &mut Resources)
+    where
+        R: Resource,
+    {
+        match (self.get_mut::<R>(), other.get_mut::<R>()) {
+            (Some(r_self), Some(r_other)) => {
+                core::mem::swap(r_self, r_other);
+
+                self.notify_changed(TypeId::of::<R>());
+                other.notify_changed(TypeId::of::<R>());
+            }
+            (Some(_), None) => {
+                other.insert(self.remove::<R>());
+            }
+            (None, Some(_)) => {
+                self.insert(other.remove::<R>());
+            }
+            (None, None) => {}
+        }
+    }
+
+    /// Downcasts a boxed resource that is known to be an `R`.
+    fn downcast_boxed<R>(resource: Box<dyn Resource>) -> R
     where
         R: Resource,
     {
-        self.0
-            .remove(&TypeId::of::<R>())
-            .map(|x: Box<dyn Resource>| x.downcast())
-            .map(|x: Result<Box<R>, _>| x.ok().unwrap())
-            .map(|x| *x)
-            .ok_or_else(ResourceFetchError::new::<R>)
+        let resource: Box<R> = resource
+            .downcast()
+            .ok()
+            .expect("Expected resource to be stored under its own `TypeId`.");
+        *resource
+    }
+
+    /// Inserts each resource in the bundle.
+    ///
+    /// Existing resources of the same types are overwritten.
+    ///
+    /// See [`try_insert_bundle`] for a non-panicking version of this
+    /// function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bundle contains the same type more than once, in which
+    /// case none are inserted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use resman::Resources;
+    ///
+    /// let mut resources = Resources::new();
+    /// resources.insert_bundle((1u32, 2u64, 3i8));
+    ///
+    /// assert!(resources.contains_all::<(u32, u64, i8)>());
+    /// assert_eq!((1, 2, 3), resources.remove_bundle::<(u32, u64, i8)>());
+    /// assert!(!resources.contains::<u32>());
+    /// ```
+    ///
+    /// [`try_insert_bundle`]: Self::try_insert_bundle
+    pub fn insert_bundle<B>(&mut self, bundle: B)
+    where
+        B: ResourceBundle,
+    {
+        self.try_insert_bundle(bundle)
+            .unwrap_or_else(|error| panic!("{error}"));
+    }
+
+    /// Inserts each resource in the bundle.
+    ///
+    /// Existing resources of the same types are overwritten. If the bundle
+    /// contains the same type more than once, none are inserted, and a
+    /// `DuplicateType` error is returned.
+    pub fn try_insert_bundle<B>(&mut self, bundle: B) -> Result<(), ResourceError>
+    where
+        B: ResourceBundle,
+    {
+        bundle.insert_into(self)
+    }
+
+    /// Removes every resource in the bundle, and returns them.
+    ///
+    /// See [`try_remove_bundle`] for a non-panicking version of this
+    /// function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any resource in the bundle doesn't exist, or the bundle
+    /// contains the same type more than once, in which case none are removed.
+    ///
+    /// [`try_remove_bundle`]: Self::try_remove_bundle
+    pub fn remove_bundle<B>(&mut self) -> B
+    where
+        B: ResourceBundle,
+    {
+        self.try_remove_bundle::<B>().unwrap()
+    }
+
+    /// Removes every resource in the bundle, and returns them.
+    ///
+    /// If any resource in the bundle doesn't exist, none are removed, and an
+    /// error is returned for the first missing resource. If the bundle
+    /// contains the same type more than once, a `DuplicateType` error is
+    /// returned.
+    pub fn try_remove_bundle<B>(&mut self) -> Result<B, ResourceError>
+    where
+        B: ResourceBundle,
+    {
+        B::remove_from(self)
+    }
+
+    /// Returns true if every resource type in the bundle exists in `self`.
+    pub fn contains_all<B>(&self) -> bool
+    where
+        B: ResourceBundle,
+    {
+        B::contained_in(self)
     }
 
     /// Returns true if the specified resource type `R` exists in `self`.
@@ -166,7 +604,12 @@ impl Resources {
     where
         R: Resource,
     {
-        self.0.contains_key(&TypeId::of::<R>())
+        self.contains_type_id(TypeId::of::<R>())
+    }
+
+    /// Returns true if the resource with the given `TypeId` exists in `self`.
+    fn contains_type_id(&self, type_id: TypeId) -> bool {
+        self.slot(type_id).is_some_and(|slot| slot.cell.is_some())
     }
 
     /// Returns the `R` resource in the resource map.
@@ -179,20 +622,55 @@ impl Resources {
     /// Panics if the resource is being accessed mutably.
     ///
     /// [`try_borrow`]: Self::try_borrow
-    pub fn borrow<R>(&self) -> Ref<R>
+    pub fn borrow<R>(&self) -> Ref<'_, R>
     where
         R: Resource,
     {
-        self.try_borrow::<R>()
-            .unwrap_or_else(Self::borrow_panic::<R, _>)
+        self.try_borrow::<R>().unwrap_or_else(Self::borrow_panic)
     }
 
     /// Returns an immutable reference to `R` if it exists, `None` otherwise.
-    pub fn try_borrow<R>(&self) -> Result<Ref<R>, BorrowFail>
+    pub fn try_borrow<R>(&self) -> Result<Ref<'_, R>, ResourceError>
     where
         R: Resource,
     {
-        self.0.try_borrow(&TypeId::of::<R>()).map(Ref::new)
+        let type_id = TypeId::of::<R>();
+        self.try_borrow_slot(self.slot(type_id), type_id, core::any::type_name::<R>())
+    }
+
+    /// Returns an immutable reference to the resource in the slot.
+    ///
+    /// This is used by typed, by-name, and `ResHandle` borrows, so that they
+    /// check and record borrows in the same way.
+    fn try_borrow_slot<'r, R>(
+        &'r self,
+        slot: Option<&'r ResourceSlot>,
+        type_id: TypeId,
+        type_name: &'static str,
+    ) -> Result<Ref<'r, R>, ResourceError>
+    where
+        R: ?Sized,
+    {
+        debug_assert!(slot.is_none_or(|slot| slot.type_id == type_id));
+        #[cfg(all(feature = "fn_res", feature = "fn_meta", feature = "std"))]
+        self.restriction_check(type_id, type_name, BorrowKind::Immutable)?;
+
+        let result = slot.ok_or(BorrowFail::ValueNotFound).and_then(|slot| {
+            let cell = slot.cell()?;
+            cell.try_borrow()
+                .map(|cell_ref| Ref::new_tracked(cell_ref, Some(&slot.tracker)))
+        });
+
+        #[cfg(feature = "stats")]
+        self.record_borrow_stats(slot, type_id, type_name, BorrowKind::Immutable, &result);
+        #[cfg(feature = "tracing")]
+        Self::trace_borrow(type_name, BorrowKind::Immutable, &result);
+
+        result.map_err(|borrow_fail| {
+            Self::borrow_error(slot, type_name, BorrowKind::Immutable, borrow_fail, || {
+                self.similar_type_names(type_name)
+            })
+        })
     }
 
     /// Returns a mutable reference to `R` if it exists, `None` otherwise.
@@ -201,20 +679,116 @@ impl Resources {
     ///
     /// Panics if the resource doesn't exist.
     /// Panics if the resource is already accessed.
-    pub fn borrow_mut<R>(&self) -> RefMut<R>
+    pub fn borrow_mut<R>(&self) -> RefMut<'_, R>
     where
         R: Resource,
     {
         self.try_borrow_mut::<R>()
-            .unwrap_or_else(Self::borrow_panic::<R, _>)
+            .unwrap_or_else(Self::borrow_panic)
     }
 
     /// Returns a mutable reference to `R` if it exists, `None` otherwise.
-    pub fn try_borrow_mut<R>(&self) -> Result<RefMut<R>, BorrowFail>
+    pub fn try_borrow_mut<R>(&self) -> Result<RefMut<'_, R>, ResourceError>
     where
         R: Resource,
     {
-        self.0.try_borrow_mut(&TypeId::of::<R>()).map(RefMut::new)
+        let type_id = TypeId::of::<R>();
+        self.try_borrow_mut_slot(self.slot(type_id), type_id, core::any::type_name::<R>())
+    }
+
+    /// Returns a mutable reference to the resource in the slot.
+    ///
+    /// See [`try_borrow_slot`] for details.
+    ///
+    /// [`try_borrow_slot`]: Self::try_borrow_slot
+    fn try_borrow_mut_slot<'r, R>(
+        &'r self,
+        slot: Option<&'r ResourceSlot>,
+        type_id: TypeId,
+        type_name: &'static str,
+    ) -> Result<RefMut<'r, R>, ResourceError>
+    where
+        R: ?Sized,
+    {
+        debug_assert!(slot.is_none_or(|slot| slot.type_id == type_id));
+        #[cfg(all(feature = "fn_res", feature = "fn_meta", feature = "std"))]
+        self.restriction_check(type_id, type_name, BorrowKind::Mutable)?;
+
+        let result = slot.ok_or(BorrowFail::ValueNotFound).and_then(|slot| {
+            let cell = slot.cell()?;
+            cell.try_borrow_mut().map(|cell_ref_mut| {
+                let ref_mut = RefMut::<R>::new_tracked(cell_ref_mut, Some(&slot.tracker));
+                if slot.subscribers.is_empty() {
+                    ref_mut
+                } else {
+                    ref_mut.with_subscribers(&slot.subscribers, cell)
+                }
+            })
+        });
+
+        #[cfg(feature = "stats")]
+        self.record_borrow_stats(slot, type_id, type_name, BorrowKind::Mutable, &result);
+        #[cfg(feature = "tracing")]
+        Self::trace_borrow(type_name, BorrowKind::Mutable, &result);
+
+        result.map_err(|borrow_fail| {
+            Self::borrow_error(slot, type_name, BorrowKind::Mutable, borrow_fail, || {
+                self.similar_type_names(type_name)
+            })
+        })
+    }
+
+    /// Returns an `AccessDenied` error if a function running in strict mode
+    /// on this map does not allow the resource to be borrowed.
+    #[cfg(all(feature = "fn_res", feature = "fn_meta", feature = "std"))]
+    fn restriction_check(
+        &self,
+        type_id: TypeId,
+        type_name: &'static str,
+        borrow_kind: BorrowKind,
+    ) -> Result<(), ResourceError> {
+        if self.strict_calls.load(Ordering::Relaxed) == 0 {
+            Ok(())
+        } else {
+            BorrowRestriction::check(self.id, type_id, type_name, borrow_kind)
+        }
+    }
+
+    /// Returns whether the resource may be read while a function is running in
+    /// strict mode, without recording a denial.
+    #[cfg(all(feature = "fn_res", feature = "fn_meta", feature = "std"))]
+    fn restriction_allows(&self, type_id: TypeId) -> bool {
+        self.strict_calls.load(Ordering::Relaxed) == 0
+            || BorrowRestriction::is_allowed(self.id, type_id, BorrowKind::Immutable)
+    }
+
+    /// Returns the slot for the resource type, if it has one.
+    fn slot(&self, type_id: TypeId) -> Option<&ResourceSlot> {
+        self.slot_indices
+            .get(&type_id)
+            .map(|index| &self.slots[*index])
+    }
+
+    /// Returns the slot for the resource type mutably, if it has one.
+    fn slot_mut(&mut self, type_id: TypeId) -> Option<&mut ResourceSlot> {
+        self.slot_indices
+            .get(&type_id)
+            .map(|index| &mut self.slots[*index])
+    }
+
+    /// Returns the index of the slot for the resource type, adding an empty
+    /// slot if it has none.
+    fn slot_index_or_insert(&mut self, type_id: TypeId, type_name: &'static str) -> usize {
+        let slots = &mut self.slots;
+        *self.slot_indices.entry(type_id).or_insert_with(|| {
+            slots.push(ResourceSlot::new(type_id, type_name));
+            slots.len() - 1
+        })
+    }
+
+    /// Returns the slots that hold a resource.
+    fn slots_occupied(&self) -> impl Iterator<Item = &ResourceSlot> {
+        self.slots.iter().filter(|slot| slot.cell.is_some())
     }
 
     /// Retrieves a resource without fetching, which is cheaper, but only
@@ -227,359 +801,2134 @@ impl Resources {
     /// Retrieves a resource without fetching, which is cheaper, but only
     /// available with `&mut self`.
     pub fn get_resource_mut(&mut self, id: TypeId) -> Option<&mut dyn Resource> {
-        self.0.get_resource_mut(&id).map(|resource| &mut **resource)
+        self.slot_mut(id)
+            .and_then(|slot| slot.cell.as_mut())
+            .map(|cell| &mut **cell.get_mut())
     }
 
     /// Get raw access to the underlying cell.
+    ///
+    /// While a function is running in strict mode, this returns `None` unless
+    /// the function borrows the resource mutably.
     pub fn get_raw(&self, id: &TypeId) -> Option<&Cell<Box<dyn Resource>>> {
-        self.0.get_raw(id)
-    }
-
-    fn borrow_panic<R, Ret>(borrow_fail: BorrowFail) -> Ret {
-        let type_name = std::any::type_name::<R>();
-        match borrow_fail {
-            BorrowFail::ValueNotFound => {
-                panic!("Expected to borrow `{type_name}`, but it does not exist.")
-            }
-            BorrowFail::BorrowConflictImm => panic!(
-                "Expected to borrow `{type_name}` immutably, but it was already borrowed mutably."
-            ),
-            BorrowFail::BorrowConflictMut => panic!(
-                "Expected to borrow `{type_name}` mutably, but it was already borrowed mutably."
-            ),
+        let slot = self.slot(*id)?;
+        #[cfg(all(feature = "fn_res", feature = "fn_meta", feature = "std"))]
+        if self
+            .restriction_check(*id, slot.tracker.type_name(), BorrowKind::Mutable)
+            .is_err()
+        {
+            return None;
         }
+        slot.cell.as_ref()
     }
 
-    /// Merges the other `Resources` map over this one.
-    pub fn merge(&mut self, other: Resources) {
-        self.0.extend(other.into_inner().into_inner())
+    /// Returns a handle to the `R` resource, for repeated access.
+    ///
+    /// See [`ResHandle`] for details. Each resource type is stored in its own
+    /// slot, and the handle borrows the resource from the slot by index,
+    /// without looking up `TypeId::of::<R>()`.
+    ///
+    /// See [`try_handle`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource doesn't exist.
+    ///
+    /// [`try_handle`]: Self::try_handle
+    pub fn handle<R>(&self) -> ResHandle<R>
+    where
+        R: Resource,
+    {
+        self.try_handle::<R>().unwrap_or_else(|error| {
+            let type_name = error.type_name();
+            let did_you_mean = did_you_mean(error.suggestions());
+            panic!(
+                "Expected to create a handle to `{type_name}`, but it does not exist.{did_you_mean}"
+            )
+        })
     }
-}
 
-#[cfg(not(feature = "debug"))]
-impl fmt::Debug for Resources {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut debug_map = f.debug_map();
+    /// Returns a handle to the `R` resource, for repeated access.
+    ///
+    /// Returns an error if the resource doesn't exist.
+    pub fn try_handle<R>(&self) -> Result<ResHandle<R>, ResourceError>
+    where
+        R: Resource,
+    {
+        match self.slot_indices.get(&TypeId::of::<R>()) {
+            Some(index) if self.slots[*index].cell.is_some() => Ok(ResHandle::new(
+                self.id,
+                *index,
+                self.slots[*index].generation,
+            )),
+            _ => Err(self.not_found_error::<R>()),
+        }
+    }
 
-        self.0.keys().for_each(|type_id| {
-            let resource = &*self.0.borrow(type_id);
-            let type_name = resource.as_ref().type_name();
+    /// Returns the resource that the handle refers to.
+    pub(crate) fn borrow_handle<R>(&self, handle: &ResHandle<R>) -> Ref<'_, R>
+    where
+        R: Resource,
+    {
+        self.try_borrow_handle(handle)
+            .unwrap_or_else(Self::borrow_panic)
+    }
 
-            // At runtime, we are unable to determine if the resource is `Debug`.
-            debug_map.entry(&type_name, &"..");
-        });
+    /// Returns the resource that the handle refers to.
+    pub(crate) fn try_borrow_handle<R>(
+        &self,
+        handle: &ResHandle<R>,
+    ) -> Result<Ref<'_, R>, ResourceError>
+    where
+        R: Resource,
+    {
+        let slot = self.handle_slot(handle, BorrowKind::Immutable)?;
+        self.try_borrow_slot(Some(slot), slot.type_id, core::any::type_name::<R>())
+    }
 
-        debug_map.finish()
+    /// Returns the resource that the handle refers to, mutably.
+    pub(crate) fn borrow_mut_handle<R>(&self, handle: &ResHandle<R>) -> RefMut<'_, R>
+    where
+        R: Resource,
+    {
+        self.try_borrow_mut_handle(handle)
+            .unwrap_or_else(Self::borrow_panic)
     }
-}
 
-#[cfg(feature = "debug")]
-impl fmt::Debug for Resources {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut debug_map = f.debug_map();
+    /// Returns the resource that the handle refers to, mutably.
+    pub(crate) fn try_borrow_mut_handle<R>(
+        &self,
+        handle: &ResHandle<R>,
+    ) -> Result<RefMut<'_, R>, ResourceError>
+    where
+        R: Resource,
+    {
+        let slot = self.handle_slot(handle, BorrowKind::Mutable)?;
+        self.try_borrow_mut_slot(Some(slot), slot.type_id, core::any::type_name::<R>())
+    }
 
-        self.0.keys().for_each(|type_id| {
-            let resource = &*self.0.borrow(type_id);
-            let type_name = resource.as_ref().type_name();
+    /// Returns the slot that the handle refers to.
+    ///
+    /// Returns an error if the handle was not created by this map, or the
+    /// resource was removed after the handle was created.
+    fn handle_slot<R>(
+        &self,
+        handle: &ResHandle<R>,
+        borrow_kind: BorrowKind,
+    ) -> Result<&ResourceSlot, ResourceError>
+    where
+        R: Resource,
+    {
+        let type_name = core::any::type_name::<R>();
+        if handle.resources_id() != self.id {
+            return Err(ResourceError::new(
+                type_name,
+                Some(borrow_kind),
+                ResourceErrorKind::HandleMismatch,
+            ));
+        }
 
-            debug_map.entry(&type_name, resource);
-        });
+        let slot = &self.slots[handle.index()];
+        debug_assert_eq!(TypeId::of::<R>(), slot.type_id);
+        if slot.generation != handle.generation() {
+            return Err(ResourceError::new(
+                type_name,
+                Some(borrow_kind),
+                ResourceErrorKind::HandleRemoved,
+            ));
+        }
 
-        debug_map.finish()
+        Ok(slot)
     }
-}
 
-impl Deref for Resources {
-    type Target = RtMap<TypeId, Box<dyn Resource>>;
+    /// Inserts a new resource of the type registered under the given name.
+    ///
+    /// The resource is constructed using the constructor registered in the
+    /// `TypeRegistry`. If the resource existed before, it will be overwritten.
+    pub fn insert_by_name(
+        &mut self,
+        type_registry: &TypeRegistry,
+        name: &str,
+    ) -> Result<(), ResourceByNameError> {
+        let resource = type_registry.try_construct(name)?;
+        self.insert_tracked(Resource::type_id(&*resource), resource);
+        Ok(())
+    }
+
+    /// Returns true if the resource type registered under the given name
+    /// exists in `self`.
+    ///
+    /// Returns false if no type is registered under the given name.
+    pub fn contains_by_name(&self, type_registry: &TypeRegistry, name: &str) -> bool {
+        type_registry
+            .type_id(name)
+            .is_some_and(|type_id| self.contains_type_id(type_id))
+    }
+
+    /// Returns the resource whose type is registered under the given name.
+    ///
+    /// See [`try_borrow_by_name`] for a non-panicking version of this
+    /// function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no type is registered under the given name.
+    /// Panics if the resource doesn't exist.
+    /// Panics if the resource is being accessed mutably.
+    ///
+    /// [`try_borrow_by_name`]: Self::try_borrow_by_name
+    pub fn borrow_by_name(
+        &self,
+        type_registry: &TypeRegistry,
+        name: &str,
+    ) -> Ref<'_, dyn Resource> {
+        self.try_borrow_by_name(type_registry, name)
+            .unwrap_or_else(Self::borrow_by_name_panic)
+    }
+
+    /// Returns the resource whose type is registered under the given name.
+    ///
+    /// Returns an error if no type is registered under the given name, or
+    /// the resource cannot be borrowed.
+    pub fn try_borrow_by_name(
+        &self,
+        type_registry: &TypeRegistry,
+        name: &str,
+    ) -> Result<Ref<'_, dyn Resource>, ResourceByNameError> {
+        let (type_id, type_name) = type_registry.try_type(name)?;
+        self.try_borrow_slot(self.slot(type_id), type_id, type_name)
+            .map_err(|error| ResourceByNameError::BorrowFail {
+                name: name.to_string(),
+                error,
+            })
+    }
+
+    /// Returns the resource whose type is registered under the given name,
+    /// mutably.
+    ///
+    /// See [`try_borrow_mut_by_name`] for a non-panicking version of this
+    /// function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no type is registered under the given name.
+    /// Panics if the resource doesn't exist.
+    /// Panics if the resource is already accessed.
+    ///
+    /// [`try_borrow_mut_by_name`]: Self::try_borrow_mut_by_name
+    pub fn borrow_mut_by_name(
+        &self,
+        type_registry: &TypeRegistry,
+        name: &str,
+    ) -> RefMut<'_, dyn Resource> {
+        self.try_borrow_mut_by_name(type_registry, name)
+            .unwrap_or_else(Self::borrow_by_name_panic)
+    }
+
+    /// Returns the resource whose type is registered under the given name,
+    /// mutably.
+    ///
+    /// Returns an error if no type is registered under the given name, or
+    /// the resource cannot be borrowed.
+    pub fn try_borrow_mut_by_name(
+        &self,
+        type_registry: &TypeRegistry,
+        name: &str,
+    ) -> Result<RefMut<'_, dyn Resource>, ResourceByNameError> {
+        let (type_id, type_name) = type_registry.try_type(name)?;
+        self.try_borrow_mut_slot(self.slot(type_id), type_id, type_name)
+            .map_err(|error| ResourceByNameError::BorrowFail {
+                name: name.to_string(),
+                error,
+            })
+    }
+
+    /// Subscribes `f` to changes of the `R` resource.
+    ///
+    /// `f` runs when `R` is inserted, including when a transaction commits, and
+    /// after a `RefMut<R>` that was dereferenced mutably is dropped, once the
+    /// borrow is released, so it can read the new value. Borrows that are only
+    /// read from do not run `f`.
+    ///
+    /// The subscription is kept when `R` is removed or replaced, and lasts
+    /// until it is passed to [`unsubscribe`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::sync::{
+    ///     atomic::{AtomicU32, Ordering},
+    ///     Arc,
+    /// };
+    ///
+    /// use resman::Resources;
+    ///
+    /// let mut resources = Resources::new();
+    /// resources.insert(1u32);
+    ///
+    /// let last_seen = Arc::new(AtomicU32::new(0));
+    /// let subscription = resources.subscribe::<u32, _>({
+    ///     let last_seen = last_seen.clone();
+    ///     move |n| last_seen.store(*n, Ordering::Relaxed)
+    /// });
+    ///
+    /// *resources.borrow_mut::<u32>() += 1;
+    /// assert_eq!(2, last_seen.load(Ordering::Relaxed));
+    ///
+    /// assert!(resources.unsubscribe(subscription));
+    /// *resources.borrow_mut::<u32>() += 1;
+    /// assert_eq!(2, last_seen.load(Ordering::Relaxed));
+    /// ```
+    ///
+    /// [`unsubscribe`]: Self::unsubscribe
+    pub fn subscribe<R, F>(&mut self, f: F) -> Subscription
+    where
+        R: Resource,
+        F: Fn(&R) + Send + Sync + 'static,
+    {
+        let type_id = TypeId::of::<R>();
+        let index = self.slot_index_or_insert(type_id, core::any::type_name::<R>());
+        let id = self.slots[index]
+            .subscribers
+            .insert(Box::new(move |resource: &dyn Resource| {
+                if let Some(r) = resource.downcast_ref::<R>() {
+                    f(r);
+                }
+            }));
+
+        Subscription::new(self.id, type_id, id)
+    }
+
+    /// Returns a [`Watcher`] that is notified when the `R` resource changes.
+    ///
+    /// The watcher is notified when `R` is inserted, or when a `RefMut<R>`
+    /// that was written to is dropped. Changes made through [`get_mut`] are
+    /// not seen.
+    ///
+    /// [`get_mut`]: Self::get_mut
+    #[cfg(feature = "std")]
+    pub fn watch<R>(&mut self) -> Watcher
+    where
+        R: Resource,
+    {
+        let watch_state = Arc::new(WatchState::default());
+        let index = self.slot_index_or_insert(TypeId::of::<R>(), core::any::type_name::<R>());
+        self.slots[index]
+            .subscribers
+            .insert_watcher(Arc::downgrade(&watch_state));
+
+        Watcher::new(watch_state)
+    }
+
+    /// Runs `f`, and returns its return value with the type names of the
+    /// resources it changed, sorted and without duplicates.
+    ///
+    /// A resource is changed if a `RefMut` to it was written to while `f`
+    /// ran. Only resources that exist before the call are tracked.
+    ///
+    /// A `RefMut` that is dereferenced mutably counts as written to, even if
+    /// the value stays the same. With the `"fn_res"` feature,
+    /// `assert_fn_res_changes!` compares resource values instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use resman::Resources;
+    ///
+    /// let mut resources = Resources::builder().with(1u32).with(2u64).build();
+    ///
+    /// let (sum, changed) = resources.track_changes(|resources| {
+    ///     *resources.borrow_mut::<u64>() += 1;
+    ///     *resources.borrow::<u32>() as u64 + *resources.borrow::<u64>()
+    /// });
+    ///
+    /// assert_eq!(4, sum);
+    /// assert_eq!(vec!["u64"], changed);
+    /// ```
+    #[cfg(feature = "testing")]
+    pub fn track_changes<F, Ret>(&mut self, f: F) -> (Ret, Vec<&'static str>)
+    where
+        F: FnOnce(&Resources) -> Ret,
+    {
+        let changed = Arc::new(Mutex::new(BTreeSet::new()));
+        let subscriber_ids = self
+            .slots
+            .iter_mut()
+            .enumerate()
+            .filter(|(_index, slot)| slot.cell.is_some())
+            .map(|(index, slot)| {
+                let type_name = slot.tracker.type_name();
+                let changed = changed.clone();
+                let id = slot
+                    .subscribers
+                    .insert(Box::new(move |_resource: &dyn Resource| {
+                        changed
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .insert(type_name);
+                    }));
+                (index, id)
+            })
+            .collect::<Vec<_>>();
+
+        let ret = f(self);
+
+        subscriber_ids.into_iter().for_each(|(index, id)| {
+            self.slots[index].subscribers.remove(id);
+        });
+
+        let changed = core::mem::take(&mut *changed.lock().unwrap_or_else(PoisonError::into_inner));
+        (ret, changed.into_iter().collect())
+    }
+
+    /// Notifies the resource's watchers and subscribers that it changed.
+    ///
+    /// Subscribers are not run if the resource is borrowed mutably, as they
+    /// cannot read it.
+    fn notify_changed(&self, type_id: TypeId) {
+        let Some(slot) = self.slot(type_id) else {
+            return;
+        };
+
+        #[cfg(feature = "std")]
+        slot.subscribers.notify_watchers();
+
+        if let Some(cell) = slot.cell.as_ref()
+            && let Ok(resource) = cell.try_borrow()
+        {
+            slot.subscribers.notify(&**resource);
+        }
+    }
+
+    /// Stops the subscription's callback from running.
+    ///
+    /// Returns `false` if the subscription was made with a different
+    /// `Resources` map.
+    pub fn unsubscribe(&mut self, subscription: Subscription) -> bool {
+        if subscription.resources_id() != self.id {
+            return false;
+        }
+
+        self.slot_mut(subscription.type_id())
+            .is_some_and(|slot| slot.subscribers.remove(subscription.id()))
+    }
+
+    /// Emits a `tracing` event for an attempt to borrow a resource.
+    #[cfg(feature = "tracing")]
+    fn trace_borrow<T>(type_name: &str, borrow_kind: BorrowKind, result: &Result<T, BorrowFail>) {
+        match result {
+            Ok(_) => tracing::trace!(resource = type_name, %borrow_kind, "Borrowed resource."),
+            Err(borrow_fail) => tracing::trace!(
+                resource = type_name,
+                %borrow_kind,
+                ?borrow_fail,
+                "Failed to borrow resource."
+            ),
+        }
+    }
+
+    /// Records an attempt to borrow a resource in its stats.
+    #[cfg(feature = "stats")]
+    fn record_borrow_stats<T>(
+        &self,
+        slot: Option<&ResourceSlot>,
+        type_id: TypeId,
+        type_name: &'static str,
+        borrow_kind: BorrowKind,
+        result: &Result<T, BorrowFail>,
+    ) {
+        match result {
+            Ok(_) => {
+                if let Some(slot) = slot {
+                    slot.tracker.borrow_succeeded(borrow_kind);
+                }
+            }
+            Err(BorrowFail::ValueNotFound) => {
+                self.value_not_found_counts
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .entry(type_id)
+                    .or_insert((type_name, 0))
+                    .1 += 1;
+            }
+            Err(borrow_fail) => {
+                if let Some(slot) = slot {
+                    slot.tracker.borrow_failed(*borrow_fail);
+                }
+            }
+        }
+    }
+
+    fn borrow_by_name_panic<Ret>(error: ResourceByNameError) -> Ret {
+        match error {
+            ResourceByNameError::NotRegistered { name, suggestions } => {
+                let did_you_mean = crate::similar_names::did_you_mean(&suggestions);
+                panic!(
+                    "Expected to borrow `{name}`, but it is not registered in the `TypeRegistry`.{did_you_mean}"
+                )
+            }
+            ResourceByNameError::BorrowFail { name: _, error } => Self::borrow_panic(error),
+        }
+    }
+
+    pub(crate) fn borrow_panic<Ret>(error: ResourceError) -> Ret {
+        panic!("{error}")
+    }
+
+    /// Returns the `ResourceError` for a failed borrow of the resource in the
+    /// slot.
+    ///
+    /// `suggestions` is only called if the resource doesn't exist.
+    fn borrow_error<F>(
+        slot: Option<&ResourceSlot>,
+        type_name: &'static str,
+        borrow_kind: BorrowKind,
+        borrow_fail: BorrowFail,
+        suggestions: F,
+    ) -> ResourceError
+    where
+        F: FnOnce() -> Vec<&'static str>,
+    {
+        let error = ResourceError::borrow_failed(type_name, borrow_kind, borrow_fail, || {
+            slot.and_then(|slot| slot.cell.as_ref())
+                .is_some_and(|cell| cell.try_borrow().is_err())
+        });
+        match error.kind() {
+            ResourceErrorKind::NotFound => error.with_suggestions(suggestions()),
+            _ => error,
+        }
+    }
+
+    /// Returns the `NotFound` error for `R`, which was not being borrowed.
+    pub(crate) fn not_found_error<R>(&self) -> ResourceError
+    where
+        R: Resource,
+    {
+        ResourceError::not_found::<R>()
+            .with_suggestions(self.similar_type_names(core::any::type_name::<R>()))
+    }
+
+    /// Returns the type names of stored resources that are similar to
+    /// `type_name`.
+    fn similar_type_names(&self, type_name: &str) -> Vec<&'static str> {
+        similar_names(
+            type_name,
+            self.slots_occupied().map(|slot| slot.tracker.type_name()),
+        )
+    }
+
+    /// Returns a view that only allows the given types to be borrowed.
+    ///
+    /// Types in `read` may be borrowed immutably, and types in `write` may be
+    /// borrowed mutably or immutably. Use [`view_builder`] to list the types
+    /// as type parameters instead.
+    ///
+    /// This is useful to limit which resources plugin code can access.
+    ///
+    /// [`view_builder`]: Self::view_builder
+    pub fn view(&self, read: &[TypeId], write: &[TypeId]) -> ResourcesView<'_> {
+        ResourcesView::new(
+            self,
+            read.iter().copied().collect(),
+            write.iter().copied().collect(),
+        )
+    }
+
+    /// Returns a builder for a view that only allows the listed types to be
+    /// borrowed.
+    ///
+    /// See [`ResourcesViewBuilder`] for details.
+    pub fn view_builder(&self) -> ResourcesViewBuilder<'_> {
+        ResourcesViewBuilder::new(self)
+    }
+
+    /// Runs `f` with a [`Transaction`], and applies its changes only if `f`
+    /// returns `Ok`.
+    ///
+    /// Resources borrowed mutably through the transaction are cloned, and `f`
+    /// modifies the copies. If `f` returns `Ok`, the copies replace the
+    /// original resources. If `f` returns `Err` or panics, the copies are
+    /// discarded and the original resources are unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use resman::Resources;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Account(i64);
+    /// #[derive(Clone, Debug)]
+    /// struct Ledger(Vec<i64>);
+    ///
+    /// let mut resources = Resources::new();
+    /// resources.insert(Account(10));
+    /// resources.insert(Ledger(Vec::new()));
+    ///
+    /// let withdraw = |resources: &mut Resources, amount: i64| {
+    ///     resources.transaction(|tx| {
+    ///         tx.borrow_mut::<Ledger>().0.push(-amount);
+    ///
+    ///         let mut account = tx.borrow_mut::<Account>();
+    ///         account.0 -= amount;
+    ///         if account.0 < 0 {
+    ///             return Err("Insufficient funds.");
+    ///         }
+    ///         Ok(account.0)
+    ///     })
+    /// };
+    ///
+    /// assert_eq!(Ok(3), withdraw(&mut resources, 7));
+    /// assert_eq!(Err("Insufficient funds."), withdraw(&mut resources, 5));
+    ///
+    /// assert_eq!(3, resources.borrow::<Account>().0);
+    /// assert_eq!(vec![-7], resources.borrow::<Ledger>().0);
+    /// ```
+    pub fn transaction<T, E, F>(&mut self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut Transaction<'_>) -> Result<T, E>,
+    {
+        let mut transaction = Transaction::new(self);
+        let value = f(&mut transaction)?;
+
+        // Commit through the same path as `insert`, so that watchers and
+        // subscribers are notified.
+        transaction
+            .into_staged()
+            .into_resources()
+            .for_each(|(type_id, resource)| {
+                self.insert_tracked(type_id, resource);
+            });
+
+        Ok(value)
+    }
+
+    /// Lends `r` to this map while `f` runs, then moves it back into `r`.
+    ///
+    /// This lets resource functions borrow a value that the caller only has
+    /// through `&mut R`. Inside `f`, `R` is borrowed from `resources` like any
+    /// other resource, and changes to it are visible in `r` afterwards.
+    ///
+    /// The value is moved back even if `f` panics. If the map already held an
+    /// `R`, that value is hidden while `f` runs, and restored afterwards.
+    /// Watchers and subscribers of `R` are not notified.
+    ///
+    /// `R` does not need to implement `Default`, as the value is moved into the
+    /// map without leaving a replacement in `r`. Like other resources, `R` must
+    /// be `'static`, as it is looked up by its `TypeId`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use resman::Resources;
+    ///
+    /// #[derive(Debug)]
+    /// struct Frame(u32);
+    ///
+    /// let mut resources = Resources::new();
+    /// let mut frame = Frame(1);
+    ///
+    /// let doubled = resources.with_lent(&mut frame, |resources| {
+    ///     let mut frame = resources.borrow_mut::<Frame>();
+    ///     frame.0 += 1;
+    ///     frame.0 * 2
+    /// });
+    ///
+    /// assert_eq!(4, doubled);
+    /// assert_eq!(2, frame.0);
+    /// assert!(!resources.contains::<Frame>());
+    /// ```
+    pub fn with_lent<R, F, Ret>(&mut self, r: &mut R, f: F) -> Ret
+    where
+        R: Resource,
+        F: FnOnce(&Resources) -> Ret,
+    {
+        let lend_guard = LendGuard::new(self, r);
+        f(lend_guard.resources())
+    }
+
+    /// Merges the other `Resources` map over this one.
+    pub fn merge(&mut self, mut other: Resources) {
+        let mut slots_other = core::mem::take(&mut other.slots);
+        other.insertion_order.iter().for_each(|type_id| {
+            let slot_other = &mut slots_other[other.slot_indices[type_id]];
+            let Some(cell) = slot_other.cell.take() else {
+                return;
+            };
+            let type_name = slot_other.tracker.type_name();
+            let tracker =
+                core::mem::replace(&mut slot_other.tracker, ResourceTracker::new(type_name));
+
+            let index = self.slot_index_or_insert(*type_id, type_name);
+            let slot = &mut self.slots[index];
+            slot.tracker = tracker;
+            if slot.cell.replace(cell).is_none() {
+                self.insertion_order.push(*type_id);
+            }
+        });
+        other.slot_indices.clear();
+        self.debug_fns.append(&mut other.debug_fns);
+
+        #[cfg(feature = "stats")]
+        {
+            let mut value_not_found_counts = self
+                .value_not_found_counts
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            other
+                .value_not_found_counts
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .iter()
+                .for_each(|(type_id, (type_name, count))| {
+                    value_not_found_counts
+                        .entry(*type_id)
+                        .or_insert((*type_name, 0))
+                        .1 += count;
+                });
+        }
+    }
+
+    /// Returns the resources that are still borrowed, ordered by type name.
+    ///
+    /// Only borrows taken through `Resources` are counted, i.e. not borrows
+    /// taken directly from a [`Cell`] returned by [`get_raw`].
+    ///
+    /// A `Ref` or `RefMut` that is [`mem::forget`]-ed is never dropped, so it
+    /// remains in this list until the resource is replaced or removed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use resman::{BorrowKind, OutstandingBorrow, Resources};
+    ///
+    /// let mut resources = Resources::new();
+    /// resources.insert(1u32);
+    ///
+    /// let a_0 = resources.borrow::<u32>();
+    /// let a_1 = resources.borrow::<u32>();
+    ///
+    /// assert_eq!(
+    ///     vec![OutstandingBorrow {
+    ///         type_name: "u32",
+    ///         borrow_kind: BorrowKind::Immutable,
+    ///         count: 2,
+    ///     }],
+    ///     resources.outstanding_borrows()
+    /// );
+    ///
+    /// drop((a_0, a_1));
+    /// assert!(resources.outstanding_borrows().is_empty());
+    /// ```
+    ///
+    /// [`get_raw`]: Self::get_raw
+    /// [`mem::forget`]: core::mem::forget
+    pub fn outstanding_borrows(&self) -> Vec<OutstandingBorrow> {
+        let mut outstanding_borrows = self
+            .slots_occupied()
+            .map(|slot| &slot.tracker)
+            .flat_map(|tracker| {
+                [
+                    (BorrowKind::Immutable, tracker.ref_count()),
+                    (BorrowKind::Mutable, tracker.ref_mut_count()),
+                ]
+                .into_iter()
+                .filter(|(_borrow_kind, count)| *count > 0)
+                .map(|(borrow_kind, count)| OutstandingBorrow {
+                    type_name: tracker.type_name(),
+                    borrow_kind,
+                    count,
+                })
+            })
+            .collect::<Vec<_>>();
+        outstanding_borrows.sort_by_key(|outstanding_borrow| {
+            (outstanding_borrow.type_name, outstanding_borrow.borrow_kind)
+        });
+        outstanding_borrows
+    }
+
+    /// Asserts that no resources are still borrowed.
+    ///
+    /// This is useful as a checkpoint, e.g. at the end of each frame, to find
+    /// `Ref`s or `RefMut`s that are held for longer than intended.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any resource is still borrowed, listing each resource with
+    /// its borrow kind and count.
+    pub fn assert_no_outstanding_borrows(&self) {
+        let outstanding_borrows = self.outstanding_borrows();
+        if !outstanding_borrows.is_empty() {
+            let outstanding_borrows = outstanding_borrows
+                .iter()
+                .map(|outstanding_borrow| format!("\n* {outstanding_borrow}"))
+                .collect::<String>();
+            panic!(
+                "Expected no outstanding borrows, but the following resources are still borrowed:\n{outstanding_borrows}"
+            );
+        }
+    }
+
+    /// Returns usage and contention stats for each resource, ordered by type
+    /// name.
+    ///
+    /// Stats are counted for borrows through `Resources`, including borrows by
+    /// resource functions. Replacing a resource keeps its stats, and removing
+    /// a resource discards them.
+    ///
+    /// Resources that were never inserted are included if borrowing them
+    /// failed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use resman::{BorrowFail, Resources};
+    ///
+    /// let mut resources = Resources::new();
+    /// resources.insert(1u32);
+    ///
+    /// drop(resources.borrow::<u32>());
+    /// let a = resources.borrow_mut::<u32>();
+    /// assert!(resources.try_borrow::<u32>().is_err());
+    /// drop(a);
+    ///
+    /// let stats = resources.stats();
+    /// assert_eq!("u32", stats[0].type_name());
+    /// assert_eq!(1, stats[0].borrow_count());
+    /// assert_eq!(1, stats[0].borrow_mut_count());
+    /// assert_eq!(1, stats[0].borrow_fail_count(BorrowFail::BorrowConflictImm));
+    ///
+    /// resources.reset_stats();
+    /// assert_eq!(0, resources.stats()[0].borrow_count());
+    /// ```
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> Vec<ResourceStats> {
+        let mut stats = self
+            .slots_occupied()
+            .map(|slot| (slot.type_id, slot.tracker.stats()))
+            .collect::<BTreeMap<_, _>>();
+
+        self.value_not_found_counts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .for_each(|(type_id, (type_name, count))| {
+                stats
+                    .entry(*type_id)
+                    .or_insert_with(|| ResourceStats::new(type_name))
+                    .value_not_found_count += count;
+            });
+
+        let mut stats = stats.into_values().collect::<Vec<_>>();
+        stats.sort_by_key(|resource_stats| resource_stats.type_name);
+        stats
+    }
+
+    /// Resets the stats of all resources to zero.
+    #[cfg(feature = "stats")]
+    pub fn reset_stats(&self) {
+        self.slots
+            .iter()
+            .for_each(|slot| slot.tracker.reset_stats());
+        self.value_not_found_counts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    /// Sets whether to check for outstanding borrows when `Resources` is
+    /// dropped.
+    ///
+    /// When enabled, dropping `Resources` while a resource is still borrowed
+    /// panics with the same message as [`assert_no_outstanding_borrows`]. The
+    /// check is skipped if the thread is already panicking.
+    ///
+    /// This is disabled by default.
+    ///
+    /// [`assert_no_outstanding_borrows`]: Self::assert_no_outstanding_borrows
+    pub fn set_check_borrows_on_drop(&mut self, check_borrows_on_drop: bool) {
+        self.check_borrows_on_drop = check_borrows_on_drop;
+    }
+
+    /// Sets the resources to drop first when `Resources` is dropped, in the
+    /// given order.
+    ///
+    /// Other resources are dropped afterwards, in reverse insertion order, so
+    /// a resource is dropped before the resources that were inserted before
+    /// it. Replacing a resource keeps its original position. Types that are
+    /// not in the map are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::any::TypeId;
+    ///
+    /// use resman::Resources;
+    ///
+    /// struct Connection;
+    /// struct Service;
+    /// struct Cache;
+    ///
+    /// let mut resources = Resources::new();
+    /// resources.insert(Connection);
+    /// resources.insert(Cache);
+    /// resources.insert(Service);
+    ///
+    /// // Drops `Service`, then `Cache`, then `Connection`.
+    /// resources.set_drop_order(&[TypeId::of::<Service>()]);
+    /// ```
+    pub fn set_drop_order(&mut self, drop_order: &[TypeId]) {
+        self.drop_order = drop_order.to_vec();
+    }
+}
+
+impl fmt::Debug for Resources {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut debug_map = f.debug_map();
+
+        self.slots.iter().for_each(|slot| {
+            let Some(cell) = slot.cell.as_ref() else {
+                return;
+            };
+            let type_name = TypeNameLit(slot.tracker.type_name());
+
+            // A function running in strict mode only sees the values of the
+            // resources in its parameters.
+            #[cfg(all(feature = "fn_res", feature = "fn_meta", feature = "std"))]
+            if !self.restriction_allows(slot.type_id) {
+                debug_map.entry(&type_name, &"..");
+                return;
+            }
+
+            let resource = &*cell.borrow();
+            let resource_debug = ResourceDebug {
+                resource: resource.as_ref(),
+                debug_fn: self.debug_fns.get(&slot.type_id).copied(),
+            };
+
+            debug_map.entry(&type_name, &resource_debug);
+        });
+
+        debug_map.finish()
+    }
+}
+
+impl Resources {
+    /// Returns whether the current thread is panicking.
+    ///
+    /// Without `std`, this is not known, so this always returns `false`.
+    pub(crate) fn panicking() -> bool {
+        #[cfg(feature = "std")]
+        {
+            std::thread::panicking()
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            false
+        }
+    }
+}
+
+impl Drop for Resources {
+    fn drop(&mut self) {
+        if self.check_borrows_on_drop && !Self::panicking() {
+            self.assert_no_outstanding_borrows();
+        }
+
+        // Slots are ordered by when each type was first seen, which differs
+        // from the drop order, so resources are removed one at a time.
+        let drop_order = core::mem::take(&mut self.drop_order);
+        let insertion_order = core::mem::take(&mut self.insertion_order);
+        drop_order
+            .into_iter()
+            .chain(insertion_order.into_iter().rev())
+            .for_each(|type_id| {
+                if let Some(slot) = self.slot_mut(type_id) {
+                    drop(slot.take());
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::any::TypeId;
+    use std::sync::{Arc, Mutex};
+
+    use super::Resources;
+    use crate::{
+        BorrowFail, BorrowKind, Entry, OutstandingBorrow, ResourceByNameError, ResourceError,
+        ResourceErrorKind, TypeRegistry,
+    };
+
+    #[test]
+    fn entry_or_insert_inserts_value() {
+        #[derive(Debug, PartialEq)]
+        struct A(usize);
+
+        let mut resources = Resources::new();
+        let mut a_ref = resources.entry::<A>().or_insert(A(1));
+
+        assert_eq!(&A(1), &*a_ref);
+
+        *a_ref = A(2);
+
+        drop(a_ref);
+
+        assert_eq!(&A(2), &*resources.borrow::<A>());
+    }
+
+    #[test]
+    fn entry_or_default_and_modify() {
+        let mut resources = Resources::new();
+
+        resources
+            .entry::<u32>()
+            .and_modify(|n| *n += 1)
+            .or_default();
+        assert_eq!(0, *resources.borrow::<u32>());
+
+        resources
+            .entry::<u32>()
+            .and_modify(|n| *n += 1)
+            .or_default();
+        assert_eq!(1, *resources.borrow::<u32>());
+    }
+
+    #[test]
+    fn entry_or_try_insert_with_inserts_only_when_ok() {
+        let mut resources = Resources::new();
+
+        let result = resources
+            .entry::<u32>()
+            .or_try_insert_with(|| Err::<u32, _>("failed"))
+            .map(|n| *n);
+        assert_eq!(Err("failed"), result);
+        assert!(!resources.contains::<u32>());
+
+        let result = resources
+            .entry::<u32>()
+            .or_try_insert_with(|| Ok::<_, ()>(1))
+            .map(|n| *n);
+        assert_eq!(Ok(1), result);
+
+        let result = resources
+            .entry::<u32>()
+            .or_try_insert_with(|| Err("unused"))
+            .map(|n| *n);
+        assert_eq!(Ok(1), result);
+    }
+
+    #[test]
+    fn entry_occupied_get_insert_remove() {
+        let mut resources = Resources::new();
+        resources.insert(1u32);
+
+        let Entry::Occupied(mut occupied) = resources.entry::<u32>() else {
+            panic!("Expected `u32` entry to be occupied.");
+        };
+        assert_eq!(1, *occupied.get());
+        *occupied.get_mut() += 1;
+        assert_eq!(2, occupied.insert(3));
+        assert_eq!(3, occupied.remove());
+
+        assert!(!resources.contains::<u32>());
+        assert!(resources.outstanding_borrows().is_empty());
+    }
+
+    #[test]
+    fn entry_vacant_insert() {
+        let mut resources = Resources::new();
+
+        let Entry::Vacant(vacant) = resources.entry::<u32>() else {
+            panic!("Expected `u32` entry to be vacant.");
+        };
+        *vacant.insert(1) += 1;
+
+        assert_eq!(2, *resources.borrow::<u32>());
+    }
+
+    #[test]
+    fn insert_returns_previous_value() {
+        let mut resources = Resources::new();
+
+        assert_eq!(None, resources.insert(1u32));
+        assert_eq!(Some(1), resources.insert(2u32));
+        assert_eq!(2, *resources.borrow::<u32>());
+    }
+
+    #[test]
+    fn replace_and_take_work_through_shared_reference() {
+        let mut resources = Resources::new();
+        resources.insert(1u32);
+        resources.insert(2u64);
+
+        let resources = &resources;
+        let _u64_borrow = resources.borrow::<u64>();
+
+        assert_eq!(1, resources.replace(3u32));
+        assert_eq!(3, resources.take::<u32>());
+        assert_eq!(0, *resources.borrow::<u32>());
+    }
+
+    #[test]
+    fn try_replace_and_try_take_return_err_when_borrowed() {
+        let mut resources = Resources::new();
+        resources.insert(1u32);
+
+        let _u32_borrow = resources.borrow::<u32>();
+
+        assert_eq!(
+            Err(ResourceErrorKind::ConflictShared),
+            resources.try_replace(2u32).map_err(|e| e.kind())
+        );
+        assert_eq!(
+            Err(ResourceErrorKind::ConflictShared),
+            resources.try_take::<u32>().map_err(|e| e.kind())
+        );
+        assert_eq!(
+            Err(ResourceErrorKind::NotFound),
+            resources.try_take::<u64>().map_err(|e| e.kind())
+        );
+    }
+
+    #[test]
+    fn swap_exchanges_or_moves_resources() {
+        let mut resources_a = Resources::new();
+        let mut resources_b = Resources::new();
+        resources_a.insert(1u32);
+        resources_b.insert(2u32);
+        resources_a.insert(3u64);
+
+        resources_a.swap::<u32>(&mut resources_b);
+        resources_a.swap::<u64>(&mut resources_b);
+        resources_a.swap::<i8>(&mut resources_b);
+
+        assert_eq!(2, *resources_a.borrow::<u32>());
+        assert_eq!(1, *resources_b.borrow::<u32>());
+        assert!(!resources_a.contains::<u64>());
+        assert_eq!(3, *resources_b.borrow::<u64>());
+        assert!(!resources_a.contains::<i8>());
+        assert!(!resources_b.contains::<i8>());
+    }
+
+    #[test]
+    fn subscribe_runs_when_dirty_ref_mut_is_dropped() {
+        let mut resources = Resources::new();
+        resources.insert(1u32);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        resources.subscribe::<u32, _>({
+            let seen = seen.clone();
+            move |n| seen.lock().unwrap().push(*n)
+        });
+
+        let n = resources.borrow_mut::<u32>();
+        assert_eq!(1, *n);
+        drop(n);
+        assert!(seen.lock().unwrap().is_empty());
+
+        let mut n = resources.borrow_mut::<u32>();
+        *n += 1;
+        assert!(seen.lock().unwrap().is_empty());
+        drop(n);
+
+        *resources
+            .borrow_mut_by_name(&type_registry(), "u32")
+            .downcast_mut::<u32>()
+            .unwrap() += 1;
+
+        assert_eq!(vec![2, 3], *seen.lock().unwrap());
+    }
+
+    #[test]
+    fn subscription_is_kept_when_resource_is_removed_until_unsubscribed() {
+        let mut resources = Resources::new();
+        resources.insert(1u32);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let subscription = resources.subscribe::<u32, _>({
+            let seen = seen.clone();
+            move |n| seen.lock().unwrap().push(*n)
+        });
+
+        resources.remove::<u32>();
+        resources.insert(5u32);
+        *resources.borrow_mut::<u32>() += 1;
+
+        let mut resources_other = Resources::new();
+        let subscription_other = resources_other.subscribe::<u32, _>(|_| {});
+        assert!(!resources.unsubscribe(subscription_other));
+        assert!(resources.unsubscribe(subscription));
+        *resources.borrow_mut::<u32>() += 1;
+
+        assert_eq!(vec![5, 6], *seen.lock().unwrap());
+    }
+
+    #[test]
+    fn subscribe_runs_when_resource_is_inserted_or_committed_by_transaction() {
+        let mut resources = Resources::new();
+        resources.insert(1u32);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        resources.subscribe::<u32, _>({
+            let seen = seen.clone();
+            move |n| seen.lock().unwrap().push(*n)
+        });
+
+        resources.insert(2u32);
+        let committed: Result<(), ()> = resources.transaction(|tx| {
+            *tx.borrow_mut::<u32>() += 1;
+            Ok(())
+        });
+        let rolled_back: Result<(), ()> = resources.transaction(|tx| {
+            *tx.borrow_mut::<u32>() += 1;
+            Err(())
+        });
+
+        assert_eq!(Ok(()), committed);
+        assert_eq!(Err(()), rolled_back);
+        assert_eq!(vec![2, 3], *seen.lock().unwrap());
+    }
+
+    #[test]
+    fn debug_uses_placeholder_for_values() {
+        let mut resources = Resources::new();
+
+        resources.insert(1u32);
+        resources.insert(2u64);
+
+        let resources_dbg = format!("{:?}", resources);
+        assert!(
+            resources_dbg.contains(r#"u32: "..""#),
+            r#"Expected `{}` to contain `u32: ".."`"#,
+            resources_dbg
+        );
+        assert!(
+            resources_dbg.contains(r#"u64: "..""#),
+            r#"Expected `{}` to contain `u64: ".."`"#,
+            resources_dbg
+        );
+    }
+
+    #[test]
+    fn debug_uses_debug_implementation_for_values_inserted_as_debug() {
+        let mut resources = Resources::new();
+
+        resources.insert_debug(1u32);
+        resources.insert_debug(2u64);
+
+        let resources_dbg = format!("{:?}", resources);
+        assert!(
+            resources_dbg.contains(r#"u32: 1"#),
+            r#"Expected `{}` to contain `u32: 1`"#,
+            resources_dbg
+        );
+        assert!(
+            resources_dbg.contains(r#"u64: 2"#),
+            r#"Expected `{}` to contain `u64: 2`"#,
+            resources_dbg
+        );
+    }
+
+    #[test]
+    fn debug_uses_placeholder_for_values_not_inserted_as_debug() {
+        let mut resources = Resources::new();
+
+        resources.insert_debug(1u32);
+        resources.insert(Res);
+        resources.entry::<u64>().or_insert(2);
+
+        let resources_dbg = format!("{:?}", resources);
+        assert!(
+            resources_dbg.contains(r#"u32: 1"#),
+            r#"Expected `{}` to contain `u32: 1`"#,
+            resources_dbg
+        );
+        assert!(
+            resources_dbg.contains(r#"resman::resources::tests::Res: "..""#),
+            r#"Expected `{}` to contain `resman::resources::tests::Res: ".."`"#,
+            resources_dbg
+        );
+        assert!(
+            resources_dbg.contains(r#"u64: "..""#),
+            r#"Expected `{}` to contain `u64: ".."`"#,
+            resources_dbg
+        );
+    }
+
+    #[test]
+    fn debug_uses_debug_implementation_for_registered_types() {
+        let mut resources = Resources::new();
+        resources.register_debug::<u64>();
+        resources.entry::<u64>().or_insert(2);
+
+        let mut resources_other = Resources::new();
+        resources_other.insert_debug(1u32);
+        resources.merge(resources_other);
+
+        let resources_dbg = format!("{:?}", resources);
+        assert!(
+            resources_dbg.contains(r#"u64: 2"#),
+            r#"Expected `{}` to contain `u64: 2`"#,
+            resources_dbg
+        );
+        assert!(
+            resources_dbg.contains(r#"u32: 1"#),
+            r#"Expected `{}` to contain `u32: 1`"#,
+            resources_dbg
+        );
+    }
+
+    #[test]
+    fn with_capacity_reserves_enough_capacity() {
+        let map = Resources::with_capacity(100);
+        assert!(map.capacity() >= 100);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn into_inner() {
+        let mut resources = Resources::default();
+        resources.insert(Res);
+
+        let rt_map = resources.into_inner();
+
+        assert!(rt_map.contains_key(&TypeId::of::<Res>()));
+    }
+
+    #[test]
+    fn insert() {
+        struct Foo;
+
+        let mut resources = Resources::default();
+        resources.insert(Res);
+
+        assert!(resources.contains::<Res>());
+        assert!(!resources.contains::<Foo>());
+    }
+
+    #[test]
+    fn insert_raw() {
+        struct Foo;
+
+        let mut resources = Resources::default();
+        resources.insert_raw(TypeId::of::<Res>(), Box::new(Res));
+
+        assert!(resources.contains::<Res>());
+        assert!(!resources.contains::<Foo>());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "`Resources::insert_raw` type_id does not match `resman::resources::tests::Res.type_id()`."
+    )]
+    fn insert_raw_panics_when_boxed_resource_does_not_match_key() {
+        struct Foo;
+
+        let mut resources = Resources::default();
+        resources.insert_raw(TypeId::of::<Foo>(), Box::new(Res));
+
+        assert!(resources.contains::<Res>());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `resman::resources::tests::Res` mutably, but it was already borrowed immutably."
+    )]
+    fn read_write_fails() {
+        let mut resources = Resources::default();
+        resources.insert(Res);
+
+        let _read = resources.borrow::<Res>();
+        let _write = resources.borrow_mut::<Res>();
+    }
+
+    #[test]
+    #[should_panic(expected = "but it was already borrowed mutably")]
+    fn write_read_fails() {
+        let mut resources = Resources::default();
+        resources.insert(Res);
+
+        let _write = resources.borrow_mut::<Res>();
+        let _read = resources.borrow::<Res>();
+    }
+
+    #[test]
+    fn remove_insert() {
+        let mut resources = Resources::default();
+        resources.insert(Res);
+
+        assert!(resources.contains::<Res>());
+
+        resources.remove::<Res>();
+
+        assert!(!resources.contains::<Res>());
+
+        resources.insert(Res);
+
+        assert!(resources.contains::<Res>());
+    }
+
+    #[test]
+    fn try_remove() {
+        let mut resources = Resources::default();
+        resources.insert(Res);
+
+        assert!(resources.contains::<Res>());
+
+        assert_eq!(Ok(Res), resources.try_remove::<Res>());
+        let error = resources.try_remove::<Res>().unwrap_err();
+        assert_eq!(ResourceErrorKind::NotFound, error.kind());
+        assert_eq!("Res", error.type_name_short());
+        assert_eq!("resman::resources::tests::Res", error.type_name());
+        assert_eq!(None, error.borrow_kind());
+    }
+
+    #[test]
+    fn try_borrow_suggests_similar_type_names_when_not_found() {
+        mod other {
+            #[derive(Debug)]
+            pub struct Res;
+        }
+
+        let mut resources = Resources::default();
+        resources.insert(other::Res);
+        resources.insert(std::sync::Arc::new(Res));
+        resources.insert(1u32);
+
+        let error = resources.try_borrow::<Res>().unwrap_err();
+        assert_eq!(ResourceErrorKind::NotFound, error.kind());
+        assert_eq!(
+            [
+                core::any::type_name::<std::sync::Arc<Res>>(),
+                core::any::type_name::<other::Res>(),
+            ],
+            error.suggestions()
+        );
+        assert!(resources
+            .try_borrow::<u64>()
+            .unwrap_err()
+            .suggestions()
+            .is_empty());
+        assert!(
+            resources
+                .try_remove::<Res>()
+                .unwrap_err()
+                .suggestions()
+                .len()
+                == 2
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `resman::resources::tests::Res`, but it does not exist. Did you mean: `alloc::sync::Arc<resman::resources::tests::Res>`?"
+    )]
+    fn borrow_panic_lists_similar_type_names_when_not_found() {
+        let mut resources = Resources::default();
+        resources.insert(std::sync::Arc::new(Res));
+
+        resources.borrow::<Res>();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `resman::resources::tests::Res`, but it does not exist."
+    )]
+    fn borrow_before_insert_panics() {
+        let resources = Resources::default();
+
+        resources.borrow::<Res>();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `resman::resources::tests::Res`, but it does not exist."
+    )]
+    fn borrow_mut_before_insert_panics() {
+        let resources = Resources::default();
+
+        resources.borrow_mut::<Res>();
+    }
+
+    #[test]
+    fn borrow_mut_try_borrow_returns_err() {
+        let mut resources = Resources::default();
+        resources.insert(Res);
+
+        let _res = resources.borrow_mut::<Res>();
+
+        let error = resources.try_borrow::<Res>().unwrap_err();
+        assert_eq!(ResourceErrorKind::ConflictMutable, error.kind());
+        assert_eq!(Some(BorrowKind::Immutable), error.borrow_kind());
+        assert_eq!(Some(BorrowFail::BorrowConflictImm), error.borrow_fail());
+    }
+
+    #[test]
+    fn borrow_try_borrow_mut_returns_err() {
+        let mut resources = Resources::default();
+        resources.insert(Res);
+
+        let _res = resources.borrow::<Res>();
+
+        let error = resources.try_borrow_mut::<Res>().unwrap_err();
+        assert_eq!(ResourceErrorKind::ConflictShared, error.kind());
+        assert_eq!(Some(BorrowKind::Mutable), error.borrow_kind());
+        assert_eq!(Some(BorrowFail::BorrowConflictMut), error.borrow_fail());
+    }
+
+    #[test]
+    fn borrow_mut_borrow_mut_returns_err() {
+        let mut resources = Resources::default();
+        resources.insert(Res);
+
+        let _res = resources.borrow_mut::<Res>();
+
+        let error = resources.try_borrow_mut::<Res>().unwrap_err();
+        assert_eq!(ResourceErrorKind::ConflictMutable, error.kind());
+        assert_eq!(Some(BorrowKind::Mutable), error.borrow_kind());
+        assert_eq!(Some(BorrowFail::BorrowConflictMut), error.borrow_fail());
+    }
+
+    #[test]
+    fn get_mut_returns_ok() {
+        let mut resources = Resources::default();
+        resources.insert(Res);
+
+        let _res = resources.get_mut::<Res>();
+
+        assert!(resources.try_borrow_mut::<Res>().is_ok());
+    }
+
+    #[test]
+    fn get_resource_mut_returns_some() {
+        let mut resources = Resources::default();
+        resources.insert(Res);
+
+        assert!(resources.get_resource_mut(TypeId::of::<Res>()).is_some());
+    }
+
+    #[test]
+    fn get_raw_returns_some() {
+        let mut resources = Resources::default();
+        resources.insert(Res);
+
+        assert!(resources.get_raw(&TypeId::of::<Res>()).is_some());
+    }
+
+    #[test]
+    fn merge() {
+        let mut resources_0 = Resources::default();
+        resources_0.insert(1u8);
+        resources_0.insert(2u16);
+
+        let mut resources_1 = Resources::default();
+        resources_1.insert(3u8);
+        resources_1.insert(4u32);
+
+        resources_0.merge(resources_1);
+
+        assert_eq!(3u8, *resources_0.borrow::<u8>());
+        assert_eq!(4u32, *resources_0.borrow::<u32>());
+    }
+
+    #[test]
+    fn insert_by_name_inserts_constructed_resource() {
+        let mut resources = Resources::default();
+        let type_registry = type_registry();
+
+        assert_eq!(Ok(()), resources.insert_by_name(&type_registry, "res"));
+        assert!(resources.contains::<Res>());
+        assert!(resources.contains_by_name(&type_registry, "Res"));
+        assert!(!resources.contains_by_name(&type_registry, "u32"));
+        assert!(!resources.contains_by_name(&type_registry, "missing"));
+    }
+
+    #[test]
+    fn borrow_mut_by_name_allows_mutation() {
+        let mut resources = Resources::default();
+        resources.insert(1u32);
+        let type_registry = type_registry();
+
+        *resources
+            .borrow_mut_by_name(&type_registry, "u32")
+            .downcast_mut::<u32>()
+            .unwrap() += 1;
+
+        let resource = resources.borrow_by_name(&type_registry, "u32");
+        assert_eq!(Some(&2), resource.downcast_ref::<u32>());
+    }
+
+    #[test]
+    fn try_borrow_by_name_returns_err_when_not_registered() {
+        let resources = Resources::default();
+        let type_registry = type_registry();
+
+        assert!(matches!(
+            resources.try_borrow_by_name(&type_registry, "Rse"),
+            Err(ResourceByNameError::NotRegistered { name, suggestions })
+            if name == "Rse" && suggestions == ["Res", "res"]
+        ));
+    }
+
+    #[test]
+    fn try_borrow_mut_by_name_returns_err_when_borrowed() {
+        let mut resources = Resources::default();
+        resources.insert(Res);
+        let type_registry = type_registry();
+
+        let _res = resources.borrow::<Res>();
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+        assert!(resources.try_borrow_by_name(&type_registry, "Res").is_ok());
+        assert!(matches!(
+            resources.try_borrow_mut_by_name(&type_registry, "res"),
+            Err(ResourceByNameError::BorrowFail { name, error })
+                if name == "res" && error.kind() == ResourceErrorKind::ConflictShared
+        ));
     }
-}
 
-impl DerefMut for Resources {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+    #[cfg(feature = "std")]
+    #[test]
+    fn try_borrow_by_name_error_source_is_resource_error() {
+        use std::error::Error;
+
+        let resources = Resources::default();
+        let type_registry = type_registry();
+
+        let error = resources
+            .try_borrow_by_name(&type_registry, "res")
+            .err()
+            .expect("Expected `Res` to not exist.");
+        let source = error
+            .source()
+            .and_then(|source| source.downcast_ref::<ResourceError>())
+            .expect("Expected source to be a `ResourceError`.");
+
+        assert_eq!(ResourceErrorKind::NotFound, source.kind());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::any::TypeId;
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `U32`, but it is not registered in the `TypeRegistry`. Did you mean: `u32`?"
+    )]
+    fn borrow_by_name_panics_when_not_registered() {
+        let resources = Resources::default();
 
-    use super::Resources;
-    use crate::{BorrowFail, ResourceFetchError};
+        resources.borrow_by_name(&type_registry(), "U32");
+    }
 
     #[test]
-    fn entry_or_insert_inserts_value() {
-        #[derive(Debug, PartialEq)]
-        struct A(usize);
+    #[should_panic(
+        expected = "Expected to borrow `resman::resources::tests::Res`, but it does not exist."
+    )]
+    fn borrow_mut_by_name_panics_when_not_inserted() {
+        let resources = Resources::default();
 
-        let mut resources = Resources::new();
-        let mut a_ref = resources.entry::<A>().or_insert(A(1));
+        resources.borrow_mut_by_name(&type_registry(), "Res");
+    }
 
-        assert_eq!(&A(1), &*a_ref);
+    #[test]
+    fn outstanding_borrows_counts_refs_and_ref_muts() {
+        let mut resources = Resources::default();
+        resources.insert(Res);
+        resources.insert(1u32);
 
-        *a_ref = A(2);
+        let res_0 = resources.borrow::<Res>();
+        let res_1 = res_0.clone();
+        let a = resources.borrow_mut::<u32>();
 
-        drop(a_ref);
+        assert_eq!(
+            vec![
+                OutstandingBorrow {
+                    type_name: "resman::resources::tests::Res",
+                    borrow_kind: BorrowKind::Immutable,
+                    count: 2,
+                },
+                OutstandingBorrow {
+                    type_name: "u32",
+                    borrow_kind: BorrowKind::Mutable,
+                    count: 1,
+                },
+            ],
+            resources.outstanding_borrows()
+        );
 
-        assert_eq!(&A(2), &*resources.borrow::<A>());
+        drop((res_0, res_1, a));
+
+        assert!(resources.outstanding_borrows().is_empty());
     }
 
-    #[cfg(not(feature = "debug"))]
     #[test]
-    fn debug_uses_placeholder_for_values() {
-        let mut resources = Resources::new();
+    fn outstanding_borrows_includes_forgotten_borrows() {
+        let mut resources = Resources::default();
+        resources.insert(Res);
 
-        resources.insert(1u32);
-        resources.insert(2u64);
+        core::mem::forget(resources.borrow_mut::<Res>());
+        core::mem::forget(resources.entry::<u32>().or_insert(1));
 
-        let resources_dbg = format!("{:?}", resources);
-        assert!(
-            resources_dbg.contains(r#"u32: "..""#),
-            r#"Expected `{}` to contain `u32: ".."`"#,
-            resources_dbg
-        );
-        assert!(
-            resources_dbg.contains(r#"u64: "..""#),
-            r#"Expected `{}` to contain `u64: ".."`"#,
-            resources_dbg
+        assert_eq!(
+            vec![
+                OutstandingBorrow {
+                    type_name: "resman::resources::tests::Res",
+                    borrow_kind: BorrowKind::Mutable,
+                    count: 1,
+                },
+                OutstandingBorrow {
+                    type_name: "u32",
+                    borrow_kind: BorrowKind::Mutable,
+                    count: 1,
+                },
+            ],
+            resources.outstanding_borrows()
         );
+
+        resources.insert(Res);
+        resources.remove::<u32>();
+
+        assert!(resources.outstanding_borrows().is_empty());
     }
 
-    #[cfg(feature = "debug")]
     #[test]
-    fn debug_uses_debug_implementation_for_values() {
-        let mut resources = Resources::new();
+    fn outstanding_borrows_includes_borrows_by_name_and_merged_resources() {
+        let mut resources_other = Resources::default();
+        resources_other.insert(Res);
+        core::mem::forget(resources_other.borrow::<Res>());
 
+        let mut resources = Resources::default();
         resources.insert(1u32);
-        resources.insert(2u64);
+        resources.merge(resources_other);
+        let _a = resources.borrow_by_name(&type_registry(), "u32");
 
-        let resources_dbg = format!("{:?}", resources);
-        assert!(
-            resources_dbg.contains(r#"u32: 1"#),
-            r#"Expected `{}` to contain `u32: 1`"#,
-            resources_dbg
-        );
-        assert!(
-            resources_dbg.contains(r#"u64: 2"#),
-            r#"Expected `{}` to contain `u64: 2`"#,
-            resources_dbg
+        assert_eq!(
+            vec![
+                OutstandingBorrow {
+                    type_name: "resman::resources::tests::Res",
+                    borrow_kind: BorrowKind::Immutable,
+                    count: 1,
+                },
+                OutstandingBorrow {
+                    type_name: "u32",
+                    borrow_kind: BorrowKind::Immutable,
+                    count: 1,
+                },
+            ],
+            resources.outstanding_borrows()
         );
     }
 
     #[test]
-    fn with_capacity_reserves_enough_capacity() {
-        let map = Resources::with_capacity(100);
-        assert!(map.capacity() >= 100);
+    #[should_panic(
+        expected = "Expected no outstanding borrows, but the following resources are still borrowed:\n\n* `u32` is borrowed mutably (count: 1)"
+    )]
+    fn assert_no_outstanding_borrows_panics_when_borrowed() {
+        let mut resources = Resources::default();
+        resources.insert(1u32);
+
+        let _a = resources.borrow_mut::<u32>();
+
+        resources.assert_no_outstanding_borrows();
     }
 
     #[test]
-    fn into_inner() {
+    fn assert_no_outstanding_borrows_does_not_panic_when_borrows_dropped() {
         let mut resources = Resources::default();
-        resources.insert(Res);
+        resources.insert(1u32);
 
-        let rt_map = resources.into_inner();
+        drop(resources.borrow_mut::<u32>());
 
-        assert!(rt_map.contains_key(&TypeId::of::<Res>()));
+        resources.assert_no_outstanding_borrows();
     }
 
     #[test]
-    fn insert() {
-        #[cfg_attr(feature = "debug", derive(Debug))]
-        struct Foo;
-
+    #[should_panic(expected = "* `u32` is borrowed immutably (count: 1)")]
+    fn drop_panics_when_borrowed_and_check_borrows_on_drop_enabled() {
         let mut resources = Resources::default();
-        resources.insert(Res);
+        resources.set_check_borrows_on_drop(true);
+        resources.insert(1u32);
 
-        assert!(resources.contains::<Res>());
-        assert!(!resources.contains::<Foo>());
+        core::mem::forget(resources.borrow::<u32>());
     }
 
     #[test]
-    fn insert_raw() {
-        #[cfg_attr(feature = "debug", derive(Debug))]
-        struct Foo;
-
+    fn drop_does_not_panic_when_borrowed_and_check_borrows_on_drop_disabled() {
         let mut resources = Resources::default();
-        resources.insert_raw(TypeId::of::<Res>(), Box::new(Res));
+        resources.insert(1u32);
 
-        assert!(resources.contains::<Res>());
-        assert!(!resources.contains::<Foo>());
+        core::mem::forget(resources.borrow::<u32>());
     }
 
     #[test]
-    #[should_panic(
-        expected = "`Resources::insert_raw` type_id does not match `resman::resources::tests::Res.type_id()`."
-    )]
-    fn insert_raw_panics_when_boxed_resource_does_not_match_key() {
-        #[cfg_attr(feature = "debug", derive(Debug))]
-        struct Foo;
-
+    fn drop_drops_resources_in_reverse_insertion_order() {
+        let dropped = Arc::new(Mutex::new(Vec::new()));
         let mut resources = Resources::default();
-        resources.insert_raw(TypeId::of::<Foo>(), Box::new(Res));
+        resources.insert(DropRecorder::<0>(dropped.clone()));
+        resources.insert(DropRecorder::<1>(dropped.clone()));
+        resources.insert(DropRecorder::<2>(dropped.clone()));
+        resources.insert(DropRecorder::<3>(dropped.clone()));
 
-        assert!(resources.contains::<Res>());
+        // Replacing keeps the position, and removing drops the value now.
+        resources.insert(DropRecorder::<1>(dropped.clone()));
+        resources.remove::<DropRecorder<2>>();
+        drop(resources);
+
+        assert_eq!(vec![1, 2, 3, 1, 0], *dropped.lock().unwrap());
     }
 
     #[test]
-    #[should_panic(
-        expected = "Expected to borrow `resman::resources::tests::Res` mutably, but it was already borrowed mutably."
-    )]
-    fn read_write_fails() {
+    fn drop_drops_resources_in_drop_order_first() {
+        let dropped = Arc::new(Mutex::new(Vec::new()));
         let mut resources = Resources::default();
-        resources.insert(Res);
-
-        let _read = resources.borrow::<Res>();
-        let _write = resources.borrow_mut::<Res>();
+        resources.insert(DropRecorder::<0>(dropped.clone()));
+        resources.insert(DropRecorder::<1>(dropped.clone()));
+        resources.insert(DropRecorder::<2>(dropped.clone()));
+        resources.insert(DropRecorder::<3>(dropped.clone()));
+
+        resources.set_drop_order(&[
+            TypeId::of::<DropRecorder<0>>(),
+            TypeId::of::<DropRecorder<2>>(),
+            TypeId::of::<u32>(),
+        ]);
+        drop(resources);
+
+        assert_eq!(vec![0, 2, 3, 1], *dropped.lock().unwrap());
     }
 
     #[test]
-    #[should_panic(expected = "but it was already borrowed mutably")]
-    fn write_read_fails() {
-        let mut resources = Resources::default();
-        resources.insert(Res);
+    fn len_counts_resources_after_insert_remove_and_merge() {
+        let mut resources = Resources::new();
+        assert!(resources.is_empty());
 
-        let _write = resources.borrow_mut::<Res>();
-        let _read = resources.borrow::<Res>();
+        resources.insert(1u32);
+        resources.insert(2u32);
+        resources.insert(3u64);
+        assert_eq!(2, resources.len());
+
+        resources.remove::<u32>();
+        assert_eq!(1, resources.len());
+
+        let mut other = Resources::new();
+        other.insert(4u32);
+        other.insert(5u64);
+        resources.merge(other);
+        assert_eq!(2, resources.len());
+        assert!(!resources.is_empty());
     }
 
     #[test]
-    fn remove_insert() {
+    fn merge_appends_new_resources_to_insertion_order() {
+        let dropped = Arc::new(Mutex::new(Vec::new()));
         let mut resources = Resources::default();
+        resources.insert(DropRecorder::<0>(dropped.clone()));
+        resources.insert(DropRecorder::<1>(dropped.clone()));
+        let mut other = Resources::default();
+        other.insert(DropRecorder::<3>(dropped.clone()));
+        other.insert(DropRecorder::<0>(dropped.clone()));
+        other.insert(DropRecorder::<2>(dropped.clone()));
+
+        resources.merge(other);
+        dropped.lock().unwrap().clear();
+        drop(resources);
+
+        assert_eq!(vec![2, 3, 1, 0], *dropped.lock().unwrap());
+    }
+
+    #[test]
+    fn handle_borrows_resource_and_survives_replace() {
+        let mut resources = Resources::new();
         resources.insert(Res);
+        resources.insert(1u32);
 
-        assert!(resources.contains::<Res>());
+        let handle = resources.handle::<u32>();
+        assert_eq!(handle.index(), resources.handle::<u32>().index());
 
-        resources.remove::<Res>();
+        *handle.borrow_mut(&resources) += 1;
+        assert_eq!(2, *handle.borrow(&resources));
 
-        assert!(!resources.contains::<Res>());
+        resources.insert(5u32);
+        assert_eq!(5, *handle.borrow(&resources));
+    }
 
-        resources.insert(Res);
+    #[test]
+    fn handle_try_borrow_returns_err_when_used_with_other_resources() {
+        let mut resources = Resources::new();
+        resources.insert(1u32);
+        let mut resources_other = Resources::new();
+        resources_other.insert(1u32);
 
-        assert!(resources.contains::<Res>());
+        let handle = resources.handle::<u32>();
+
+        assert_eq!(
+            Some(ResourceError::new(
+                "u32",
+                Some(BorrowKind::Immutable),
+                ResourceErrorKind::HandleMismatch
+            )),
+            handle.try_borrow(&resources_other).err()
+        );
     }
 
     #[test]
-    fn try_remove() {
-        let mut resources = Resources::default();
-        resources.insert(Res);
+    fn handle_try_borrow_mut_returns_err_when_removed_or_borrowed() {
+        let mut resources = Resources::new();
+        resources.insert(1u32);
 
-        assert!(resources.contains::<Res>());
+        let handle = resources.handle::<u32>();
+        let a = handle.borrow(&resources);
+        assert_eq!(
+            Some(ResourceError::new(
+                "u32",
+                Some(BorrowKind::Mutable),
+                ResourceErrorKind::ConflictShared
+            )),
+            handle.try_borrow_mut(&resources).err()
+        );
+        drop(a);
 
-        assert_eq!(Ok(Res), resources.try_remove::<Res>());
-        assert!(matches!(
-            resources.try_remove::<Res>(),
-            Err(ResourceFetchError {
-                resource_name_short,
-                resource_name_full,
-            }) if resource_name_short == "Res"
-            && resource_name_full == "resman::resources::tests::Res"
-        ));
+        resources.remove::<u32>();
+        resources.insert(2u32);
+        assert_eq!(
+            Some(ResourceError::new(
+                "u32",
+                Some(BorrowKind::Mutable),
+                ResourceErrorKind::HandleRemoved
+            )),
+            handle.try_borrow_mut(&resources).err()
+        );
+        assert_eq!(2, *resources.handle::<u32>().borrow(&resources));
     }
 
     #[test]
     #[should_panic(
-        expected = "Expected to borrow `resman::resources::tests::Res`, but it does not exist."
+        expected = "Expected to borrow `u32` through a handle, but it was removed after the handle was created."
     )]
-    fn borrow_before_insert_panics() {
-        let resources = Resources::default();
+    fn handle_borrow_panics_when_removed() {
+        let mut resources = Resources::new();
+        resources.insert(1u32);
 
-        resources.borrow::<Res>();
+        let handle = resources.handle::<u32>();
+        resources.remove::<u32>();
+
+        handle.borrow(&resources);
     }
 
     #[test]
-    #[should_panic(
-        expected = "Expected to borrow `resman::resources::tests::Res`, but it does not exist."
-    )]
-    fn borrow_mut_before_insert_panics() {
-        let resources = Resources::default();
+    fn handle_stays_valid_when_other_resources_are_inserted_and_removed() {
+        let mut resources = Resources::new();
+        resources.insert(1u32);
+        let handle = resources.handle::<u32>();
 
-        resources.borrow_mut::<Res>();
+        resources.insert(2u64);
+        resources.insert(3i8);
+        resources.remove::<u64>();
+        *handle.borrow_mut(&resources) += 1;
+
+        assert_eq!(2, *handle.borrow(&resources));
+        assert_eq!(2, *resources.borrow::<u32>());
+        assert_eq!(3, *resources.borrow::<i8>());
     }
 
     #[test]
-    fn borrow_mut_try_borrow_returns_err() {
-        let mut resources = Resources::default();
-        resources.insert(Res);
-
-        let _res = resources.borrow_mut::<Res>();
+    fn try_handle_returns_err_when_resource_does_not_exist() {
+        let resources = Resources::new();
 
         assert_eq!(
-            Err(BorrowFail::BorrowConflictImm),
-            resources.try_borrow::<Res>()
+            Some(ResourceError::not_found::<u32>()),
+            resources.try_handle::<u32>().err()
         );
     }
 
     #[test]
-    fn borrow_try_borrow_mut_returns_err() {
-        let mut resources = Resources::default();
-        resources.insert(Res);
+    fn with_lent_lends_value_and_moves_it_back() {
+        #[derive(Debug, PartialEq)]
+        struct Frame(u32);
 
-        let _res = resources.borrow::<Res>();
+        let mut resources = Resources::new();
+        let mut frame = Frame(1);
 
-        assert_eq!(
-            Err(BorrowFail::BorrowConflictMut),
-            resources.try_borrow_mut::<Res>()
-        );
+        let value = resources.with_lent(&mut frame, |resources| {
+            resources.borrow_mut::<Frame>().0 += 1;
+            resources.borrow::<Frame>().0
+        });
+
+        assert_eq!(2, value);
+        assert_eq!(Frame(2), frame);
+        assert!(!resources.contains::<Frame>());
     }
 
     #[test]
-    fn borrow_mut_borrow_mut_returns_err() {
-        let mut resources = Resources::default();
-        resources.insert(Res);
+    fn with_lent_restores_previous_value() {
+        let mut resources = Resources::new();
+        resources.insert(1u32);
+        let handle = resources.handle::<u32>();
+        let mut lent = 5u32;
 
-        let _res = resources.borrow_mut::<Res>();
+        resources.with_lent(&mut lent, |resources| {
+            *resources.borrow_mut::<u32>() += 1;
+        });
 
+        assert_eq!(6, lent);
+        assert_eq!(1, *handle.borrow(&resources));
+    }
+
+    #[test]
+    fn with_lent_moves_value_back_when_panicking() {
+        use std::panic::{self, AssertUnwindSafe};
+
+        #[derive(Debug, PartialEq)]
+        struct Frame(String);
+
+        let mut resources = Resources::new();
+        resources.insert(Frame(String::from("previous")));
+        let mut frame = Frame(String::from("lent"));
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            resources.with_lent(&mut frame, |resources| {
+                resources.borrow_mut::<Frame>().0.push_str(" and changed");
+                panic!("failed");
+            })
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(Frame(String::from("lent and changed")), frame);
         assert_eq!(
-            Err(BorrowFail::BorrowConflictMut),
-            resources.try_borrow_mut::<Res>()
+            Frame(String::from("previous")),
+            *resources.borrow::<Frame>()
         );
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn get_mut_returns_ok() {
-        let mut resources = Resources::default();
-        resources.insert(Res);
+    fn with_lent_does_not_notify_watchers() {
+        let mut resources = Resources::new();
+        resources.insert(1u32);
+        let watcher = resources.watch::<u32>();
 
-        let _res = resources.get_mut::<Res>();
+        let mut lent = 5u32;
+        let value = resources.with_lent(&mut lent, |resources| *resources.borrow::<u32>());
 
-        assert!(resources.try_borrow_mut::<Res>().is_ok());
+        assert_eq!(5, value);
+        assert!(!watcher.has_changed());
     }
 
+    #[cfg(feature = "stats")]
     #[test]
-    fn get_resource_mut_returns_some() {
-        let mut resources = Resources::default();
+    fn stats_counts_borrows_and_failures() {
+        use core::time::Duration;
+
+        use crate::ResourceStats;
+
+        let type_registry = type_registry();
+        let mut resources = Resources::new();
         resources.insert(Res);
 
-        assert!(resources.get_resource_mut(TypeId::of::<Res>()).is_some());
+        drop(resources.borrow::<Res>());
+        drop(resources.borrow_by_name(&type_registry, "res"));
+        let res = resources.borrow_mut::<Res>();
+        assert!(resources.try_borrow::<Res>().is_err());
+        assert!(resources.try_borrow_mut::<Res>().is_err());
+        std::thread::sleep(Duration::from_millis(1));
+        drop(res);
+        assert!(resources.try_borrow::<u32>().is_err());
+        assert!(resources
+            .try_borrow_mut_by_name(&type_registry, "u32")
+            .is_err());
+
+        let stats = resources.stats();
+        assert_eq!(2, stats.len());
+
+        let res_stats = &stats[0];
+        assert_eq!("resman::resources::tests::Res", res_stats.type_name());
+        assert_eq!(2, res_stats.borrow_count());
+        assert_eq!(1, res_stats.borrow_mut_count());
+        assert_eq!(0, res_stats.borrow_fail_count(BorrowFail::ValueNotFound));
+        assert_eq!(
+            1,
+            res_stats.borrow_fail_count(BorrowFail::BorrowConflictImm)
+        );
+        assert_eq!(
+            1,
+            res_stats.borrow_fail_count(BorrowFail::BorrowConflictMut)
+        );
+        assert!(res_stats.borrow_mut_held_max() >= Duration::from_millis(1));
+
+        assert_eq!(
+            ResourceStats {
+                value_not_found_count: 2,
+                ..ResourceStats::new("u32")
+            },
+            stats[1]
+        );
     }
 
+    #[cfg(feature = "stats")]
     #[test]
-    fn get_raw_returns_some() {
-        let mut resources = Resources::default();
+    fn stats_are_kept_on_replace_and_cleared_on_reset() {
+        let mut resources = Resources::new();
+        assert!(resources.try_borrow::<Res>().is_err());
+        resources.insert(Res);
+        drop(resources.borrow::<Res>());
         resources.insert(Res);
 
-        assert!(resources.get_raw(&TypeId::of::<Res>()).is_some());
+        let stats = resources.stats();
+        assert_eq!(1, stats[0].borrow_count());
+        assert_eq!(1, stats[0].borrow_fail_count(BorrowFail::ValueNotFound));
+
+        resources.reset_stats();
+
+        let stats = resources.stats();
+        assert_eq!(
+            vec![crate::ResourceStats::new("resman::resources::tests::Res")],
+            stats
+        );
     }
 
+    #[cfg(feature = "tracing")]
     #[test]
-    fn merge() {
-        let mut resources_0 = Resources::default();
-        resources_0.insert(1u8);
-        resources_0.insert(2u16);
+    fn try_borrow_emits_trace_events() {
+        use crate::test_subscriber::TestSubscriber;
 
-        let mut resources_1 = Resources::default();
-        resources_1.insert(3u8);
-        resources_1.insert(4u32);
+        let mut resources = Resources::default();
+        resources.insert(1u32);
+        let test_subscriber = TestSubscriber::default();
+
+        tracing::subscriber::with_default(test_subscriber.clone(), || {
+            let _a = resources.borrow::<u32>();
+            assert!(resources.try_borrow_mut::<u32>().is_err());
+            assert!(resources
+                .try_borrow_by_name(&type_registry(), "u32")
+                .is_ok());
+        });
 
-        resources_0.merge(resources_1);
+        assert_eq!(
+            vec![
+                String::from(
+                    r#"event: message=Borrowed resource. resource="u32" borrow_kind=immutably"#
+                ),
+                String::from(
+                    r#"event: message=Failed to borrow resource. resource="u32" borrow_kind=mutably borrow_fail=BorrowConflictMut"#
+                ),
+                String::from(
+                    r#"event: message=Borrowed resource. resource="u32" borrow_kind=immutably"#
+                ),
+            ],
+            test_subscriber.records()
+        );
+    }
 
-        assert_eq!(3u8, *resources_0.borrow::<u8>());
-        assert_eq!(4u32, *resources_0.borrow::<u32>());
+    fn type_registry() -> TypeRegistry {
+        let mut type_registry = TypeRegistry::new();
+        type_registry.register::<Res>();
+        type_registry.register_as::<Res>("res");
+        type_registry.register::<u32>();
+        type_registry
     }
 
     #[derive(Debug, Default, PartialEq)]
     struct Res;
+
+    /// Records its `ID` when dropped.
+    struct DropRecorder<const ID: u8>(Arc<Mutex<Vec<u8>>>);
+
+    impl<const ID: u8> Drop for DropRecorder<ID> {
+        fn drop(&mut self) {
+            self.0.lock().unwrap().push(ID);
+        }
+    }
 }
@@ -0,0 +1,72 @@
+use core::fmt;
+
+use crate::{Resource, Resources};
+
+/// Builds a `Resources` map with the given resources.
+///
+/// Created by [`Resources::builder`].
+///
+/// # Examples
+///
+/// ```rust
+/// use resman::Resources;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct A(u32);
+/// #[derive(Debug, Default, PartialEq)]
+/// struct B(u32);
+///
+/// let resources = Resources::builder().with(A(1)).with_default::<B>().build();
+///
+/// resman::assert_resource_eq!(resources, A, A(1));
+/// resman::assert_resource_eq!(resources, B, B(0));
+/// ```
+///
+/// [`Resources::builder`]: crate::Resources::builder
+#[derive(Debug, Default)]
+pub struct ResourcesBuilder {
+    /// The resources built so far.
+    resources: Resources,
+}
+
+impl ResourcesBuilder {
+    /// Returns a new `ResourcesBuilder` with no resources.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts the resource.
+    #[must_use]
+    pub fn with<R>(mut self, r: R) -> Self
+    where
+        R: Resource,
+    {
+        self.resources.insert(r);
+        self
+    }
+
+    /// Inserts the resource, and includes its value in the `Debug` output of
+    /// `Resources`.
+    #[must_use]
+    pub fn with_debug<R>(mut self, r: R) -> Self
+    where
+        R: Resource + fmt::Debug,
+    {
+        self.resources.insert_debug(r);
+        self
+    }
+
+    /// Inserts `R::default()`.
+    #[must_use]
+    pub fn with_default<R>(self) -> Self
+    where
+        R: Resource + Default,
+    {
+        self.with(R::default())
+    }
+
+    /// Returns the built `Resources`.
+    pub fn build(self) -> Resources {
+        self.resources
+    }
+}
@@ -0,0 +1,15 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// ID of the next `Resources` map.
+static RESOURCES_ID_NEXT: AtomicUsize = AtomicUsize::new(0);
+
+/// Unique ID of a `Resources` map, used to check that a `ResHandle` is used
+/// with the map that created it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ResourcesId(usize);
+
+impl Default for ResourcesId {
+    fn default() -> Self {
+        Self(RESOURCES_ID_NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
@@ -0,0 +1,308 @@
+use alloc::collections::BTreeSet;
+use core::any::TypeId;
+
+use crate::{BorrowKind, Ref, RefMut, Resource, ResourceError, ResourceErrorKind, Resources};
+
+/// View of `Resources` that only allows borrowing the listed resource types.
+///
+/// Created by [`Resources::view`] or [`ResourcesViewBuilder`]. Types that are
+/// allowed to be written may also be read. Borrowing any other type returns a
+/// [`ResourceError`] of kind [`ResourceErrorKind::AccessDenied`], even if the
+/// resource exists.
+///
+/// A view can only be narrowed with [`narrow`], so code that receives a view
+/// cannot gain access to types that the view does not allow.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::any::TypeId;
+///
+/// use resman::{ResourceErrorKind, Resources};
+///
+/// let mut resources = Resources::new();
+/// resources.insert(1u32);
+/// resources.insert(2u64);
+/// resources.insert(3i32);
+///
+/// let view = resources.view(&[TypeId::of::<u32>()], &[TypeId::of::<u64>()]);
+///
+/// *view.borrow_mut::<u64>() += *view.borrow::<u32>() as u64;
+/// assert_eq!(3, *view.borrow::<u64>());
+///
+/// assert_eq!(
+///     Some(ResourceErrorKind::AccessDenied),
+///     view.try_borrow_mut::<u32>().err().map(|error| error.kind())
+/// );
+/// assert!(view.try_borrow::<i32>().is_err());
+/// ```
+///
+/// [`Resources::view`]: crate::Resources::view
+/// [`ResourcesViewBuilder`]: crate::ResourcesViewBuilder
+/// [`narrow`]: Self::narrow
+#[derive(Clone, Debug)]
+pub struct ResourcesView<'r> {
+    /// The underlying resources.
+    resources: &'r Resources,
+    /// Types that may be borrowed immutably.
+    reads: BTreeSet<TypeId>,
+    /// Types that may be borrowed mutably or immutably.
+    writes: BTreeSet<TypeId>,
+}
+
+impl<'r> ResourcesView<'r> {
+    /// Returns a new `ResourcesView` that allows the given types to be
+    /// borrowed.
+    pub(crate) fn new(
+        resources: &'r Resources,
+        reads: BTreeSet<TypeId>,
+        writes: BTreeSet<TypeId>,
+    ) -> Self {
+        Self {
+            resources,
+            reads,
+            writes,
+        }
+    }
+
+    /// Returns a view that only allows the given types, and only as far as
+    /// this view allows them.
+    ///
+    /// Types that this view does not allow are ignored, and types in `write`
+    /// that this view only allows to be read may only be read.
+    pub fn narrow(&self, read: &[TypeId], write: &[TypeId]) -> ResourcesView<'r> {
+        let writes = write
+            .iter()
+            .copied()
+            .filter(|type_id| self.allows(*type_id, BorrowKind::Mutable))
+            .collect::<BTreeSet<_>>();
+        let reads = read
+            .iter()
+            .chain(write)
+            .copied()
+            .filter(|type_id| !writes.contains(type_id))
+            .filter(|type_id| self.allows(*type_id, BorrowKind::Immutable))
+            .collect();
+
+        Self::new(self.resources, reads, writes)
+    }
+
+    /// Returns whether the view allows the given type to be borrowed with the
+    /// given borrow kind.
+    pub fn allows(&self, type_id: TypeId, borrow_kind: BorrowKind) -> bool {
+        match borrow_kind {
+            BorrowKind::Immutable => {
+                self.reads.contains(&type_id) || self.writes.contains(&type_id)
+            }
+            BorrowKind::Mutable => self.writes.contains(&type_id),
+        }
+    }
+
+    /// Returns true if `R` exists and the view allows it to be borrowed.
+    pub fn contains<R>(&self) -> bool
+    where
+        R: Resource,
+    {
+        self.allows(TypeId::of::<R>(), BorrowKind::Immutable) && self.resources.contains::<R>()
+    }
+
+    /// Returns the `R` resource.
+    ///
+    /// See [`try_borrow`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the view does not allow `R` to be borrowed.
+    /// Panics if the resource doesn't exist.
+    /// Panics if the resource is being accessed mutably.
+    ///
+    /// [`try_borrow`]: Self::try_borrow
+    pub fn borrow<R>(&self) -> Ref<'r, R>
+    where
+        R: Resource,
+    {
+        self.try_borrow::<R>()
+            .unwrap_or_else(Resources::borrow_panic)
+    }
+
+    /// Returns the `R` resource.
+    ///
+    /// Returns an error if the view does not allow `R` to be borrowed, or the
+    /// resource cannot be borrowed.
+    pub fn try_borrow<R>(&self) -> Result<Ref<'r, R>, ResourceError>
+    where
+        R: Resource,
+    {
+        self.access_check::<R>(BorrowKind::Immutable)?;
+        self.resources.try_borrow::<R>()
+    }
+
+    /// Returns the `R` resource mutably.
+    ///
+    /// See [`try_borrow_mut`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the view does not allow `R` to be borrowed mutably.
+    /// Panics if the resource doesn't exist.
+    /// Panics if the resource is already accessed.
+    ///
+    /// [`try_borrow_mut`]: Self::try_borrow_mut
+    pub fn borrow_mut<R>(&self) -> RefMut<'r, R>
+    where
+        R: Resource,
+    {
+        self.try_borrow_mut::<R>()
+            .unwrap_or_else(Resources::borrow_panic)
+    }
+
+    /// Returns the `R` resource mutably.
+    ///
+    /// Returns an error if the view does not allow `R` to be borrowed mutably,
+    /// or the resource cannot be borrowed.
+    pub fn try_borrow_mut<R>(&self) -> Result<RefMut<'r, R>, ResourceError>
+    where
+        R: Resource,
+    {
+        self.access_check::<R>(BorrowKind::Mutable)?;
+        self.resources.try_borrow_mut::<R>()
+    }
+
+    /// Returns an `AccessDenied` error if the view does not allow `R` to be
+    /// borrowed with the given borrow kind.
+    fn access_check<R>(&self, borrow_kind: BorrowKind) -> Result<(), ResourceError>
+    where
+        R: Resource,
+    {
+        if self.allows(TypeId::of::<R>(), borrow_kind) {
+            Ok(())
+        } else {
+            Err(ResourceError::new(
+                core::any::type_name::<R>(),
+                Some(borrow_kind),
+                ResourceErrorKind::AccessDenied,
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::any::TypeId;
+
+    use crate::{BorrowKind, ResourceErrorKind, Resources};
+
+    #[test]
+    fn view_builder_allows_listed_types_with_listed_mutability() {
+        let resources = resources();
+        let view = resources
+            .view_builder()
+            .with_read::<A>()
+            .with_write::<B>()
+            .build();
+
+        assert!(view.allows(TypeId::of::<A>(), BorrowKind::Immutable));
+        assert!(!view.allows(TypeId::of::<A>(), BorrowKind::Mutable));
+        assert!(view.allows(TypeId::of::<B>(), BorrowKind::Mutable));
+        assert!(!view.allows(TypeId::of::<C>(), BorrowKind::Immutable));
+    }
+
+    #[test]
+    fn view_cannot_gain_access_to_types_not_granted() {
+        let resources = resources();
+        let view = resources.view(&[TypeId::of::<A>()], &[TypeId::of::<B>()]);
+
+        let narrowed = view.clone().narrow(
+            &[TypeId::of::<C>()],
+            &[TypeId::of::<A>(), TypeId::of::<B>()],
+        );
+
+        assert_eq!(
+            Some(ResourceErrorKind::AccessDenied),
+            narrowed.try_borrow::<C>().err().map(|error| error.kind())
+        );
+        assert_eq!(
+            Some(ResourceErrorKind::AccessDenied),
+            narrowed
+                .try_borrow_mut::<A>()
+                .err()
+                .map(|error| error.kind())
+        );
+        assert_eq!(1, narrowed.borrow::<A>().0);
+        narrowed.borrow_mut::<B>().0 += 1;
+
+        let narrowed = narrowed.narrow(&[TypeId::of::<B>()], &[]);
+        assert!(!narrowed.allows(TypeId::of::<A>(), BorrowKind::Immutable));
+        assert!(!narrowed.allows(TypeId::of::<B>(), BorrowKind::Mutable));
+        assert_eq!(3, narrowed.borrow::<B>().0);
+    }
+
+    #[test]
+    fn view_allows_listed_types_with_listed_mutability() {
+        let resources = resources();
+        let view = resources.view(&[TypeId::of::<A>()], &[TypeId::of::<B>()]);
+
+        assert_eq!(1, view.borrow::<A>().0);
+        assert_eq!(2, view.borrow::<B>().0);
+        view.borrow_mut::<B>().0 = 3;
+        assert_eq!(3, resources.borrow::<B>().0);
+
+        assert!(view.contains::<A>());
+        assert!(!view.contains::<C>());
+    }
+
+    #[test]
+    fn try_borrow_returns_access_denied_when_type_not_listed() {
+        let resources = resources();
+        let view = resources.view(&[TypeId::of::<A>()], &[]);
+
+        let error = view.try_borrow_mut::<A>().unwrap_err();
+        assert_eq!(ResourceErrorKind::AccessDenied, error.kind());
+        assert_eq!("resman::resources_view::tests::A", error.type_name());
+        assert_eq!(Some(BorrowKind::Mutable), error.borrow_kind());
+
+        let error = view.try_borrow::<C>().unwrap_err();
+        assert_eq!(ResourceErrorKind::AccessDenied, error.kind());
+        assert_eq!("resman::resources_view::tests::C", error.type_name());
+        assert_eq!(Some(BorrowKind::Immutable), error.borrow_kind());
+    }
+
+    #[test]
+    fn try_borrow_mut_returns_borrow_fail_when_allowed_but_borrowed() {
+        let resources = resources();
+        let view = resources.view(&[], &[TypeId::of::<B>()]);
+
+        let _b = resources.borrow::<B>();
+
+        assert_eq!(
+            Some(ResourceErrorKind::ConflictShared),
+            view.try_borrow_mut::<B>().err().map(|error| error.kind())
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `resman::resources_view::tests::C` immutably, but access was denied by the `ResourcesView`."
+    )]
+    fn borrow_panics_when_access_denied() {
+        let resources = resources();
+        let view = resources.view(&[TypeId::of::<A>()], &[]);
+
+        view.borrow::<C>();
+    }
+
+    fn resources() -> Resources {
+        let mut resources = Resources::new();
+        resources.insert(A(1));
+        resources.insert(B(2));
+        resources.insert(C);
+        resources
+    }
+
+    #[derive(Debug)]
+    struct A(u32);
+    #[derive(Debug)]
+    struct B(u32);
+    #[derive(Debug)]
+    struct C;
+}
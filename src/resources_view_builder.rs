@@ -0,0 +1,80 @@
+use alloc::collections::BTreeSet;
+use core::any::TypeId;
+
+use crate::{Resource, Resources, ResourcesView};
+
+/// Builds a [`ResourcesView`] by listing the types it allows.
+///
+/// Created by [`Resources::view_builder`]. Types can only be added to the
+/// builder, and the built view cannot be widened, so code that receives a
+/// `ResourcesView` cannot gain access to other resources.
+///
+/// # Examples
+///
+/// ```rust
+/// use resman::{ResourceErrorKind, Resources};
+///
+/// let mut resources = Resources::new();
+/// resources.insert(1u32);
+/// resources.insert(2u64);
+///
+/// let view = resources
+///     .view_builder()
+///     .with_read::<u32>()
+///     .with_write::<u64>()
+///     .build();
+///
+/// *view.borrow_mut::<u64>() += *view.borrow::<u32>() as u64;
+/// assert_eq!(3, *view.borrow::<u64>());
+/// assert_eq!(
+///     Some(ResourceErrorKind::AccessDenied),
+///     view.try_borrow_mut::<u32>().err().map(|error| error.kind())
+/// );
+/// ```
+///
+/// [`Resources::view_builder`]: crate::Resources::view_builder
+#[derive(Debug)]
+pub struct ResourcesViewBuilder<'r> {
+    /// The underlying resources.
+    resources: &'r Resources,
+    /// Types that may be borrowed immutably.
+    reads: BTreeSet<TypeId>,
+    /// Types that may be borrowed mutably or immutably.
+    writes: BTreeSet<TypeId>,
+}
+
+impl<'r> ResourcesViewBuilder<'r> {
+    /// Returns a new `ResourcesViewBuilder` that allows no types.
+    pub(crate) fn new(resources: &'r Resources) -> Self {
+        Self {
+            resources,
+            reads: BTreeSet::new(),
+            writes: BTreeSet::new(),
+        }
+    }
+
+    /// Allows `R` to be borrowed immutably.
+    #[must_use]
+    pub fn with_read<R>(mut self) -> Self
+    where
+        R: Resource,
+    {
+        self.reads.insert(TypeId::of::<R>());
+        self
+    }
+
+    /// Allows `R` to be borrowed mutably or immutably.
+    #[must_use]
+    pub fn with_write<R>(mut self) -> Self
+    where
+        R: Resource,
+    {
+        self.writes.insert(TypeId::of::<R>());
+        self
+    }
+
+    /// Returns the `ResourcesView` that allows the listed types.
+    pub fn build(self) -> ResourcesView<'r> {
+        ResourcesView::new(self.resources, self.reads, self.writes)
+    }
+}
@@ -0,0 +1,172 @@
+//! Suggestions for names that were looked up but not found.
+
+use alloc::{format, string::String, vec, vec::Vec};
+
+/// Maximum number of suggestions returned by [`similar_names`].
+const SUGGESTIONS_MAX: usize = 3;
+
+/// Returns the candidates that are similar to `name`, most similar first.
+///
+/// Names are compared case insensitively, and candidates are also compared by
+/// their last path segment, so `config` is similar to `my_crate::Config`.
+/// Generic parameters are compared with the other name's last path segment, so
+/// `Config` and `Arc<Config>` are similar to each other.
+pub(crate) fn similar_names<'n, I>(name: &str, candidates: I) -> Vec<&'n str>
+where
+    I: IntoIterator<Item = &'n str>,
+{
+    let name = name.to_lowercase();
+    let name_short = short_name(&name);
+
+    let mut similar = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let candidate_lower = candidate.to_lowercase();
+            let candidate_short = short_name(&candidate_lower);
+            let distance_params = generic_params(&candidate_lower)
+                .map(|param| edit_distance(name_short, short_name(param)))
+                .chain(
+                    generic_params(&name)
+                        .map(|param| edit_distance(short_name(param), candidate_short)),
+                )
+                .min()
+                .unwrap_or(usize::MAX);
+            let distance = edit_distance(&name, &candidate_lower)
+                .min(edit_distance(name_short, candidate_short))
+                .min(distance_params);
+            let distance_max = (name_short.chars().count() / 3).max(1);
+
+            (distance <= distance_max).then_some((distance, candidate))
+        })
+        .collect::<Vec<_>>();
+
+    similar.sort();
+    similar
+        .into_iter()
+        .take(SUGGESTIONS_MAX)
+        .map(|(_distance, candidate)| candidate)
+        .collect()
+}
+
+/// Returns ` Did you mean: `a`, `b`?` for the given suggestions, or an empty
+/// string if there are none.
+pub(crate) fn did_you_mean<S>(suggestions: &[S]) -> String
+where
+    S: AsRef<str>,
+{
+    if suggestions.is_empty() {
+        return String::new();
+    }
+
+    let suggestions = suggestions
+        .iter()
+        .map(|suggestion| format!("`{}`", suggestion.as_ref()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(" Did you mean: {suggestions}?")
+}
+
+/// Returns the last path segment of a type name, ignoring generic parameters.
+pub(crate) fn short_name(name: &str) -> &str {
+    let name_without_params = name.split('<').next().unwrap_or(name);
+    match name_without_params.rfind("::") {
+        Some(index) => &name[index + 2..],
+        None => name,
+    }
+}
+
+/// Returns the type names within the generic parameters of a type name, e.g.
+/// `a::B` and `C` for `Map<a::B, &mut C>`.
+fn generic_params(name: &str) -> impl Iterator<Item = &str> {
+    let params = name.find('<').map_or("", |start| &name[start + 1..]);
+    params
+        .split(|c: char| "<>,()[];&* ".contains(c))
+        .filter(|param| !matches!(*param, "" | "mut" | "const" | "dyn"))
+}
+
+/// Returns the number of single character edits to turn `a` into `b`, where
+/// swapping two adjacent characters counts as one edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a_chars = a.chars().collect::<Vec<_>>();
+    let b_chars = b.chars().collect::<Vec<_>>();
+    let b_len = b_chars.len();
+
+    // `distances[i][j]` is the distance between `a[..i]` and `b[..j]`.
+    let mut distances = vec![vec![0; b_len + 1]; a_chars.len() + 1];
+    distances
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, row)| row[0] = i);
+    (0..=b_len).for_each(|j| distances[0][j] = j);
+
+    (1..=a_chars.len()).for_each(|i| {
+        (1..=b_len).for_each(|j| {
+            let cost = usize::from(a_chars[i - 1] != b_chars[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+
+            if i > 1
+                && j > 1
+                && a_chars[i - 1] == b_chars[j - 2]
+                && a_chars[i - 2] == b_chars[j - 1]
+            {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+
+            distances[i][j] = distance;
+        });
+    });
+
+    distances[a_chars.len()][b_len]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{did_you_mean, edit_distance, generic_params, similar_names};
+
+    #[test]
+    fn edit_distance_counts_edits() {
+        assert_eq!(0, edit_distance("config", "config"));
+        assert_eq!(1, edit_distance("config", "confg"));
+        assert_eq!(1, edit_distance("config", "cnofig"));
+        assert_eq!(2, edit_distance("config", "cnofgi"));
+        assert_eq!(6, edit_distance("", "config"));
+    }
+
+    #[test]
+    fn similar_names_matches_case_insensitively_and_by_short_name() {
+        let candidates = ["my_crate::Config", "Configs", "Window", "u32"];
+
+        assert_eq!(
+            vec!["my_crate::Config", "Configs"],
+            similar_names("config", candidates)
+        );
+        assert_eq!(vec!["Window"], similar_names("Windwo", candidates));
+        assert!(similar_names("Renderer", candidates).is_empty());
+    }
+
+    #[test]
+    fn similar_names_matches_generic_params() {
+        let candidates = ["alloc::sync::Arc<app::Config>", "app::Window", "Vec<u8>"];
+
+        assert_eq!(
+            vec!["alloc::sync::Arc<app::Config>"],
+            similar_names("other_app::Config", candidates)
+        );
+        assert_eq!(
+            vec!["app::Window"],
+            similar_names("alloc::rc::Rc<app::Window>", candidates)
+        );
+        assert_eq!(
+            vec!["a::B", "C"],
+            generic_params("Map<a::B, &mut C>").collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn did_you_mean_lists_suggestions() {
+        assert_eq!("", did_you_mean::<&str>(&[]));
+        assert_eq!(" Did you mean: `A`, `B`?", did_you_mean(&["A", "B"]));
+    }
+}
@@ -0,0 +1,140 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt;
+
+#[cfg(feature = "std")]
+use alloc::sync::Weak;
+
+use crate::Resource;
+
+#[cfg(feature = "std")]
+use crate::watch_state::WatchState;
+
+/// Callback that runs when a resource is changed.
+pub(crate) type SubscriberFn = Box<dyn Fn(&dyn Resource) + Send + Sync>;
+
+/// Callbacks and watchers subscribed to changes of a resource.
+#[derive(Default)]
+pub(crate) struct Subscribers {
+    /// ID of the next subscriber.
+    id_next: usize,
+    /// Subscriber callbacks, with their IDs.
+    subscribers: Vec<(usize, SubscriberFn)>,
+    /// State of each `Watcher`, which is dropped with the `Watcher`.
+    #[cfg(feature = "std")]
+    watchers: Vec<Weak<WatchState>>,
+}
+
+impl Subscribers {
+    /// Adds a subscriber, and returns its ID.
+    pub(crate) fn insert(&mut self, subscriber_fn: SubscriberFn) -> usize {
+        let id = self.id_next;
+        self.id_next += 1;
+        self.subscribers.push((id, subscriber_fn));
+        id
+    }
+
+    /// Removes the subscriber with the given ID, returning whether it existed.
+    pub(crate) fn remove(&mut self, id: usize) -> bool {
+        let len = self.subscribers.len();
+        self.subscribers
+            .retain(|(subscriber_id, _)| *subscriber_id != id);
+        self.subscribers.len() != len
+    }
+
+    /// Adds a watcher, and removes watchers that were dropped.
+    #[cfg(feature = "std")]
+    pub(crate) fn insert_watcher(&mut self, watch_state: Weak<WatchState>) {
+        self.watchers
+            .retain(|watch_state| watch_state.strong_count() > 0);
+        self.watchers.push(watch_state);
+    }
+
+    /// Returns whether there are no subscribers or live watchers.
+    ///
+    /// Watchers that were dropped are only removed in `insert_watcher`, so
+    /// they are skipped here.
+    pub(crate) fn is_empty(&self) -> bool {
+        #[cfg(feature = "std")]
+        {
+            self.subscribers.is_empty()
+                && self
+                    .watchers
+                    .iter()
+                    .all(|watch_state| watch_state.strong_count() == 0)
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            self.subscribers.is_empty()
+        }
+    }
+
+    /// Notifies each watcher that the resource changed.
+    #[cfg(feature = "std")]
+    pub(crate) fn notify_watchers(&self) {
+        self.watchers
+            .iter()
+            .filter_map(Weak::upgrade)
+            .for_each(|watch_state| watch_state.notify());
+    }
+
+    /// Runs each subscriber with the changed resource.
+    pub(crate) fn notify(&self, resource: &dyn Resource) {
+        self.subscribers
+            .iter()
+            .for_each(|(_, subscriber_fn)| subscriber_fn(resource));
+    }
+}
+
+impl fmt::Debug for Subscribers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Subscribers")
+            .field("id_next", &self.id_next)
+            .field("subscribers", &self.subscribers.len())
+            .finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for Subscribers {
+    fn drop(&mut self) {
+        self.watchers
+            .iter()
+            .filter_map(Weak::upgrade)
+            .for_each(|watch_state| watch_state.close());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::boxed::Box;
+
+    use super::Subscribers;
+
+    #[test]
+    fn is_empty_returns_true_when_subscriber_removed() {
+        let mut subscribers = Subscribers::default();
+        let id = subscribers.insert(Box::new(|_| {}));
+        assert!(!subscribers.is_empty());
+
+        subscribers.remove(id);
+
+        assert!(subscribers.is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn is_empty_returns_true_when_watchers_dropped() {
+        use alloc::sync::Arc;
+
+        use crate::watch_state::WatchState;
+
+        let mut subscribers = Subscribers::default();
+        let watch_state = Arc::new(WatchState::default());
+        subscribers.insert_watcher(Arc::downgrade(&watch_state));
+        assert!(!subscribers.is_empty());
+
+        drop(watch_state);
+
+        assert!(subscribers.is_empty());
+    }
+}
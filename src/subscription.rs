@@ -0,0 +1,48 @@
+use core::any::TypeId;
+
+use crate::resources_id::ResourcesId;
+
+/// Handle to a subscription to changes of a resource.
+///
+/// Returned by [`Resources::subscribe`], and passed to
+/// [`Resources::unsubscribe`] to stop the callback from running. Dropping the
+/// `Subscription` does not unsubscribe the callback.
+///
+/// [`Resources::subscribe`]: crate::Resources::subscribe
+/// [`Resources::unsubscribe`]: crate::Resources::unsubscribe
+#[derive(Debug, PartialEq, Eq)]
+pub struct Subscription {
+    /// ID of the `Resources` map that the subscription was made with.
+    resources_id: ResourcesId,
+    /// `TypeId` of the subscribed resource.
+    type_id: TypeId,
+    /// ID of the subscriber.
+    id: usize,
+}
+
+impl Subscription {
+    /// Returns a new `Subscription`.
+    pub(crate) fn new(resources_id: ResourcesId, type_id: TypeId, id: usize) -> Self {
+        Self {
+            resources_id,
+            type_id,
+            id,
+        }
+    }
+
+    /// Returns the ID of the `Resources` map that the subscription was made
+    /// with.
+    pub(crate) fn resources_id(&self) -> ResourcesId {
+        self.resources_id
+    }
+
+    /// Returns the `TypeId` of the subscribed resource.
+    pub(crate) fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Returns the ID of the subscriber.
+    pub(crate) fn id(&self) -> usize {
+        self.id
+    }
+}
@@ -0,0 +1,82 @@
+//! `tracing` subscriber that records entered spans and events as strings.
+
+use std::{
+    fmt::{self, Write},
+    sync::{Arc, Mutex},
+};
+
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+/// Records entered spans and events, so tests can assert on them.
+#[derive(Clone, Default)]
+pub(crate) struct TestSubscriber {
+    state: Arc<Mutex<TestSubscriberState>>,
+}
+
+#[derive(Default)]
+struct TestSubscriberState {
+    /// Name and formatted fields of each span, indexed by `Id - 1`.
+    spans: Vec<(&'static str, String)>,
+    /// `"span <name>:<fields>"` or `"event:<fields>"`, in order.
+    records: Vec<String>,
+}
+
+impl TestSubscriber {
+    /// Returns the recorded spans and events.
+    pub(crate) fn records(&self) -> Vec<String> {
+        self.state.lock().expect("Lock poisoned.").records.clone()
+    }
+}
+
+impl Subscriber for TestSubscriber {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+        let mut fields = String::new();
+        attributes.record(&mut FieldsVisitor(&mut fields));
+
+        let mut state = self.state.lock().expect("Lock poisoned.");
+        state.spans.push((attributes.metadata().name(), fields));
+        Id::from_u64(state.spans.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut state = self.state.lock().expect("Lock poisoned.");
+        let (_name, fields) = &mut state.spans[span.into_u64() as usize - 1];
+        values.record(&mut FieldsVisitor(fields));
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = String::new();
+        event.record(&mut FieldsVisitor(&mut fields));
+
+        let mut state = self.state.lock().expect("Lock poisoned.");
+        state.records.push(format!("event:{fields}"));
+    }
+
+    fn enter(&self, span: &Id) {
+        let mut state = self.state.lock().expect("Lock poisoned.");
+        let (name, fields) = &state.spans[span.into_u64() as usize - 1];
+        let record = format!("span {name}:{fields}");
+        state.records.push(record);
+    }
+
+    fn exit(&self, _span: &Id) {}
+}
+
+/// Appends ` name=value` for each field.
+struct FieldsVisitor<'s>(&'s mut String);
+
+impl Visit for FieldsVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        write!(self.0, " {}={value:?}", field.name()).expect("Failed to write field.");
+    }
+}
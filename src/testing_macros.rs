@@ -0,0 +1,265 @@
+//! Assertion macros for tests that use `Resources`.
+
+/// Asserts that a resource is equal to the expected value.
+///
+/// The resource type must implement `PartialEq` and `Debug`.
+///
+/// # Panics
+///
+/// Panics if the resource is not equal to the expected value, or cannot be
+/// borrowed.
+///
+/// # Examples
+///
+/// ```rust
+/// use resman::{assert_resource_eq, Resources};
+///
+/// let resources = Resources::builder().with(1u32).build();
+///
+/// assert_resource_eq!(resources, u32, 1);
+/// ```
+#[macro_export]
+macro_rules! assert_resource_eq {
+    ($resources:expr, $resource_type:ty, $expected:expr $(,)?) => {
+        match (&*$resources.borrow::<$resource_type>(), &$expected) {
+            (resource, expected) => {
+                if !(*resource == *expected) {
+                    ::core::panic!(
+                        "Expected resource `{}` to equal `{:?}`, but it was `{:?}`.",
+                        ::core::any::type_name::<$resource_type>(),
+                        expected,
+                        resource,
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Asserts that a resource exists and can be borrowed.
+///
+/// Use `mut` before the type to assert that it can be borrowed mutably.
+///
+/// # Panics
+///
+/// Panics if the resource does not exist, or is already borrowed in a
+/// conflicting way.
+///
+/// # Examples
+///
+/// ```rust
+/// use resman::{assert_borrowable, Resources};
+///
+/// let resources = Resources::builder().with(1u32).build();
+/// let _n = resources.borrow::<u32>();
+///
+/// assert_borrowable!(resources, u32);
+/// ```
+#[macro_export]
+macro_rules! assert_borrowable {
+    ($resources:expr, mut $resource_type:ty $(,)?) => {
+        if let ::core::result::Result::Err(error) = $resources.try_borrow_mut::<$resource_type>() {
+            ::core::panic!(
+                "Expected `{}` to be borrowable mutably, but borrowing failed: {}",
+                ::core::any::type_name::<$resource_type>(),
+                error,
+            );
+        }
+    };
+    ($resources:expr, $resource_type:ty $(,)?) => {
+        if let ::core::result::Result::Err(error) = $resources.try_borrow::<$resource_type>() {
+            ::core::panic!(
+                "Expected `{}` to be borrowable immutably, but borrowing failed: {}",
+                ::core::any::type_name::<$resource_type>(),
+                error,
+            );
+        }
+    };
+}
+
+/// Runs a resource function, and asserts that it changed the values of the
+/// listed resources.
+///
+/// Each listed resource is cloned before the function runs, and compared with
+/// its value afterwards, so a `&mut` parameter that the function does not write
+/// to is not counted as changed. Resources listed after `unchanged:` are
+/// asserted to keep their values. Evaluates to the function's return value.
+///
+/// The listed resource types must implement `Clone`, `PartialEq`, and `Debug`.
+///
+/// # Panics
+///
+/// Panics if a listed resource's value did not change, or a resource listed
+/// after `unchanged:` did.
+///
+/// # Examples
+///
+/// ```rust
+/// use resman::{assert_fn_res_changes, IntoFnRes, Resources};
+///
+/// let resources = Resources::builder().with(1u32).with(2u64).build();
+/// let fn_res = (|a: &mut u32, b: &mut u64| {
+///     *a += 1;
+///     *a as u64 + *b
+/// })
+/// .into_fn_res();
+///
+/// let sum = assert_fn_res_changes!(resources, fn_res, [u32], unchanged: [u64]);
+/// assert_eq!(4, sum);
+/// ```
+#[cfg(feature = "fn_res")]
+#[macro_export]
+macro_rules! assert_fn_res_changes {
+    (
+        $resources:expr,
+        $fn_res:expr,
+        [$($changed:ty),* $(,)?]
+        $(, unchanged: [$($unchanged:ty),* $(,)?])?
+        $(,)?
+    ) => {{
+        #[allow(unused_imports)]
+        use $crate::FnRes as _;
+
+        let resources: &$crate::Resources = &$resources;
+
+        // Each check holds a clone of the resource from before the call, and
+        // returns a message if the value afterwards is not as expected.
+        #[allow(unused_mut)]
+        let mut checks = ::std::vec::Vec::<
+            ::std::boxed::Box<dyn ::core::ops::FnOnce(&$crate::Resources) -> ::core::option::Option<::std::string::String>>,
+        >::new();
+        $(
+            let before = ::core::clone::Clone::clone(&*resources.borrow::<$changed>());
+            checks.push(::std::boxed::Box::new(move |resources: &$crate::Resources| {
+                let after = resources.borrow::<$changed>();
+                (*after == before).then(|| ::std::format!(
+                    "Expected resource function to change `{}`, but it is still `{:?}`.",
+                    ::core::any::type_name::<$changed>(),
+                    before,
+                ))
+            }));
+        )*
+        $($(
+            let before = ::core::clone::Clone::clone(&*resources.borrow::<$unchanged>());
+            checks.push(::std::boxed::Box::new(move |resources: &$crate::Resources| {
+                let after = resources.borrow::<$unchanged>();
+                (*after != before).then(|| ::std::format!(
+                    "Expected resource function to not change `{}`, but it changed from `{:?}` to `{:?}`.",
+                    ::core::any::type_name::<$unchanged>(),
+                    before,
+                    *after,
+                ))
+            }));
+        )*)?
+
+        let ret = $fn_res.call(resources);
+
+        if let ::core::option::Option::Some(message) = checks
+            .into_iter()
+            .find_map(|check| check(resources))
+        {
+            ::core::panic!("{}", message);
+        }
+
+        ret
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Resources;
+
+    #[test]
+    fn assert_resource_eq_passes_when_equal() {
+        let resources = resources();
+
+        assert_resource_eq!(resources, A, A(1));
+        assert_resource_eq!(&resources, B, B(0));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected resource `resman::testing_macros::tests::A` to equal `A(2)`, but it was `A(1)`."
+    )]
+    fn assert_resource_eq_panics_when_not_equal() {
+        let resources = resources();
+
+        assert_resource_eq!(resources, A, A(2));
+    }
+
+    #[test]
+    fn assert_borrowable_passes_when_borrowable() {
+        let resources = resources();
+        let _a = resources.borrow::<A>();
+
+        assert_borrowable!(resources, A);
+        assert_borrowable!(resources, mut B);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected `resman::testing_macros::tests::A` to be borrowable mutably, but borrowing failed: Expected to borrow `resman::testing_macros::tests::A` mutably, but it was already borrowed immutably."
+    )]
+    fn assert_borrowable_panics_when_borrowed() {
+        let resources = resources();
+        let _a = resources.borrow::<A>();
+
+        assert_borrowable!(resources, mut A);
+    }
+
+    #[cfg(feature = "fn_res")]
+    #[test]
+    fn assert_fn_res_changes_passes_when_listed_resources_changed() {
+        use crate::IntoFnRes;
+
+        let resources = resources();
+        let fn_res = (|a: &mut A, b: &mut B| {
+            a.0 += 1;
+            b.0
+        })
+        .into_fn_res();
+
+        let b = assert_fn_res_changes!(resources, fn_res, [A], unchanged: [B]);
+
+        assert_eq!(0, b);
+        assert_resource_eq!(resources, A, A(2));
+        assert_fn_res_changes!(resources, (|_a: &A| ()).into_fn_res(), []);
+    }
+
+    #[cfg(feature = "fn_res")]
+    #[test]
+    #[should_panic(
+        expected = "Expected resource function to change `resman::testing_macros::tests::A`, but it is still `A(1)`."
+    )]
+    fn assert_fn_res_changes_panics_when_mut_parameter_is_not_written() {
+        use crate::IntoFnRes;
+
+        let resources = resources();
+        let fn_res = (|a: &mut A, b: &mut B| b.0 += a.0).into_fn_res();
+
+        assert_fn_res_changes!(resources, fn_res, [B, A]);
+    }
+
+    #[cfg(feature = "fn_res")]
+    #[test]
+    #[should_panic(
+        expected = "Expected resource function to not change `resman::testing_macros::tests::B`, but it changed from `B(0)` to `B(1)`."
+    )]
+    fn assert_fn_res_changes_panics_when_unchanged_resource_changes() {
+        use crate::IntoFnRes;
+
+        let resources = resources();
+        let fn_res = (|b: &mut B| b.0 += 1).into_fn_res();
+
+        assert_fn_res_changes!(resources, fn_res, [], unchanged: [B]);
+    }
+
+    fn resources() -> Resources {
+        Resources::builder().with(A(1)).with_default::<B>().build()
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct A(u32);
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct B(u32);
+}
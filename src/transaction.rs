@@ -0,0 +1,198 @@
+use crate::{Ref, RefMut, Resource, ResourceError, Resources};
+
+/// Staged changes to resources, applied only if the transaction succeeds.
+///
+/// Created by [`Resources::transaction`]. The first time a resource is
+/// borrowed mutably, it is cloned into the transaction, and later borrows of
+/// that resource use the staged copy.
+///
+/// [`Resources::transaction`]: crate::Resources::transaction
+#[derive(Debug)]
+pub struct Transaction<'r> {
+    /// The resources the transaction was started on.
+    resources: &'r Resources,
+    /// Copies of the resources that have been borrowed mutably.
+    staged: Resources,
+}
+
+impl<'r> Transaction<'r> {
+    /// Returns a new `Transaction` over the given resources.
+    pub(crate) fn new(resources: &'r Resources) -> Self {
+        Self {
+            resources,
+            staged: Resources::new(),
+        }
+    }
+
+    /// Returns the `R` resource, from the staged copy if it has been borrowed
+    /// mutably in this transaction.
+    ///
+    /// See [`try_borrow`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource doesn't exist.
+    ///
+    /// [`try_borrow`]: Self::try_borrow
+    pub fn borrow<R>(&self) -> Ref<'_, R>
+    where
+        R: Resource,
+    {
+        if self.staged.contains::<R>() {
+            self.staged.borrow::<R>()
+        } else {
+            self.resources.borrow::<R>()
+        }
+    }
+
+    /// Returns the `R` resource, from the staged copy if it has been borrowed
+    /// mutably in this transaction.
+    pub fn try_borrow<R>(&self) -> Result<Ref<'_, R>, ResourceError>
+    where
+        R: Resource,
+    {
+        if self.staged.contains::<R>() {
+            self.staged.try_borrow::<R>()
+        } else {
+            self.resources.try_borrow::<R>()
+        }
+    }
+
+    /// Returns the staged copy of the `R` resource, cloning it from the
+    /// original resource the first time it is borrowed.
+    ///
+    /// See [`try_borrow_mut`] for a non-panicking version of this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource doesn't exist.
+    ///
+    /// [`try_borrow_mut`]: Self::try_borrow_mut
+    pub fn borrow_mut<R>(&mut self) -> RefMut<'_, R>
+    where
+        R: Resource + Clone,
+    {
+        self.stage::<R>().unwrap_or_else(Resources::borrow_panic);
+        self.staged.borrow_mut::<R>()
+    }
+
+    /// Returns the staged copy of the `R` resource, cloning it from the
+    /// original resource the first time it is borrowed.
+    pub fn try_borrow_mut<R>(&mut self) -> Result<RefMut<'_, R>, ResourceError>
+    where
+        R: Resource + Clone,
+    {
+        self.stage::<R>()?;
+        self.staged.try_borrow_mut::<R>()
+    }
+
+    /// Returns whether the `R` resource has a staged copy.
+    pub fn is_staged<R>(&self) -> bool
+    where
+        R: Resource,
+    {
+        self.staged.contains::<R>()
+    }
+
+    /// Clones `R` into the staged resources, if it is not already staged.
+    fn stage<R>(&mut self) -> Result<(), ResourceError>
+    where
+        R: Resource + Clone,
+    {
+        if !self.staged.contains::<R>() {
+            let resource = R::clone(&*self.resources.try_borrow::<R>()?);
+            self.staged.insert(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the staged resources, to replace the originals.
+    pub(crate) fn into_staged(self) -> Resources {
+        self.staged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{self, AssertUnwindSafe};
+
+    use crate::{ResourceErrorKind, Resources};
+
+    #[test]
+    fn transaction_replaces_resources_when_ok() {
+        let mut resources = resources();
+
+        let result = resources.transaction(|tx| {
+            tx.borrow_mut::<A>().0 += 1;
+            tx.borrow_mut::<A>().0 += 1;
+
+            assert!(tx.is_staged::<A>());
+            assert!(!tx.is_staged::<B>());
+            assert_eq!(3, tx.borrow::<A>().0);
+            assert_eq!(2, tx.borrow::<B>().0);
+
+            Ok::<_, ()>(tx.borrow::<A>().0)
+        });
+
+        assert_eq!(Ok(3), result);
+        assert_eq!(A(3), *resources.borrow::<A>());
+        assert_eq!(B(2), *resources.borrow::<B>());
+        assert!(resources.outstanding_borrows().is_empty());
+    }
+
+    #[test]
+    fn transaction_discards_staged_resources_when_err() {
+        let mut resources = resources();
+
+        let result = resources.transaction(|tx| {
+            tx.borrow_mut::<A>().0 = 10;
+            tx.borrow_mut::<B>().0 = 20;
+
+            Err::<(), _>("failed")
+        });
+
+        assert_eq!(Err("failed"), result);
+        assert_eq!(A(1), *resources.borrow::<A>());
+        assert_eq!(B(2), *resources.borrow::<B>());
+    }
+
+    #[test]
+    fn transaction_discards_staged_resources_when_panicking() {
+        let mut resources = resources();
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            resources.transaction(|tx| -> Result<(), ()> {
+                tx.borrow_mut::<A>().0 = 10;
+                panic!("failed")
+            })
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(A(1), *resources.borrow::<A>());
+    }
+
+    #[test]
+    fn try_borrow_mut_returns_err_when_resource_does_not_exist() {
+        let mut resources = Resources::new();
+
+        let result = resources.transaction(|tx| tx.try_borrow_mut::<A>().map(|a| a.0));
+
+        assert_eq!(
+            Err(ResourceErrorKind::NotFound),
+            result.map_err(|error| error.kind())
+        );
+    }
+
+    fn resources() -> Resources {
+        let mut resources = Resources::new();
+        resources.insert(A(1));
+        resources.insert(B(2));
+        resources
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct A(u32);
+    #[derive(Clone, Debug, PartialEq)]
+    struct B(u32);
+}
@@ -0,0 +1,278 @@
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use core::{any::TypeId, fmt};
+
+use crate::{similar_names::similar_names, Resource, ResourceByNameError};
+
+/// Constructs a boxed resource.
+type Constructor = Arc<dyn Fn() -> Box<dyn Resource> + Send + Sync>;
+
+/// Map of type names to resource types, used to access resources by name.
+///
+/// This is useful when resource types are only known as strings at runtime,
+/// such as from a configuration file. Each name maps to the type's `TypeId`
+/// and a constructor for the resource.
+///
+/// # Examples
+///
+/// ```rust
+/// use resman::{Resources, TypeRegistry};
+///
+/// #[derive(Debug, Default)]
+/// struct Config(u32);
+///
+/// let mut type_registry = TypeRegistry::new();
+/// type_registry.register_as::<Config>("Config");
+/// type_registry.register_as::<Config>("config");
+///
+/// let mut resources = Resources::new();
+/// resources.insert_by_name(&type_registry, "config").unwrap();
+///
+/// assert!(resources.contains_by_name(&type_registry, "Config"));
+///
+/// let config = resources.borrow_by_name(&type_registry, "Config");
+/// assert_eq!(
+///     Some(0),
+///     config.downcast_ref::<Config>().map(|config| config.0)
+/// );
+///
+/// let error = resources
+///     .try_borrow_by_name(&type_registry, "Confg")
+///     .err()
+///     .unwrap();
+/// assert_eq!(
+///     "Type `Confg` is not registered in the `TypeRegistry`. Did you mean: `Config`, `config`?",
+///     error.to_string()
+/// );
+/// ```
+#[derive(Clone, Default)]
+pub struct TypeRegistry {
+    /// The registered types, ordered by name.
+    registrations: BTreeMap<String, TypeRegistration>,
+}
+
+/// Type ID and constructor for a registered type.
+#[derive(Clone)]
+struct TypeRegistration {
+    /// `TypeId` of the resource.
+    type_id: TypeId,
+    /// Full type name of the resource.
+    type_name: &'static str,
+    /// Constructs the resource.
+    constructor: Constructor,
+}
+
+impl TypeRegistry {
+    /// Returns an empty `TypeRegistry`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `R` under its short type name, e.g. `Config` or
+    /// `Option<String>`, constructed with `R::default()`.
+    ///
+    /// The short type name is the same as what [`tynm::type_name`] returns.
+    ///
+    /// [`tynm::type_name`]: https://docs.rs/tynm/latest/tynm/fn.type_name.html
+    pub fn register<R>(&mut self)
+    where
+        R: Resource + Default,
+    {
+        self.register_with(tynm::type_name::<R>(), R::default);
+    }
+
+    /// Registers `R` under the given name, constructed with `R::default()`.
+    ///
+    /// Use this to register aliases for a type.
+    pub fn register_as<R>(&mut self, name: impl Into<String>)
+    where
+        R: Resource + Default,
+    {
+        self.register_with(name, R::default);
+    }
+
+    /// Registers `R` under the given name, constructed with the given
+    /// function.
+    ///
+    /// If a type was already registered under that name, it is replaced.
+    pub fn register_with<R, F>(&mut self, name: impl Into<String>, constructor: F)
+    where
+        R: Resource,
+        F: Fn() -> R + Send + Sync + 'static,
+    {
+        let type_registration = TypeRegistration {
+            type_id: TypeId::of::<R>(),
+            type_name: core::any::type_name::<R>(),
+            constructor: Arc::new(move || Box::new(constructor())),
+        };
+        self.registrations.insert(name.into(), type_registration);
+    }
+
+    /// Removes the registration for the given name, returning whether it
+    /// existed.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.registrations.remove(name).is_some()
+    }
+
+    /// Returns the `TypeId` registered under the given name.
+    pub fn type_id(&self, name: &str) -> Option<TypeId> {
+        self.registrations
+            .get(name)
+            .map(|type_registration| type_registration.type_id)
+    }
+
+    /// Returns a new resource of the type registered under the given name.
+    pub fn construct(&self, name: &str) -> Option<Box<dyn Resource>> {
+        self.registrations
+            .get(name)
+            .map(|type_registration| (type_registration.constructor)())
+    }
+
+    /// Returns whether a type is registered under the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.registrations.contains_key(name)
+    }
+
+    /// Returns the number of registered names.
+    pub fn len(&self) -> usize {
+        self.registrations.len()
+    }
+
+    /// Returns whether no names are registered.
+    pub fn is_empty(&self) -> bool {
+        self.registrations.is_empty()
+    }
+
+    /// Returns an iterator over the registered names, in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.registrations.keys().map(String::as_str)
+    }
+
+    /// Returns the registered names that are similar to the given name, most
+    /// similar first.
+    pub fn similar_names(&self, name: &str) -> Vec<&str> {
+        similar_names(name, self.names())
+    }
+
+    /// Returns the `TypeId` and full type name registered under the given
+    /// name, or a `NotRegistered` error with similar names.
+    pub(crate) fn try_type(
+        &self,
+        name: &str,
+    ) -> Result<(TypeId, &'static str), ResourceByNameError> {
+        self.registrations
+            .get(name)
+            .map(|type_registration| (type_registration.type_id, type_registration.type_name))
+            .ok_or_else(|| self.not_registered(name))
+    }
+
+    /// Returns a new resource of the type registered under the given name, or
+    /// a `NotRegistered` error with similar names.
+    pub(crate) fn try_construct(
+        &self,
+        name: &str,
+    ) -> Result<Box<dyn Resource>, ResourceByNameError> {
+        self.construct(name)
+            .ok_or_else(|| self.not_registered(name))
+    }
+
+    fn not_registered(&self, name: &str) -> ResourceByNameError {
+        ResourceByNameError::NotRegistered {
+            name: name.to_string(),
+            suggestions: self
+                .similar_names(name)
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Debug for TypeRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.registrations
+                    .iter()
+                    .map(|(name, type_registration)| (name, type_registration.type_id)),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::any::TypeId;
+
+    use crate::ResourceByNameError;
+
+    use super::TypeRegistry;
+
+    #[test]
+    fn register_uses_short_type_name() {
+        let mut type_registry = TypeRegistry::new();
+        type_registry.register::<S0>();
+        type_registry.register::<Option<String>>();
+
+        assert_eq!(
+            vec!["Option<String>", "S0"],
+            type_registry.names().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(TypeId::of::<S0>()), type_registry.type_id("S0"));
+    }
+
+    #[test]
+    fn register_with_uses_constructor() {
+        let mut type_registry = TypeRegistry::new();
+        type_registry.register_with("s0", || S0(5));
+
+        let resource = type_registry.construct("s0").unwrap();
+
+        assert_eq!(Some(&S0(5)), resource.downcast_ref::<S0>());
+        assert!(type_registry.construct("S0").is_none());
+    }
+
+    #[test]
+    fn remove_removes_registration() {
+        let mut type_registry = TypeRegistry::new();
+        type_registry.register::<S0>();
+
+        assert!(type_registry.remove("S0"));
+        assert!(!type_registry.remove("S0"));
+        assert!(type_registry.is_empty());
+    }
+
+    #[test]
+    fn try_type_suggests_similar_names() {
+        let mut type_registry = TypeRegistry::new();
+        type_registry.register::<S0>();
+        type_registry.register_as::<S0>("ess_zero");
+
+        assert_eq!(
+            Err(ResourceByNameError::NotRegistered {
+                name: String::from("s1"),
+                suggestions: vec![String::from("S0")],
+            }),
+            type_registry.try_type("s1")
+        );
+    }
+
+    #[test]
+    fn debug_lists_names() {
+        let mut type_registry = TypeRegistry::new();
+        type_registry.register::<S0>();
+
+        assert_eq!(
+            format!("{{\"S0\": {:?}}}", TypeId::of::<S0>()),
+            format!("{type_registry:?}")
+        );
+    }
+
+    #[derive(Debug, Default, PartialEq)]
+    struct S0(u32);
+}
@@ -0,0 +1,50 @@
+use core::marker::PhantomData;
+
+use crate::{EntryBackend, Resources};
+
+#[cfg(feature = "std")]
+use crate::KeyedEntryBackend;
+
+/// An entry to a resource that does not exist in its map.
+///
+/// Part of the [`Entry`] enum.
+///
+/// [`Entry`]: crate::Entry
+pub struct VacantEntry<'a, R, B = &'a mut Resources> {
+    /// The map that `R` will be inserted into.
+    backend: B,
+    marker: PhantomData<(&'a (), R)>,
+}
+
+impl<'a, R, B> VacantEntry<'a, R, B>
+where
+    B: EntryBackend<'a, R>,
+{
+    /// Returns a new `VacantEntry`.
+    ///
+    /// `backend` must not contain `R`.
+    pub(crate) fn new(backend: B) -> Self {
+        Self {
+            backend,
+            marker: PhantomData,
+        }
+    }
+
+    /// Inserts the resource, and returns it mutably.
+    pub fn insert(self, value: R) -> B::RefMut<'a> {
+        self.backend.insert(value)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, K, R> VacantEntry<'a, R, KeyedEntryBackend<'a, K>> {
+    /// Returns the key of this entry.
+    pub fn key(&self) -> &K {
+        self.backend.key()
+    }
+
+    /// Returns the key of this entry, without inserting a resource.
+    pub fn into_key(self) -> K {
+        self.backend.into_key()
+    }
+}
@@ -0,0 +1,66 @@
+use core::{
+    sync::atomic::{AtomicBool, Ordering},
+    task::Waker,
+};
+use std::sync::{Mutex, PoisonError};
+
+/// State shared between a `Watcher` and the `Resources` map it watches.
+#[derive(Debug, Default)]
+pub(crate) struct WatchState {
+    /// Whether the resource changed since the watcher last saw it.
+    changed: AtomicBool,
+    /// Whether the `Resources` map was dropped.
+    closed: AtomicBool,
+    /// Waker of the task waiting for a change.
+    waker: Mutex<Option<Waker>>,
+}
+
+impl WatchState {
+    /// Records a change, and wakes the waiting task.
+    pub(crate) fn notify(&self) {
+        self.changed.store(true, Ordering::Release);
+        self.wake();
+    }
+
+    /// Records that no more changes will happen, and wakes the waiting task.
+    pub(crate) fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.wake();
+    }
+
+    /// Returns whether the resource changed since the last call to
+    /// `take_changed`.
+    pub(crate) fn has_changed(&self) -> bool {
+        self.changed.load(Ordering::Acquire)
+    }
+
+    /// Returns whether the resource changed, and marks it as seen.
+    pub(crate) fn take_changed(&self) -> bool {
+        self.changed.swap(false, Ordering::AcqRel)
+    }
+
+    /// Returns whether the `Resources` map was dropped.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+
+    /// Stores the waker to wake when the resource changes.
+    pub(crate) fn register(&self, waker: &Waker) {
+        let mut waker_current = self.waker.lock().unwrap_or_else(PoisonError::into_inner);
+        match waker_current.as_mut() {
+            Some(waker_current) => waker_current.clone_from(waker),
+            None => *waker_current = Some(waker.clone()),
+        }
+    }
+
+    fn wake(&self) {
+        let waker = self
+            .waker
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use alloc::sync::Arc;
+use core::{future::Future, task::Poll};
+
+use crate::{watch_state::WatchState, WatcherClosed};
+
+/// Waits for changes to a resource.
+///
+/// Created by [`Resources::watch`]. The watcher is notified when the resource
+/// is replaced through `Resources::insert`, or when a `RefMut` that was
+/// written to is dropped. Changes that happen before the watcher sees them are
+/// combined into one notification.
+///
+/// [`changed`] is a plain `Future`, so it works with any async runtime.
+///
+/// # Examples
+///
+/// ```rust
+/// use resman::{Resources, Watcher};
+///
+/// async fn log_changes(resources: &std::sync::RwLock<Resources>, mut watcher: Watcher) {
+///     while watcher.changed().await.is_ok() {
+///         let resources = resources.read().unwrap();
+///         println!("count: {}", *resources.borrow::<u32>());
+///     }
+/// }
+///
+/// let mut resources = Resources::new();
+/// resources.insert(1u32);
+///
+/// let watcher = resources.watch::<u32>();
+/// assert!(!watcher.has_changed());
+///
+/// *resources.borrow_mut::<u32>() += 1;
+/// resources.insert(3u32);
+/// assert!(watcher.has_changed());
+/// ```
+///
+/// [`Resources::watch`]: crate::Resources::watch
+/// [`changed`]: Self::changed
+#[derive(Debug)]
+pub struct Watcher {
+    /// State shared with the `Resources` map.
+    state: Arc<WatchState>,
+}
+
+impl Watcher {
+    /// Returns a new `Watcher`.
+    pub(crate) fn new(state: Arc<WatchState>) -> Self {
+        Self { state }
+    }
+
+    /// Waits until the resource changes, and marks the change as seen.
+    ///
+    /// Completes immediately if the resource changed since the last call.
+    /// Returns an error if the `Resources` map was dropped without further
+    /// changes.
+    pub fn changed(&mut self) -> impl Future<Output = Result<(), WatcherClosed>> + '_ {
+        core::future::poll_fn(|context| {
+            if self.state.take_changed() {
+                return Poll::Ready(Ok(()));
+            }
+            if self.state.is_closed() {
+                return Poll::Ready(Err(WatcherClosed));
+            }
+
+            self.state.register(context.waker());
+
+            // Check again, in case a change happened before the waker was
+            // registered.
+            if self.state.take_changed() {
+                Poll::Ready(Ok(()))
+            } else if self.state.is_closed() {
+                Poll::Ready(Err(WatcherClosed))
+            } else {
+                Poll::Pending
+            }
+        })
+    }
+
+    /// Returns whether the resource changed since the last completed
+    /// [`changed`] call.
+    ///
+    /// [`changed`]: Self::changed
+    pub fn has_changed(&self) -> bool {
+        self.state.has_changed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        future::Future,
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    use crate::{Resources, WatcherClosed};
+
+    #[test]
+    fn changed_is_pending_until_resource_changes() {
+        let mut resources = Resources::new();
+        resources.insert(1u32);
+        let mut watcher = resources.watch::<u32>();
+
+        let mut changed = pin!(watcher.changed());
+        assert_eq!(Poll::Pending, poll(changed.as_mut()));
+
+        let _n = *resources.borrow_mut::<u32>();
+        assert_eq!(Poll::Pending, poll(changed.as_mut()));
+
+        *resources.borrow_mut::<u32>() += 1;
+        assert_eq!(Poll::Ready(Ok(())), poll(changed.as_mut()));
+    }
+
+    #[test]
+    fn changed_combines_changes_into_one_notification() {
+        let mut resources = Resources::new();
+        let mut watcher = resources.watch::<u32>();
+
+        resources.insert(1u32);
+        *resources.borrow_mut::<u32>() += 1;
+        resources.replace(3u32);
+        assert!(watcher.has_changed());
+
+        assert_eq!(Poll::Ready(Ok(())), poll(pin!(watcher.changed())));
+        assert!(!watcher.has_changed());
+        assert_eq!(Poll::Pending, poll(pin!(watcher.changed())));
+    }
+
+    #[test]
+    fn changed_returns_err_when_resources_dropped() {
+        let mut resources = Resources::new();
+        let mut watcher = resources.watch::<u32>();
+
+        drop(resources);
+
+        assert_eq!(
+            Poll::Ready(Err(WatcherClosed)),
+            poll(pin!(watcher.changed()))
+        );
+    }
+
+    fn poll<F>(future: core::pin::Pin<&mut F>) -> Poll<F::Output>
+    where
+        F: Future,
+    {
+        future.poll(&mut Context::from_waker(Waker::noop()))
+    }
+}
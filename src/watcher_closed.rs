@@ -0,0 +1,13 @@
+use core::fmt;
+
+/// Error returned by `Watcher::changed` when the `Resources` map was dropped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WatcherClosed;
+
+impl fmt::Display for WatcherClosed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The watched `Resources` map was dropped.")
+    }
+}
+
+impl std::error::Error for WatcherClosed {}